@@ -1,8 +1,27 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
-use crate::db::{ChannelStats, Database, Settings, Subscription, Video};
-use crate::ytdlp::{ChannelInfo, VideoDescription};
+use chrono::{DateTime, Utc};
+
+use crate::db::{
+    ChannelStats, Comment, Database, LiveStatus, Settings, SponsorSegment, Subscription,
+    Transcript, Video, VideoDescription,
+};
+use crate::format::{self, FormatPrefs};
+use crate::thumbnail::{self, Thumbnail};
+use crate::ytdlp::{self, ChannelInfo, ChannelTab, PlaylistEntry};
+
+/// Background SponsorBlock lookup yielding segments per video ID.
+pub type SponsorPrefetch = tokio::task::JoinHandle<Vec<(String, Vec<SponsorSegment>)>>;
+
+/// Background re-check of channels' streams tabs, yielding videos per channel ID.
+pub type LiveRefresh = tokio::task::JoinHandle<Vec<(String, Vec<Video>)>>;
+
+/// Background Shorts check over a batch of stored videos.
+pub type ShortCheck = tokio::task::JoinHandle<Vec<(String, Option<bool>)>>;
+
+/// Background thumbnail download for one video ID.
+pub type ThumbnailFetch = tokio::task::JoinHandle<(String, Result<Thumbnail, String>)>;
 
 // ── Screens & Modes ────────────────────────────────────────
 
@@ -20,16 +39,38 @@ pub enum Mode {
     Add,
     GlobalSearch,
     ConfirmDelete,
+    Rename,
     ConfirmPrime,
     ConfirmPrimeAll,
     ConfirmMarkAll,
     ConfirmMarkAllVideos,
     ConfirmChannelWatched,
     ConfirmAddChannel,
+    ConfirmSubscribeLink,
     NewSearch,
+    TranscriptSearch,
     Description,
 }
 
+// ── Info panel targets ─────────────────────────────────────
+
+/// Something selectable in the info panel: a place to start playback or a link.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InfoTarget {
+    Chapter { start: u64, title: String },
+    Timestamp { start: u64, label: String },
+    Link(String),
+}
+
+impl InfoTarget {
+    pub fn start(&self) -> Option<u64> {
+        match self {
+            InfoTarget::Chapter { start, .. } | InfoTarget::Timestamp { start, .. } => Some(*start),
+            InfoTarget::Link(_) => None,
+        }
+    }
+}
+
 // ── Auto-hide messages ─────────────────────────────────────
 
 pub struct StatusMessage {
@@ -57,6 +98,10 @@ pub struct App {
     pub fully_watched: HashSet<String>,
     pub saved_channel_index: usize,
 
+    // Archived subscriptions, listed instead of the active ones when toggled
+    pub archived_subscriptions: Vec<Subscription>,
+    pub show_archived: bool,
+
     // Video list state
     pub current_channel: Option<Subscription>,
     pub videos: Vec<Video>,
@@ -66,6 +111,12 @@ pub struct App {
     pub total_videos: usize,
     pub page_size: usize,
 
+    // Channel tabs: the tab shown for the current channel, the playlists listed
+    // on a Playlists/Podcasts tab, and a playlist opened from that list
+    pub channel_tab: ChannelTab,
+    pub channel_playlists: Vec<PlaylistEntry>,
+    pub open_playlist: Option<PlaylistEntry>,
+
     // Search state
     pub search_query: String,
     pub search_results: Vec<Video>,
@@ -96,9 +147,33 @@ pub struct App {
     pub description: Option<VideoDescription>,
     pub loading_description: bool,
     pub show_description: bool,
+    pub info_selected: usize,
+
+    // Comments overlay
+    pub comments: Option<Vec<Comment>>,
+    pub loading_comments: bool,
+    pub show_comments: bool,
+    pub comments_scroll: usize,
+
+    // Transcript overlay
+    pub transcript: Option<Transcript>,
+    pub loading_transcript: bool,
+    pub show_transcript: bool,
+    pub transcript_selected: usize,
+    pub transcript_query: String,
+
+    // Videos whose transcript matches the current filter text
+    pub transcript_matches: HashSet<String>,
+
+    // Thumbnail previews (None when disabled)
+    pub thumbnail_protocol: Option<thumbnail::Protocol>,
+    pub thumbnails: HashMap<String, Thumbnail>,
+    pub thumbnail_failed: HashSet<String>,
+    pub thumbnail_fetch: Option<ThumbnailFetch>,
 
     // Pending channel (after add for prime prompt)
     pub pending_channel: Option<ChannelInfo>,
+    pub pending_link: Option<String>,
 
     // First load tracker
     pub has_checked_for_new: bool,
@@ -108,6 +183,18 @@ pub struct App {
 
     // Channel IDs cache for all-videos view
     pub all_channel_ids: Vec<String>,
+
+    // SponsorBlock: seconds of skippable time per video, plus any background lookup
+    pub sponsor_totals: HashMap<String, f64>,
+    pub sponsor_prefetch: Option<SponsorPrefetch>,
+
+    // Periodic re-check of live and upcoming streams
+    pub live_refresh: Option<LiveRefresh>,
+    pub last_live_refresh: Option<Instant>,
+
+    // Background reclassification of guessed Shorts flags
+    pub short_check: Option<ShortCheck>,
+    pub short_check_idle_until: Option<Instant>,
 }
 
 impl App {
@@ -116,6 +203,8 @@ impl App {
         let hide_shorts = settings.hide_shorts;
         let max_resolution = settings.max_resolution.clone();
         let watched_ids = db.get_watched_ids();
+        let sponsor_totals = db.get_sponsor_totals();
+        let thumbnail_protocol = thumbnail::Protocol::from_setting(&settings.thumbnails);
 
         App {
             db,
@@ -129,6 +218,8 @@ impl App {
             channel_stats: HashMap::new(),
             fully_watched: HashSet::new(),
             saved_channel_index: 0,
+            archived_subscriptions: Vec::new(),
+            show_archived: false,
             current_channel: None,
             videos: Vec::new(),
             video_selected: 0,
@@ -136,6 +227,9 @@ impl App {
             current_page: 0,
             total_videos: 0,
             page_size: 100,
+            channel_tab: ChannelTab::Videos,
+            channel_playlists: Vec::new(),
+            open_playlist: None,
             search_query: String::new(),
             search_results: Vec::new(),
             search_selected: 0,
@@ -153,10 +247,32 @@ impl App {
             description: None,
             loading_description: false,
             show_description: false,
+            info_selected: 0,
+            comments: None,
+            loading_comments: false,
+            show_comments: false,
+            comments_scroll: 0,
+            transcript: None,
+            loading_transcript: false,
+            show_transcript: false,
+            transcript_selected: 0,
+            transcript_query: String::new(),
+            transcript_matches: HashSet::new(),
+            thumbnail_protocol,
+            thumbnails: HashMap::new(),
+            thumbnail_failed: HashSet::new(),
+            thumbnail_fetch: None,
             pending_channel: None,
+            pending_link: None,
             has_checked_for_new: false,
             playing: false,
             all_channel_ids: Vec::new(),
+            sponsor_totals,
+            sponsor_prefetch: None,
+            live_refresh: None,
+            last_live_refresh: None,
+            short_check: None,
+            short_check_idle_until: None,
         }
     }
 
@@ -171,20 +287,26 @@ impl App {
 
     pub fn load_subscriptions(&mut self) {
         self.subscriptions = self.db.get_subscriptions();
+        self.archived_subscriptions = self.db.get_archived_subscriptions();
         self.refresh_counts();
     }
 
     pub fn refresh_counts(&mut self) {
-        self.new_counts = self.db.get_new_video_counts(self.hide_shorts);
-        self.upcoming_counts = self.db.get_upcoming_video_counts(self.hide_shorts);
-        self.channel_stats = self.db.get_channel_stats(self.hide_shorts);
-        self.fully_watched = self.db.get_fully_watched_channels(self.hide_shorts);
+        let hide_vods = self.settings.hide_stream_vods;
+        self.new_counts = self.db.get_new_video_counts(self.hide_shorts, hide_vods);
+        self.upcoming_counts = self.db.get_upcoming_video_counts(self.hide_shorts, hide_vods);
+        self.channel_stats = self.db.get_channel_stats(self.hide_shorts, hide_vods);
+        self.fully_watched = self.db.get_fully_watched_channels(self.hide_shorts, hide_vods);
     }
 
     pub fn refresh_watched(&mut self) {
         self.watched_ids = self.db.get_watched_ids();
     }
 
+    pub fn refresh_sponsor_totals(&mut self) {
+        self.sponsor_totals = self.db.get_sponsor_totals();
+    }
+
     // ── Status Messages ────────────────────────────────────
 
     pub fn set_message(&mut self, msg: &str) {
@@ -214,13 +336,18 @@ impl App {
     // ── Channel List Filtering ─────────────────────────────
 
     pub fn filtered_subscriptions(&self) -> Vec<&Subscription> {
+        let source = if self.show_archived {
+            &self.archived_subscriptions
+        } else {
+            &self.subscriptions
+        };
         if self.filter_text.is_empty() {
-            self.subscriptions.iter().collect()
+            source.iter().collect()
         } else {
             let search = self.filter_text.to_lowercase();
-            self.subscriptions
+            source
                 .iter()
-                .filter(|s| s.name.to_lowercase().contains(&search))
+                .filter(|s| s.label().to_lowercase().contains(&search))
                 .collect()
         }
     }
@@ -234,10 +361,18 @@ impl App {
             &self.videos
         };
 
+        // The Shorts and Live tabs list exactly what the hide settings hide
+        let tab = (self.screen == Screen::Videos).then_some(self.channel_tab);
+        let hide_shorts = self.hide_shorts && tab != Some(ChannelTab::Shorts);
+        let hide_vods = self.settings.hide_stream_vods && tab != Some(ChannelTab::Live);
+
         source
             .iter()
             .filter(|v| {
-                if self.hide_shorts && v.is_short {
+                if hide_shorts && v.is_short {
+                    return false;
+                }
+                if hide_vods && v.live_status == Some(LiveStatus::WasLive) {
                     return false;
                 }
                 if !self.filter_text.is_empty() {
@@ -247,13 +382,112 @@ impl App {
                             .as_deref()
                             .unwrap_or("")
                             .to_lowercase()
-                            .contains(&search);
+                            .contains(&search)
+                        || self.transcript_matches.contains(&v.id);
                 }
                 true
             })
             .collect()
     }
 
+    // ── Live Status ────────────────────────────────────────
+
+    /// Carry refreshed live state and schedule into the loaded lists.
+    pub fn apply_live_updates(&mut self, fresh: &[Video]) {
+        let by_id: HashMap<&str, &Video> = fresh.iter().map(|v| (v.id.as_str(), v)).collect();
+        for video in self.videos.iter_mut().chain(self.search_results.iter_mut()) {
+            if let Some(update) = by_id.get(video.id.as_str()) {
+                if update.live_status.is_some() {
+                    video.live_status = update.live_status;
+                }
+                if update.live_status == Some(LiveStatus::Upcoming) && update.published_date.is_some() {
+                    video.published_date = update.published_date;
+                    video.relative_date = update.relative_date.clone();
+                }
+            }
+        }
+    }
+
+    /// Apply settled Shorts flags to the loaded lists.
+    pub fn apply_short_flags(&mut self, results: &[(String, Option<bool>)]) {
+        let flags: HashMap<&str, bool> = results
+            .iter()
+            .filter_map(|(id, r)| r.map(|is_short| (id.as_str(), is_short)))
+            .collect();
+        for video in self.videos.iter_mut().chain(self.search_results.iter_mut()) {
+            if let Some(&is_short) = flags.get(video.id.as_str()) {
+                video.is_short = is_short;
+            }
+        }
+    }
+
+    // ── Channel Tabs ───────────────────────────────────────
+
+    /// Whether the video screen is listing a channel's playlists instead of videos.
+    pub fn showing_playlists(&self) -> bool {
+        self.screen == Screen::Videos
+            && self.channel_tab.lists_playlists()
+            && self.open_playlist.is_none()
+    }
+
+    pub fn filtered_playlists(&self) -> Vec<&PlaylistEntry> {
+        let search = self.filter_text.to_lowercase();
+        self.channel_playlists
+            .iter()
+            .filter(|p| search.is_empty() || p.title.to_lowercase().contains(&search))
+            .collect()
+    }
+
+    /// Length of whichever list the video screen is showing.
+    pub fn video_list_len(&self) -> usize {
+        if self.showing_playlists() {
+            self.filtered_playlists().len()
+        } else {
+            self.filtered_videos().len()
+        }
+    }
+
+    /// Switch the current channel to another tab; its contents load separately.
+    pub fn switch_channel_tab(&mut self, tab: ChannelTab) {
+        self.channel_tab = tab;
+        self.channel_playlists.clear();
+        self.open_playlist = None;
+        self.videos.clear();
+        self.total_videos = 0;
+        self.video_selected = 0;
+        self.video_scroll = 0;
+        self.filter_text.clear();
+        self.close_overlays();
+    }
+
+    pub fn open_selected_playlist(&mut self) -> bool {
+        let Some(playlist) = self.filtered_playlists().get(self.video_selected).map(|p| (*p).clone())
+        else {
+            return false;
+        };
+        self.open_playlist = Some(playlist);
+        self.videos.clear();
+        self.video_selected = 0;
+        self.video_scroll = 0;
+        self.filter_text.clear();
+        true
+    }
+
+    /// Leave an opened playlist and reselect it in the playlist list.
+    pub fn close_playlist(&mut self) {
+        if let Some(playlist) = self.open_playlist.take() {
+            self.videos.clear();
+            self.filter_text.clear();
+            self.close_overlays();
+            self.video_selected = self
+                .channel_playlists
+                .iter()
+                .position(|p| p.id == playlist.id)
+                .unwrap_or(0);
+            self.video_scroll = 0;
+        }
+    }
+
     // ── Scrolling ──────────────────────────────────────────
 
     pub fn move_up(&mut self) {
@@ -276,6 +510,147 @@ impl App {
         }
     }
 
+    // ── Info Panel ─────────────────────────────────────────
+
+    /// Selectable entries in the info panel, in display order: chapters, then
+    /// timestamps from the description that aren't already chapters, then links.
+    pub fn info_targets(&self) -> Vec<InfoTarget> {
+        let Some(desc) = self.description.as_ref() else {
+            return Vec::new();
+        };
+        let mut targets: Vec<InfoTarget> = desc
+            .chapters
+            .iter()
+            .map(|c| InfoTarget::Chapter {
+                start: c.start.max(0.0) as u64,
+                title: c.title.clone(),
+            })
+            .collect();
+        let chapter_starts: HashSet<u64> = targets.iter().filter_map(|t| t.start()).collect();
+        targets.extend(
+            ytdlp::extract_timestamps(&desc.description)
+                .into_iter()
+                .filter(|(start, _)| !chapter_starts.contains(start))
+                .map(|(start, label)| InfoTarget::Timestamp { start, label }),
+        );
+        targets.extend(desc.links.iter().cloned().map(InfoTarget::Link));
+        targets
+    }
+
+    pub fn info_up(&mut self) {
+        self.info_selected = self.info_selected.saturating_sub(1);
+    }
+
+    pub fn info_down(&mut self) {
+        if self.info_selected + 1 < self.info_targets().len() {
+            self.info_selected += 1;
+        }
+    }
+
+    pub fn selected_info_target(&self) -> Option<InfoTarget> {
+        if !self.show_description {
+            return None;
+        }
+        self.info_targets().into_iter().nth(self.info_selected)
+    }
+
+    /// Look up which videos mention the filter text in their transcripts.
+    pub fn update_transcript_matches(&mut self) {
+        self.transcript_matches = if self.filter_text.trim().len() >= 3 {
+            self.db.search_transcripts(&self.filter_text)
+        } else {
+            HashSet::new()
+        };
+    }
+
+    // ── Overlays ───────────────────────────────────────────
+
+    /// Close whichever of the info, comments and transcript panels is open.
+    pub fn close_overlays(&mut self) {
+        self.show_description = false;
+        self.description = None;
+        self.close_comments();
+        self.close_transcript();
+    }
+
+    pub fn close_comments(&mut self) {
+        self.show_comments = false;
+        self.comments = None;
+        self.comments_scroll = 0;
+    }
+
+    pub fn comments_up(&mut self) {
+        self.comments_scroll = self.comments_scroll.saturating_sub(1);
+    }
+
+    pub fn comments_down(&mut self) {
+        let count = self.comments.as_ref().map(|c| c.len()).unwrap_or(0);
+        if self.comments_scroll + 1 < count {
+            self.comments_scroll += 1;
+        }
+    }
+
+    // ── Transcript ─────────────────────────────────────────
+
+    pub fn close_transcript(&mut self) {
+        self.show_transcript = false;
+        self.transcript = None;
+        self.transcript_selected = 0;
+        self.transcript_query.clear();
+    }
+
+    fn transcript_len(&self) -> usize {
+        self.transcript.as_ref().map(|t| t.lines.len()).unwrap_or(0)
+    }
+
+    pub fn transcript_up(&mut self) {
+        self.transcript_selected = self.transcript_selected.saturating_sub(1);
+    }
+
+    pub fn transcript_down(&mut self) {
+        if self.transcript_selected + 1 < self.transcript_len() {
+            self.transcript_selected += 1;
+        }
+    }
+
+    pub fn selected_transcript_start(&self) -> Option<u64> {
+        if !self.show_transcript {
+            return None;
+        }
+        self.transcript
+            .as_ref()?
+            .lines
+            .get(self.transcript_selected)
+            .map(|l| l.start.max(0.0) as u64)
+    }
+
+    /// Move to the next (or previous) line containing the transcript query,
+    /// wrapping around. Returns false when nothing matches.
+    pub fn transcript_find(&mut self, forward: bool) -> bool {
+        let query = self.transcript_query.trim().to_lowercase();
+        let lines = match self.transcript.as_ref() {
+            Some(t) if !query.is_empty() && !t.lines.is_empty() => &t.lines,
+            _ => return false,
+        };
+        let len = lines.len();
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (self.transcript_selected + step) % len
+                } else {
+                    (self.transcript_selected + len - step % len) % len
+                }
+            })
+            .find(|&i| lines[i].text.to_lowercase().contains(&query));
+        match found {
+            Some(i) => {
+                self.transcript_selected = i;
+                true
+            }
+            None => false,
+        }
+    }
+
     fn current_selection_mut(&mut self) -> (&mut usize, &mut usize) {
         match self.screen {
             Screen::Channels => (&mut self.channel_selected, &mut self.channel_scroll),
@@ -311,13 +686,15 @@ impl App {
     pub fn navigate_to_videos(&mut self, channel: Option<Subscription>, index: usize) {
         self.saved_channel_index = index;
         self.current_channel = channel;
+        self.channel_tab = ChannelTab::Videos;
+        self.channel_playlists.clear();
+        self.open_playlist = None;
         self.screen = Screen::Videos;
         self.mode = Mode::List;
         self.video_selected = 0;
         self.video_scroll = 0;
         self.filter_text.clear();
-        self.show_description = false;
-        self.description = None;
+        self.close_overlays();
     }
 
     pub fn navigate_to_search(&mut self, query: String) {
@@ -327,18 +704,19 @@ impl App {
         self.search_selected = 0;
         self.search_scroll = 0;
         self.filter_text.clear();
-        self.show_description = false;
-        self.description = None;
+        self.close_overlays();
     }
 
     pub fn navigate_back(&mut self) {
         self.screen = Screen::Channels;
         self.mode = Mode::List;
         self.current_channel = None;
+        self.channel_tab = ChannelTab::Videos;
+        self.channel_playlists.clear();
+        self.open_playlist = None;
         self.search_query.clear();
         self.filter_text.clear();
-        self.show_description = false;
-        self.description = None;
+        self.close_overlays();
 
         // Restore saved index
         self.channel_selected = self.saved_channel_index;
@@ -399,22 +777,188 @@ impl App {
         self.refresh_counts();
     }
 
-    // ── Toggle Resolution ──────────────────────────────────
+    /// Open the rename input for the selected subscription, prefilled with its
+    /// current display name.
+    pub fn start_rename(&mut self) {
+        let Some(label) = self
+            .filtered_subscriptions()
+            .get(self.channel_selected)
+            .map(|s| s.display_name.clone().unwrap_or_default())
+        else {
+            return;
+        };
+        self.input_clear();
+        for c in label.chars() {
+            self.input_insert(c);
+        }
+        self.mode = Mode::Rename;
+    }
+
+    /// Rename the selected subscription; an empty name restores the upstream one.
+    pub fn rename_selected(&mut self, name: &str) {
+        self.mode = Mode::List;
+        self.input_clear();
+        let Some(id) = self
+            .filtered_subscriptions()
+            .get(self.channel_selected)
+            .map(|s| s.id.clone())
+        else {
+            return;
+        };
+        let name = Some(name.trim()).filter(|n| !n.is_empty());
+        match self.db.rename_subscription(&id, name) {
+            Ok(()) => {
+                self.load_subscriptions();
+                // Follow the renamed row; if it no longer matches the filter,
+                // stay put but keep the selection inside the shorter list.
+                let visible = self.filtered_subscriptions();
+                self.channel_selected = visible
+                    .iter()
+                    .position(|s| s.id == id)
+                    .unwrap_or_else(|| self.channel_selected.min(visible.len().saturating_sub(1)));
+                let label = self
+                    .subscriptions
+                    .iter()
+                    .chain(self.archived_subscriptions.iter())
+                    .find(|s| s.id == id)
+                    .map(|s| s.label().to_string())
+                    .unwrap_or_default();
+                self.set_message(&format!("Renamed to {}", label));
+            }
+            Err(e) => self.set_error(&e),
+        }
+    }
 
-    pub fn toggle_resolution(&mut self) {
-        if self.max_resolution == "1080" {
-            self.max_resolution = "max".to_string();
-            self.db
-                .update_setting("maxResolution", &serde_json::to_string("max").unwrap());
-            self.set_message("Resolution: unlimited");
+    /// Flip desktop notifications for the selected subscription.
+    pub fn toggle_selected_notify(&mut self) {
+        let Some(id) = self
+            .filtered_subscriptions()
+            .get(self.channel_selected)
+            .map(|s| s.id.clone())
+        else {
+            return;
+        };
+        let Some(sub) = self
+            .subscriptions
+            .iter_mut()
+            .chain(self.archived_subscriptions.iter_mut())
+            .find(|s| s.id == id)
+        else {
+            return;
+        };
+        sub.notify = !sub.notify;
+        let (notify, name) = (sub.notify, sub.label().to_string());
+        if let Err(e) = self.db.set_subscription_notify(&id, notify) {
+            self.set_error(&e);
+        } else if notify {
+            self.set_message(&format!("Notifying about new videos from {}", name));
         } else {
-            self.max_resolution = "1080".to_string();
-            self.db
-                .update_setting("maxResolution", &serde_json::to_string("1080").unwrap());
-            self.set_message("Resolution: 1080p max");
+            self.set_message(&format!("Notifications off for {}", name));
+        }
+    }
+
+    /// Include the selected channel's members-only videos when priming it.
+    pub fn toggle_selected_members(&mut self) {
+        let Some(id) = self
+            .filtered_subscriptions()
+            .get(self.channel_selected)
+            .map(|s| s.id.clone())
+        else {
+            return;
+        };
+        let Some(sub) = self
+            .subscriptions
+            .iter_mut()
+            .chain(self.archived_subscriptions.iter_mut())
+            .find(|s| s.id == id)
+        else {
+            return;
+        };
+        if sub.kind != crate::db::SourceKind::Channel {
+            self.set_error("Members-only videos belong to channels, not playlists");
+            return;
+        }
+        sub.members = !sub.members;
+        let (members, name) = (sub.members, sub.label().to_string());
+        if let Err(e) = self.db.set_subscription_members(&id, members) {
+            self.set_error(&e);
+        } else if !members {
+            self.set_message(&format!("Members-only videos off for {}", name));
+        } else if self.settings.cookies().is_none() {
+            self.set_message(&format!(
+                "Members-only videos on for {}; set cookiesFile or cookiesFromBrowser to fetch them",
+                name
+            ));
+        } else {
+            self.set_message(&format!("Members-only videos on for {}; prime it to fetch them", name));
+        }
+    }
+
+    // ── Archive ────────────────────────────────────────────
+
+    /// Switch the channel list between active and archived subscriptions.
+    pub fn toggle_archived_section(&mut self) {
+        self.show_archived = !self.show_archived;
+        self.filter_text.clear();
+        self.channel_selected = 0;
+        self.channel_scroll = 0;
+    }
+
+    /// Archive the selected subscription, or restore it in the Archived section.
+    pub fn toggle_selected_archived(&mut self) {
+        let Some(sub) = self
+            .filtered_subscriptions()
+            .get(self.channel_selected)
+            .map(|s| (*s).clone())
+        else {
+            return;
+        };
+        let archive = !sub.archived;
+        if let Err(e) = self.db.set_subscription_archived(&sub.id, archive) {
+            self.set_error(&e);
+            return;
+        }
+        self.load_subscriptions();
+        let len = self.filtered_subscriptions().len();
+        self.channel_selected = self.channel_selected.min(len.saturating_sub(1));
+        if archive {
+            self.set_message(&format!("Archived {}", sub.label()));
+        } else {
+            self.set_message(&format!("Restored {}", sub.label()));
+        }
+    }
+
+    /// Active subscriptions without an upload in `archive_after_days`.
+    pub fn archive_suggestions(&self) -> Vec<&Subscription> {
+        let days = self.settings.archive_after_days;
+        let now = Utc::now();
+        self.subscriptions
+            .iter()
+            .filter(|s| {
+                let latest = self.channel_stats.get(&s.id).and_then(|st| st.latest_date.as_deref());
+                is_inactive(latest, days, now)
+            })
+            .collect()
+    }
+
+    // ── Cycle Resolution ───────────────────────────────────
+
+    pub fn cycle_resolution(&mut self) {
+        self.max_resolution = format::next_resolution(&self.max_resolution).to_string();
+        self.db.update_setting(
+            "maxResolution",
+            &serde_json::to_string(&self.max_resolution).unwrap(),
+        );
+        match format::parse_height(&self.max_resolution) {
+            Some(h) => self.set_message(&format!("Resolution: {}p max", h)),
+            None => self.set_message("Resolution: unlimited"),
         }
     }
 
+    pub fn format_prefs(&self) -> FormatPrefs {
+        FormatPrefs::from_settings(&self.settings, &self.max_resolution)
+    }
+
     // ── Toggle Watched ─────────────────────────────────────
 
     pub fn toggle_watched_current(&mut self) {
@@ -450,7 +994,7 @@ impl App {
             self.refresh_watched();
             let name = self.filtered_subscriptions()
                 .get(self.channel_selected)
-                .map(|s| s.name.clone())
+                .map(|s| s.label().to_string())
                 .unwrap_or_default();
             self.set_message(&format!("Marked {} videos as watched in {}", count, name));
         }
@@ -482,6 +1026,17 @@ impl App {
     }
 }
 
+/// Whether a channel whose latest upload is `latest` (RFC 3339) has gone
+/// `days` without one. Channels with nothing stored, or `days` of 0, never are.
+fn is_inactive(latest: Option<&str>, days: u32, now: DateTime<Utc>) -> bool {
+    if days == 0 {
+        return false;
+    }
+    latest
+        .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+        .is_some_and(|d| now.signed_duration_since(d).num_days() >= days as i64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -507,6 +1062,8 @@ mod tests {
             duration: None,
             duration_string: None,
             view_count: None,
+            live_status: None,
+            thumbnail: None,
         }
     }
 
@@ -534,6 +1091,11 @@ mod tests {
             name: "Test".to_string(),
             url: "https://youtube.com/channel/ch1".to_string(),
             added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
         };
         let app = App::with_initial_channel(db, ch);
         assert_eq!(app.screen, Screen::Videos);
@@ -686,6 +1248,11 @@ mod tests {
             name: "Foo".to_string(),
             url: "https://youtube.com/channel/ch1".to_string(),
             added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
         };
         app.db.add_subscription(&sub).unwrap();
         app.load_subscriptions();
@@ -702,12 +1269,22 @@ mod tests {
             name: "Foo Bar".to_string(),
             url: "https://youtube.com/channel/ch1".to_string(),
             added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
         }).unwrap();
         app.db.add_subscription(&crate::db::Subscription {
             id: "ch2".to_string(),
             name: "Baz Qux".to_string(),
             url: "https://youtube.com/channel/ch2".to_string(),
             added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
         }).unwrap();
         app.load_subscriptions();
         app.filter_text = "foo".to_string();
@@ -787,6 +1364,11 @@ mod tests {
             name: "Test".to_string(),
             url: "https://youtube.com/channel/ch1".to_string(),
             added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
         };
         app.navigate_to_videos(Some(ch), 2);
         assert_eq!(app.screen, Screen::Videos);
@@ -818,6 +1400,11 @@ mod tests {
             name: "Test".to_string(),
             url: "https://youtube.com/channel/ch1".to_string(),
             added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
         }).unwrap();
         app.load_subscriptions();
         app.saved_channel_index = 10; // larger than subscription count
@@ -835,6 +1422,296 @@ mod tests {
         assert_eq!(app.search_selected, 0);
     }
 
+    // ── Channel tab tests ────────────────────────────────────
+
+    fn playlist(id: &str, title: &str) -> PlaylistEntry {
+        PlaylistEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            url: format!("https://www.youtube.com/playlist?list={}", id),
+            video_count: None,
+        }
+    }
+
+    #[test]
+    fn test_switch_channel_tab_to_playlists() {
+        let mut app = test_app();
+        app.screen = Screen::Videos;
+        app.videos = vec![make_video("v1", "ch1", false)];
+        app.video_selected = 0;
+        app.switch_channel_tab(ChannelTab::Playlists);
+        assert!(app.videos.is_empty());
+        assert!(app.showing_playlists());
+
+        app.channel_playlists = vec![playlist("PL1", "Talks"), playlist("PL2", "Tutorials")];
+        assert_eq!(app.video_list_len(), 2);
+        app.filter_text = "tut".to_string();
+        assert_eq!(app.video_list_len(), 1);
+    }
+
+    #[test]
+    fn test_open_and_close_playlist() {
+        let mut app = test_app();
+        app.screen = Screen::Videos;
+        app.switch_channel_tab(ChannelTab::Playlists);
+        app.channel_playlists = vec![playlist("PL1", "Talks"), playlist("PL2", "Tutorials")];
+        app.video_selected = 1;
+
+        assert!(app.open_selected_playlist());
+        assert_eq!(app.open_playlist.as_ref().unwrap().id, "PL2");
+        assert!(!app.showing_playlists());
+        assert_eq!(app.video_selected, 0);
+
+        app.videos = vec![make_video("v1", "ch1", false)];
+        app.close_playlist();
+        assert!(app.open_playlist.is_none());
+        assert!(app.videos.is_empty());
+        assert_eq!(app.video_selected, 1);
+    }
+
+    #[test]
+    fn test_shorts_and_live_tabs_ignore_hide_settings() {
+        let mut app = test_app();
+        app.screen = Screen::Videos;
+        app.hide_shorts = true;
+        app.settings.hide_stream_vods = true;
+        app.switch_channel_tab(ChannelTab::Shorts);
+        app.videos = vec![make_video("s1", "ch1", true), make_video("s2", "ch1", true)];
+        assert_eq!(app.filtered_videos().len(), 2);
+
+        app.switch_channel_tab(ChannelTab::Live);
+        let mut vod = make_video("l1", "ch1", false);
+        vod.live_status = Some(LiveStatus::WasLive);
+        app.videos = vec![vod.clone()];
+        assert_eq!(app.filtered_videos().len(), 1);
+
+        // Elsewhere the settings still apply
+        app.switch_channel_tab(ChannelTab::Videos);
+        app.videos = vec![make_video("s1", "ch1", true), vod];
+        assert!(app.filtered_videos().is_empty());
+    }
+
+    #[test]
+    fn test_navigate_back_resets_channel_tab() {
+        let mut app = test_app();
+        app.screen = Screen::Videos;
+        app.switch_channel_tab(ChannelTab::Live);
+        app.navigate_back();
+        assert_eq!(app.channel_tab, ChannelTab::Videos);
+        assert!(!app.showing_playlists());
+    }
+
+    #[test]
+    fn test_apply_short_flags() {
+        let mut app = test_app();
+        app.videos = vec![
+            make_video("v1", "ch1", false),
+            make_video("v2", "ch1", true),
+            make_video("v3", "ch1", false),
+        ];
+        app.apply_short_flags(&[
+            ("v1".to_string(), Some(true)),
+            ("v2".to_string(), Some(false)),
+            ("v3".to_string(), None),
+        ]);
+        let flags: Vec<bool> = app.videos.iter().map(|v| v.is_short).collect();
+        assert_eq!(flags, vec![true, false, false]);
+    }
+
+    // ── Info panel tests ─────────────────────────────────────
+
+    fn description_with(starts: &[f64], text: &str, links: &[&str]) -> VideoDescription {
+        VideoDescription {
+            title: "Video".to_string(),
+            description: text.to_string(),
+            channel_name: "Channel".to_string(),
+            upload_date: None,
+            duration: None,
+            view_count: None,
+            like_count: None,
+            tags: Vec::new(),
+            categories: Vec::new(),
+            links: links.iter().map(|l| l.to_string()).collect(),
+            chapters: starts
+                .iter()
+                .map(|&start| crate::db::Chapter {
+                    start,
+                    end: start + 60.0,
+                    title: format!("At {}", start),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_info_targets_order_and_dedup() {
+        let mut app = test_app();
+        app.description = Some(description_with(
+            &[0.0, 95.0],
+            "0:00 Intro\n1:35 Setup\n4:10 Bonus",
+            &["https://example.com"],
+        ));
+        assert_eq!(
+            app.info_targets(),
+            vec![
+                InfoTarget::Chapter { start: 0, title: "At 0".to_string() },
+                InfoTarget::Chapter { start: 95, title: "At 95".to_string() },
+                InfoTarget::Timestamp { start: 250, label: "Bonus".to_string() },
+                InfoTarget::Link("https://example.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_info_cursor_clamps() {
+        let mut app = test_app();
+        app.show_description = true;
+        app.description = Some(description_with(&[0.0, 95.5], "", &["https://example.com"]));
+
+        app.info_up();
+        assert_eq!(app.info_selected, 0);
+        for _ in 0..5 {
+            app.info_down();
+        }
+        assert_eq!(app.info_selected, 2);
+        assert_eq!(
+            app.selected_info_target(),
+            Some(InfoTarget::Link("https://example.com".to_string()))
+        );
+        app.info_up();
+        assert_eq!(app.selected_info_target().and_then(|t| t.start()), Some(95));
+    }
+
+    #[test]
+    fn test_selected_info_target_without_targets() {
+        let mut app = test_app();
+        assert_eq!(app.selected_info_target(), None);
+
+        app.show_description = true;
+        app.description = Some(description_with(&[], "No timestamps here", &[]));
+        app.info_down();
+        assert_eq!(app.info_selected, 0);
+        assert_eq!(app.selected_info_target(), None);
+    }
+
+    // ── Comments tests ───────────────────────────────────────
+
+    fn comment(id: &str) -> Comment {
+        Comment {
+            id: id.to_string(),
+            author: "@someone".to_string(),
+            text: "text".to_string(),
+            like_count: None,
+            timestamp: None,
+            is_reply: false,
+            is_uploader: false,
+        }
+    }
+
+    #[test]
+    fn test_comments_scroll_clamps() {
+        let mut app = test_app();
+        app.show_comments = true;
+        app.comments = Some(vec![comment("a"), comment("b")]);
+
+        app.comments_up();
+        assert_eq!(app.comments_scroll, 0);
+        app.comments_down();
+        app.comments_down();
+        assert_eq!(app.comments_scroll, 1);
+    }
+
+    #[test]
+    fn test_navigation_closes_comments() {
+        let mut app = test_app();
+        app.show_comments = true;
+        app.comments = Some(vec![comment("a")]);
+        app.comments_scroll = 1;
+        app.navigate_to_search("rust".to_string());
+
+        assert!(!app.show_comments);
+        assert!(app.comments.is_none());
+        assert_eq!(app.comments_scroll, 0);
+    }
+
+    // ── Transcript tests ─────────────────────────────────────
+
+    fn transcript_app(lines: &[&str]) -> App {
+        let mut app = test_app();
+        app.show_transcript = true;
+        app.transcript = Some(Transcript {
+            language: "en".to_string(),
+            automatic: false,
+            lines: lines
+                .iter()
+                .enumerate()
+                .map(|(i, text)| crate::db::TranscriptLine {
+                    start: i as f64 * 10.0,
+                    text: text.to_string(),
+                })
+                .collect(),
+        });
+        app
+    }
+
+    #[test]
+    fn test_transcript_cursor() {
+        let mut app = transcript_app(&["a", "b", "c"]);
+        app.transcript_up();
+        assert_eq!(app.transcript_selected, 0);
+        for _ in 0..5 {
+            app.transcript_down();
+        }
+        assert_eq!(app.transcript_selected, 2);
+        assert_eq!(app.selected_transcript_start(), Some(20));
+    }
+
+    #[test]
+    fn test_transcript_find_wraps() {
+        let mut app = transcript_app(&["Intro", "the Parser", "setup", "parser again"]);
+        app.transcript_query = "parser".to_string();
+
+        assert!(app.transcript_find(true));
+        assert_eq!(app.transcript_selected, 1);
+        assert!(app.transcript_find(true));
+        assert_eq!(app.transcript_selected, 3);
+        assert!(app.transcript_find(true));
+        assert_eq!(app.transcript_selected, 1);
+        assert!(app.transcript_find(false));
+        assert_eq!(app.transcript_selected, 3);
+
+        app.transcript_query = "missing".to_string();
+        assert!(!app.transcript_find(true));
+        assert_eq!(app.transcript_selected, 3);
+    }
+
+    #[test]
+    fn test_filter_matches_transcripts() {
+        let mut app = test_app();
+        app.screen = Screen::Videos;
+        app.videos = vec![make_video("v1", "ch1", false), make_video("v2", "ch1", false)];
+        app.db.store_transcript(
+            "v2",
+            &Transcript {
+                language: "en".to_string(),
+                automatic: true,
+                lines: vec![crate::db::TranscriptLine {
+                    start: 0.0,
+                    text: "we talk about borrow checking".to_string(),
+                }],
+            },
+        );
+
+        app.filter_text = "borrow".to_string();
+        app.update_transcript_matches();
+        let ids: Vec<&str> = app.filtered_videos().iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["v2"]);
+
+        app.filter_text = "bo".to_string();
+        app.update_transcript_matches();
+        assert!(app.transcript_matches.is_empty());
+    }
+
     // ── Message tests ────────────────────────────────────────
 
     #[test]
@@ -877,13 +1754,175 @@ mod tests {
     }
 
     #[test]
-    fn test_toggle_resolution() {
+    fn test_rename_selected() {
+        let mut app = test_app();
+        for (id, name) in [("ch1", "Zeta"), ("ch2", "Foo - Topic")] {
+            app.db.add_subscription(&crate::db::Subscription {
+                id: id.to_string(),
+                name: name.to_string(),
+                url: format!("https://youtube.com/channel/{}", id),
+                added_at: None,
+                kind: crate::db::SourceKind::Channel,
+                notify: false,
+                display_name: None,
+                archived: false,
+                members: false,
+            }).unwrap();
+        }
+        app.load_subscriptions();
+        app.channel_selected = 0;
+        app.start_rename();
+        assert_eq!(app.mode, Mode::Rename);
+        assert_eq!(app.input_text, "");
+
+        app.rename_selected("  Foo ");
+        assert_eq!(app.mode, Mode::List);
+        let sub = app.subscriptions.iter().find(|s| s.id == "ch2").unwrap();
+        assert_eq!(sub.label(), "Foo");
+        assert_eq!(sub.name, "Foo - Topic");
+        // Selection follows the renamed subscription to its new position
+        assert_eq!(app.filtered_subscriptions()[app.channel_selected].id, "ch2");
+
+        app.start_rename();
+        assert_eq!(app.input_text, "Foo");
+        app.rename_selected("");
+        assert_eq!(app.subscriptions[0].label(), "Foo - Topic");
+
+        // Renaming the last filtered row out of the filter keeps the selection in range
+        app.filter_text = "t".to_string();
+        app.channel_selected = app.filtered_subscriptions().len() - 1;
+        app.start_rename();
+        app.rename_selected("Bar");
+        let visible = app.filtered_subscriptions();
+        assert_eq!(visible.len(), 1);
+        assert!(app.channel_selected < visible.len());
+        assert_eq!(app.status_message.as_ref().unwrap().text, "Renamed to Bar");
+    }
+
+    #[test]
+    fn test_toggle_selected_notify() {
+        let mut app = test_app();
+        app.db.add_subscription(&crate::db::Subscription {
+            id: "ch1".to_string(),
+            name: "Foo".to_string(),
+            url: "https://youtube.com/channel/ch1".to_string(),
+            added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
+        }).unwrap();
+        app.load_subscriptions();
+        app.toggle_selected_notify();
+        assert!(app.subscriptions[0].notify);
+        assert!(app.db.get_subscriptions()[0].notify);
+        app.toggle_selected_notify();
+        assert!(!app.db.get_subscriptions()[0].notify);
+    }
+
+    #[test]
+    fn test_toggle_selected_members() {
+        let mut app = test_app();
+        for (id, kind) in [("UC1", crate::db::SourceKind::Channel), ("PL1", crate::db::SourceKind::Playlist)] {
+            app.db.add_subscription(&crate::db::Subscription {
+                id: id.to_string(),
+                name: id.to_string(),
+                url: format!("https://youtube.com/{}", id),
+                added_at: None,
+                kind,
+                notify: false,
+                display_name: None,
+                archived: false,
+                members: false,
+            }).unwrap();
+        }
+        app.load_subscriptions();
+        app.channel_selected = 1;
+        app.toggle_selected_members();
+        assert!(app.db.get_subscriptions()[1].members);
+        assert!(app.status_message.as_ref().unwrap().text.contains("cookiesFile"));
+
+        app.channel_selected = 0;
+        app.toggle_selected_members();
+        assert!(!app.db.get_subscriptions()[0].members);
+        assert!(app.status_message.as_ref().unwrap().is_error);
+    }
+
+    // ── Archive tests ────────────────────────────────────────
+
+    fn add_channel(app: &App, id: &str, name: &str) {
+        app.db.add_subscription(&crate::db::Subscription {
+            id: id.to_string(),
+            name: name.to_string(),
+            url: format!("https://youtube.com/channel/{}", id),
+            added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_toggle_selected_archived() {
+        let mut app = test_app();
+        add_channel(&app, "ch1", "Alpha");
+        add_channel(&app, "ch2", "Beta");
+        app.load_subscriptions();
+
+        app.toggle_selected_archived();
+        assert_eq!(app.filtered_subscriptions().len(), 1);
+        assert_eq!(app.filtered_subscriptions()[0].id, "ch2");
+        assert!(app.status_message.as_ref().unwrap().text.contains("Archived Alpha"));
+
+        app.toggle_archived_section();
+        assert!(app.show_archived);
+        assert_eq!(app.filtered_subscriptions()[0].id, "ch1");
+        app.toggle_selected_archived();
+        assert!(app.filtered_subscriptions().is_empty());
+        assert_eq!(app.db.get_subscriptions().len(), 2);
+    }
+
+    #[test]
+    fn test_archive_suggestions() {
+        let mut app = test_app();
+        add_channel(&app, "ch1", "Alpha");
+        add_channel(&app, "ch2", "Beta");
+        add_channel(&app, "ch3", "Empty");
+        let mut old = make_video("v1", "ch1", false);
+        old.published_date = Some(Utc::now() - chrono::Duration::days(400));
+        app.db.store_videos(&[old, make_video("v2", "ch2", false)]);
+        app.load_subscriptions();
+
+        let ids: Vec<&str> = app.archive_suggestions().iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["ch1"]);
+        app.settings.archive_after_days = 0;
+        assert!(app.archive_suggestions().is_empty());
+    }
+
+    #[test]
+    fn test_cycle_resolution() {
         let mut app = test_app();
         assert_eq!(app.max_resolution, "1080"); // default
-        app.toggle_resolution();
+        app.cycle_resolution();
+        assert_eq!(app.max_resolution, "1440");
+        app.cycle_resolution();
+        assert_eq!(app.max_resolution, "2160");
+        app.cycle_resolution();
         assert_eq!(app.max_resolution, "max");
-        app.toggle_resolution();
-        assert_eq!(app.max_resolution, "1080");
+        app.cycle_resolution();
+        assert_eq!(app.max_resolution, "360");
+        assert_eq!(app.db.get_settings().max_resolution, "360");
+    }
+
+    #[test]
+    fn test_format_prefs_follow_resolution() {
+        let mut app = test_app();
+        assert_eq!(app.format_prefs().max_height, Some(1080));
+        app.max_resolution = "max".to_string();
+        assert_eq!(app.format_prefs().max_height, None);
     }
 
     #[test]
@@ -0,0 +1,535 @@
+use std::collections::HashSet;
+
+use clap::Subcommand;
+use serde_json::{json, Value};
+
+use crate::db::{Database, DeletePolicy, Subscription};
+use crate::format::FormatPrefs;
+use crate::{player, sponsorblock, ytdlp};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Manage subscriptions
+    Subs {
+        #[command(subcommand)]
+        action: SubsCommand,
+    },
+    /// Browse stored videos
+    Videos {
+        #[command(subcommand)]
+        action: VideosCommand,
+    },
+    /// Mark videos as watched or unwatched
+    Watched {
+        #[command(subcommand)]
+        action: WatchedCommand,
+    },
+    /// Play a video by ID or URL
+    Play {
+        video: String,
+    },
+    /// Search YouTube
+    Search {
+        query: String,
+        /// Number of results (1-50)
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Read or change settings
+    Settings {
+        #[command(subcommand)]
+        action: SettingsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SubsCommand {
+    /// List subscriptions
+    List,
+    /// Subscribe to a channel or playlist URL
+    Add {
+        url: String,
+        /// Also fetch the full upload history
+        #[arg(long)]
+        prime: bool,
+    },
+    /// Unsubscribe (by index, ID or name)
+    Rm {
+        subscription: String,
+        /// What to do with its stored videos: keep them out of sight until
+        /// it is added again, archive the subscription with its history, or
+        /// purge them
+        #[arg(long, default_value = "keep", value_parser = ["keep", "archive", "purge"])]
+        policy: String,
+        /// Shorthand for `--policy purge`
+        #[arg(long, conflicts_with = "policy")]
+        purge: bool,
+    },
+    /// Set the name shown for a subscription (by index, ID or name); leave
+    /// out the name to go back to the channel's own
+    Rename {
+        subscription: String,
+        name: Option<String>,
+    },
+    /// Stop refreshing a subscription but keep its videos and history
+    Archive {
+        subscription: String,
+    },
+    /// Refresh an archived subscription again
+    Unarchive {
+        subscription: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VideosCommand {
+    /// List stored videos, newest first
+    List {
+        /// Only this subscription (by index, ID or name)
+        #[arg(long)]
+        channel: Option<String>,
+        /// Skip watched videos
+        #[arg(long)]
+        unwatched: bool,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WatchedCommand {
+    /// Mark videos as watched
+    Mark {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Mark videos as unwatched
+    Unmark {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SettingsCommand {
+    /// Show one setting, or all of them
+    Get {
+        key: Option<String>,
+    },
+    /// Change a setting; the value is JSON, or a plain string
+    Set {
+        key: String,
+        value: String,
+    },
+}
+
+/// Run a subcommand, printing JSON when `json` is set. Returns the exit code.
+pub async fn run(command: Command, json: bool) -> i32 {
+    let db = match Database::open() {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let result = match command {
+        Command::Subs { action } => subs(&db, action, json).await,
+        Command::Videos { action } => videos(&db, action, json),
+        Command::Watched { action } => watched(&db, action, json),
+        Command::Play { video } => play(&db, &video, json).await,
+        Command::Search { query, limit } => search(&query, limit, json).await,
+        Command::Settings { action } => settings(&db, action, json),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            if json {
+                println!("{}", json!({ "error": e }));
+            } else {
+                eprintln!("Error: {}", e);
+            }
+            1
+        }
+    }
+}
+
+/// Find a subscription by 1-based index, exact ID, or a name match that
+/// leaves no doubt. Both the display name and the upstream name count.
+pub fn resolve_subscription<'a>(
+    subs: &'a [Subscription],
+    query: &str,
+) -> Result<&'a Subscription, String> {
+    if let Ok(idx) = query.parse::<usize>() {
+        return idx
+            .checked_sub(1)
+            .and_then(|i| subs.get(i))
+            .ok_or_else(|| format!("Invalid index: {}", idx));
+    }
+    if let Some(sub) = subs.iter().find(|s| s.id == query) {
+        return Ok(sub);
+    }
+    let search = query.to_lowercase();
+    let names = |s: &Subscription| [s.label().to_lowercase(), s.name.to_lowercase()];
+    if let Some(sub) = subs.iter().find(|s| names(s).contains(&search)) {
+        return Ok(sub);
+    }
+    let matches: Vec<&Subscription> = subs
+        .iter()
+        .filter(|s| names(s).iter().any(|n| n.contains(&search)))
+        .collect();
+    match matches.as_slice() {
+        [sub] => Ok(sub),
+        [] => Err(format!("No subscription matching \"{}\"", query)),
+        _ => Err(format!(
+            "\"{}\" matches several subscriptions: {}",
+            query,
+            matches.iter().map(|s| s.label()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+// ── Subscriptions ──────────────────────────────────────────
+
+async fn subs(db: &Database, action: SubsCommand, json: bool) -> Result<(), String> {
+    match action {
+        SubsCommand::List => {
+            let subs = db.get_all_subscriptions();
+            if json {
+                let list: Vec<Value> = subs.iter().map(|s| s.to_json()).collect();
+                println!("{}", Value::Array(list));
+            } else if subs.is_empty() {
+                println!("No subscriptions yet. Use `subs add <url>` to add one.");
+            } else {
+                for (i, sub) in subs.iter().enumerate() {
+                    let tag = if sub.archived { "  (archived)" } else { "" };
+                    println!("{:>3}. {}  {}{}", i + 1, sub.id, sub.label(), tag);
+                }
+            }
+        }
+        SubsCommand::Add { url, prime } => {
+            let info = ytdlp::get_channel_info(&url).await?;
+            let sub = info.to_subscription();
+            db.add_subscription(&sub)?;
+            let mut added = None;
+            if prime {
+                let members = db.get_subscriptions().iter().any(|s| s.id == info.id && s.members);
+                let result =
+                    ytdlp::prime_channel(&info.id, &info.name, &info.url, members, &HashSet::new(), |_| {})
+                        .await?;
+                db.store_source_videos(&info.id, &result.videos);
+                added = Some(result.added);
+            }
+            if json {
+                let mut out = sub.to_json();
+                out["primed"] = json!(added);
+                println!("{}", out);
+            } else {
+                println!("Added: {}", sub.name);
+                if let Some(n) = added {
+                    println!("Primed {} video(s)", n);
+                }
+            }
+        }
+        SubsCommand::Rm { subscription, policy, purge } => {
+            let policy = if purge {
+                DeletePolicy::Purge
+            } else {
+                DeletePolicy::parse(&policy).unwrap_or_default()
+            };
+            let subs = db.get_all_subscriptions();
+            let sub = resolve_subscription(&subs, &subscription)?;
+            let purged = db.remove_subscription_with(&sub.id, policy)?;
+            if json {
+                let mut out = sub.to_json();
+                out["policy"] = json!(policy.as_str());
+                out["purgedVideos"] = json!(purged);
+                println!("{}", out);
+            } else {
+                match policy {
+                    DeletePolicy::Keep => println!("Removed: {}", sub.label()),
+                    DeletePolicy::Archive => println!("Archived: {}", sub.label()),
+                    DeletePolicy::Purge => {
+                        println!("Removed: {} ({} stored video(s) deleted)", sub.label(), purged)
+                    }
+                }
+            }
+        }
+        SubsCommand::Rename { subscription, name } => {
+            let name = name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+            let subs = db.get_all_subscriptions();
+            let mut sub = resolve_subscription(&subs, &subscription)?.clone();
+            db.rename_subscription(&sub.id, name)?;
+            let old = sub.label().to_string();
+            sub.display_name = name.map(str::to_string);
+            if json {
+                println!("{}", sub.to_json());
+            } else {
+                println!("Renamed {} to {}", old, sub.label());
+            }
+        }
+        SubsCommand::Archive { subscription } => set_archived(db, &subscription, true, json)?,
+        SubsCommand::Unarchive { subscription } => set_archived(db, &subscription, false, json)?,
+    }
+    Ok(())
+}
+
+fn set_archived(db: &Database, query: &str, archived: bool, json: bool) -> Result<(), String> {
+    let subs = db.get_all_subscriptions();
+    let mut sub = resolve_subscription(&subs, query)?.clone();
+    db.set_subscription_archived(&sub.id, archived)?;
+    sub.archived = archived;
+    if json {
+        println!("{}", sub.to_json());
+    } else if archived {
+        println!("Archived: {}", sub.label());
+    } else {
+        println!("Restored: {}", sub.label());
+    }
+    Ok(())
+}
+
+// ── Videos ─────────────────────────────────────────────────
+
+fn videos(db: &Database, action: VideosCommand, json: bool) -> Result<(), String> {
+    let VideosCommand::List { channel, unwatched, limit } = action;
+    let source = match channel {
+        Some(ref query) => {
+            let subs = db.get_all_subscriptions();
+            Some(resolve_subscription(&subs, query)?.id.clone())
+        }
+        None => None,
+    };
+    let list = db.list_videos(source.as_deref(), unwatched, limit);
+    let watched = db.get_watched_ids();
+
+    if json {
+        let out: Vec<Value> = list
+            .iter()
+            .map(|v| {
+                let mut out = v.to_json();
+                out["watched"] = json!(watched.contains(&v.id));
+                out
+            })
+            .collect();
+        println!("{}", Value::Array(out));
+    } else {
+        for v in list {
+            let mark = if watched.contains(&v.id) { "✓" } else { " " };
+            println!(
+                "{} {}  {:<10} {}  ({})",
+                mark,
+                v.id,
+                v.relative_date,
+                v.title,
+                v.channel_name.as_deref().unwrap_or("Unknown")
+            );
+        }
+    }
+    Ok(())
+}
+
+fn watched(db: &Database, action: WatchedCommand, json: bool) -> Result<(), String> {
+    let (ids, watched) = match action {
+        WatchedCommand::Mark { ids } => (ids, true),
+        WatchedCommand::Unmark { ids } => (ids, false),
+    };
+    if let Some(bad) = ids.iter().find(|id| !ytdlp::is_valid_video_id(id)) {
+        return Err(format!("Invalid video ID: {}", bad));
+    }
+    for id in &ids {
+        if watched {
+            db.mark_as_watched(id);
+        } else {
+            db.unmark_watched(id);
+        }
+    }
+    if json {
+        println!("{}", json!({ "ids": ids, "watched": watched }));
+    } else {
+        let verb = if watched { "watched" } else { "unwatched" };
+        println!("Marked {} video(s) as {}", ids.len(), verb);
+    }
+    Ok(())
+}
+
+// ── Playback & Search ──────────────────────────────────────
+
+async fn play(db: &Database, video: &str, json: bool) -> Result<(), String> {
+    let (id, start) = if ytdlp::is_valid_video_id(video) {
+        (video.to_string(), None)
+    } else {
+        match ytdlp::classify_youtube_link(video) {
+            Some(ytdlp::YoutubeLink::Video { id, start }) => (id, start),
+            _ => return Err(format!("Not a video ID or URL: {}", video)),
+        }
+    };
+    let stored = db.get_video(&id);
+    let url = stored
+        .as_ref()
+        .map(|v| v.url.clone())
+        .unwrap_or_else(|| format!("https://www.youtube.com/watch?v={}", id));
+    let title = stored.as_ref().map(|v| v.title.clone()).unwrap_or_else(|| id.clone());
+
+    let settings = db.get_settings();
+    let segments = if settings.sponsorblock {
+        match db.get_sponsor_segments(&id) {
+            Some(cached) => cached,
+            None => {
+                let client = sponsorblock::SponsorBlockClient::new(&settings.sponsorblock_api);
+                let segments = client.fetch_segments(&id).await.unwrap_or_default();
+                db.store_sponsor_segments(&id, &segments);
+                segments
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    db.mark_as_watched(&id);
+    let request = player::PlayRequest {
+        url: &url,
+        video_id: Some(&id),
+        title: &title,
+        start,
+        segments: &segments,
+    };
+    let format = FormatPrefs::from_settings(&settings, &settings.max_resolution);
+    let (result, _) = player::play_video(&request, &settings, &format).await;
+    if let Some(err) = result.error {
+        return Err(format!("Failed to play: {}", err));
+    }
+    if json {
+        println!("{}", json!({ "id": id, "title": title, "player": result.player }));
+    } else {
+        println!("Playing {} in {}", title, result.player);
+    }
+    Ok(())
+}
+
+async fn search(query: &str, limit: usize, json: bool) -> Result<(), String> {
+    let results = ytdlp::search_youtube(query, limit).await?;
+    if json {
+        let list: Vec<Value> = results.iter().map(|v| v.to_json()).collect();
+        println!("{}", Value::Array(list));
+    } else {
+        for v in &results {
+            println!(
+                "{}  {}  ({})",
+                v.id,
+                v.title,
+                v.channel_name.as_deref().unwrap_or("Unknown")
+            );
+        }
+    }
+    Ok(())
+}
+
+// ── Settings ───────────────────────────────────────────────
+
+/// A `settings set` value as stored JSON: valid JSON is kept as is, anything
+/// else is taken as a plain string.
+fn setting_value(raw: &str) -> String {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(_) => raw.to_string(),
+        Err(_) => Value::String(raw.to_string()).to_string(),
+    }
+}
+
+fn settings(db: &Database, action: SettingsCommand, json: bool) -> Result<(), String> {
+    match action {
+        SettingsCommand::Get { key } => {
+            let all = db.get_settings().to_json();
+            let out = match key {
+                Some(ref k) => {
+                    let value = all.get(k).ok_or_else(|| format!("Unknown setting: {}", k))?;
+                    if json {
+                        json!({ k: value })
+                    } else {
+                        value.clone()
+                    }
+                }
+                None => all,
+            };
+            match out {
+                Value::Object(ref map) if !json => {
+                    for (k, v) in map {
+                        println!("{} = {}", k, v);
+                    }
+                }
+                _ => println!("{}", out),
+            }
+        }
+        SettingsCommand::Set { key, value } => {
+            let value = setting_value(&value);
+            let mut settings = db.get_settings();
+            if !settings.apply(&key, &value) {
+                return Err(format!("Invalid value for {}: {}", key, value));
+            }
+            db.update_setting(&key, &value);
+            if json {
+                println!("{}", json!({ &key: settings.to_json()[&key] }));
+            } else {
+                println!("{} = {}", key, settings.to_json()[&key]);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sub(id: &str, name: &str) -> Subscription {
+        Subscription {
+            id: id.to_string(),
+            name: name.to_string(),
+            url: String::new(),
+            added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
+        }
+    }
+
+    // ── resolve_subscription tests ───────────────────────────
+
+    #[test]
+    fn test_resolve_subscription() {
+        let subs = vec![
+            sub("UCa", "Rust Talks"),
+            sub("UCb", "Rust"),
+            sub("UCc", "Cooking"),
+        ];
+        assert_eq!(resolve_subscription(&subs, "3").unwrap().id, "UCc");
+        assert_eq!(resolve_subscription(&subs, "UCa").unwrap().id, "UCa");
+        assert_eq!(resolve_subscription(&subs, "rust").unwrap().id, "UCb");
+        assert_eq!(resolve_subscription(&subs, "cook").unwrap().id, "UCc");
+        assert!(resolve_subscription(&subs, "0").is_err());
+        assert!(resolve_subscription(&subs, "4").is_err());
+        assert!(resolve_subscription(&subs, "nothing").is_err());
+        assert!(resolve_subscription(&subs, "s").is_err());
+    }
+
+    #[test]
+    fn test_resolve_subscription_by_display_name() {
+        let mut renamed = sub("UCa", "Foo - Topic");
+        renamed.display_name = Some("Foo".to_string());
+        let subs = vec![renamed, sub("UCb", "Bar")];
+        assert_eq!(resolve_subscription(&subs, "foo").unwrap().id, "UCa");
+        assert_eq!(resolve_subscription(&subs, "topic").unwrap().id, "UCa");
+    }
+
+    // ── setting_value tests ──────────────────────────────────
+
+    #[test]
+    fn test_setting_value() {
+        assert_eq!(setting_value("true"), "true");
+        assert_eq!(setting_value("42"), "42");
+        assert_eq!(setting_value("[\"av01\"]"), "[\"av01\"]");
+        assert_eq!(setting_value("mpv"), "\"mpv\"");
+        assert_eq!(setting_value("\"iina\""), "\"iina\"");
+    }
+}
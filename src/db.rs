@@ -6,12 +6,52 @@ use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 
+/// What a subscription follows. Channel ids start with `UC`, playlist ids
+/// with `PL` (or `UU`, `OL`, ...), and each kind has its own RSS feed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    #[default]
+    Channel,
+    Playlist,
+}
+
+impl SourceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceKind::Channel => "channel",
+            SourceKind::Playlist => "playlist",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "playlist" => SourceKind::Playlist,
+            _ => SourceKind::Channel,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Subscription {
     pub id: String,
     pub name: String,
     pub url: String,
     pub added_at: Option<String>,
+    #[serde(default)]
+    pub kind: SourceKind,
+    /// Send a desktop notification for new uploads in watch mode.
+    #[serde(default)]
+    pub notify: bool,
+    /// Name chosen by the user; `name` keeps the one YouTube reports.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Kept with its history but no longer refreshed or listed with the rest.
+    #[serde(default)]
+    pub archived: bool,
+    /// Also list the channel's members-only videos, which needs cookies.
+    #[serde(default)]
+    pub members: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -28,25 +68,551 @@ pub struct Video {
     pub duration: Option<i64>,
     pub duration_string: Option<String>,
     pub view_count: Option<u64>,
+    pub live_status: Option<LiveStatus>,
+    /// Thumbnail URL from the feed or yt-dlp, if it gave one.
+    pub thumbnail: Option<String>,
+}
+
+/// What happens to a subscription's stored data when it is removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeletePolicy {
+    /// Drop the subscription only; its videos and history stay out of sight,
+    /// also through `--gc`, until it is added again.
+    #[default]
+    Keep,
+    /// Keep the subscription as archived, with its videos and history.
+    Archive,
+    /// Delete its videos, watch history and cached data too.
+    Purge,
+}
+
+impl DeletePolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DeletePolicy::Keep => "keep",
+            DeletePolicy::Archive => "archive",
+            DeletePolicy::Purge => "purge",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "keep" => Some(DeletePolicy::Keep),
+            "archive" => Some(DeletePolicy::Archive),
+            "purge" => Some(DeletePolicy::Purge),
+            _ => None,
+        }
+    }
+}
+
+/// Livestream state from yt-dlp's `live_status`. None means it has not been
+/// checked yet, e.g. for videos only seen in an RSS feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveStatus {
+    NotLive,
+    Live,
+    Upcoming,
+    WasLive,
+}
+
+impl LiveStatus {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "not_live" => Some(LiveStatus::NotLive),
+            "is_live" => Some(LiveStatus::Live),
+            "is_upcoming" => Some(LiveStatus::Upcoming),
+            "was_live" | "post_live" => Some(LiveStatus::WasLive),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LiveStatus::NotLive => "not_live",
+            LiveStatus::Live => "is_live",
+            LiveStatus::Upcoming => "is_upcoming",
+            LiveStatus::WasLive => "was_live",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SponsorSegment {
+    pub start: f64,
+    pub end: f64,
+    pub category: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoDescription {
+    pub title: String,
+    pub description: String,
+    pub channel_name: String,
+    #[serde(default)]
+    pub upload_date: Option<String>,
+    #[serde(default)]
+    pub duration: Option<i64>,
+    #[serde(default)]
+    pub view_count: Option<u64>,
+    #[serde(default)]
+    pub like_count: Option<u64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+    #[serde(default)]
+    pub links: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub author: String,
+    pub text: String,
+    pub like_count: Option<u64>,
+    pub timestamp: Option<i64>,
+    pub is_reply: bool,
+    pub is_uploader: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptLine {
+    pub start: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    pub language: String,
+    pub automatic: bool,
+    pub lines: Vec<TranscriptLine>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Settings {
     pub player: String,
+    pub player_command: Option<String>,
     pub videos_per_channel: i64,
     pub hide_shorts: bool,
     pub max_resolution: String,
+    pub preferred_codecs: Vec<String>,
+    pub max_fps: Option<u32>,
+    pub allow_hdr: bool,
+    pub playback_mode: String,
+    pub sponsorblock: bool,
+    pub sponsorblock_api: String,
+    pub comment_limit: u32,
+    pub subtitle_language: String,
+    pub thumbnails: String,
+    pub hide_stream_vods: bool,
+    /// Suggest archiving channels with no upload in this many days; 0 disables.
+    pub archive_after_days: u32,
+    /// yt-dlp binary, by name or path.
+    pub ytdlp_path: String,
+    /// Arguments added to every yt-dlp call, e.g. a proxy.
+    pub ytdlp_args: Vec<String>,
+    /// yt-dlp processes running at once.
+    pub ytdlp_concurrency: u32,
+    /// yt-dlp processes started, plus direct YouTube requests, per minute;
+    /// 0 means no limit.
+    pub ytdlp_requests_per_minute: u32,
+    /// Retries of a rate-limited yt-dlp call.
+    pub ytdlp_retries: u32,
+    /// First pause after a rate limit, in seconds; doubles while it repeats.
+    pub ytdlp_backoff_secs: u64,
+    /// Feed downloads running at once during a refresh.
+    pub feed_concurrency: u32,
+    /// Netscape cookies file handed to yt-dlp and the player.
+    pub cookies_file: Option<String>,
+    /// Browser (and optional profile) to read cookies from, as yt-dlp's
+    /// `--cookies-from-browser` takes it. Ignored when `cookies_file` is set.
+    pub cookies_from_browser: Option<String>,
+}
+
+/// Where yt-dlp gets a signed-in session for age-restricted and members-only
+/// videos. Its `Debug` output leaves out the path and profile.
+#[derive(Clone, PartialEq)]
+pub enum Cookies {
+    File(String),
+    Browser(String),
+}
+
+impl std::fmt::Debug for Cookies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cookies::File(_) => write!(f, "File(<redacted>)"),
+            Cookies::Browser(spec) => write!(f, "Browser({})", browser_name(spec)),
+        }
+    }
+}
+
+/// The browser of a `BROWSER[+KEYRING][:PROFILE][::CONTAINER]` spec.
+fn browser_name(spec: &str) -> &str {
+    spec.split([':', '+']).next().unwrap_or(spec)
+}
+
+impl Cookies {
+    /// yt-dlp arguments that load these cookies.
+    pub fn ytdlp_args(&self) -> Vec<String> {
+        match self {
+            Cookies::File(path) => vec!["--cookies".to_string(), path.clone()],
+            Cookies::Browser(spec) => vec!["--cookies-from-browser".to_string(), spec.clone()],
+        }
+    }
+
+    /// The same as a `key=value` yt-dlp option, for a player's ytdl hook.
+    pub fn ytdl_raw_option(&self) -> String {
+        match self {
+            Cookies::File(path) => format!("cookies={}", path),
+            Cookies::Browser(spec) => format!("cookies-from-browser={}", spec),
+        }
+    }
+
+    /// Hide the cookie file path or browser profile wherever it shows up in
+    /// `text`, such as yt-dlp's error output.
+    pub fn redact(&self, text: &str) -> String {
+        let secret = match self {
+            Cookies::File(path) => path.as_str(),
+            Cookies::Browser(spec) => spec
+                .split_once(':')
+                .and_then(|(_, rest)| rest.split("::").next())
+                .unwrap_or(""),
+        };
+        if secret.is_empty() {
+            return text.to_string();
+        }
+        text.replace(secret, "<redacted>")
+    }
+
+    /// Short description without the secret part.
+    pub fn describe(&self) -> String {
+        match self {
+            Cookies::File(_) => "cookies file".to_string(),
+            Cookies::Browser(spec) => format!("cookies from {}", browser_name(spec)),
+        }
+    }
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Settings {
             player: "mpv".to_string(),
+            player_command: None,
             videos_per_channel: 15,
             hide_shorts: true,
             max_resolution: "1080".to_string(),
+            preferred_codecs: Vec::new(),
+            max_fps: None,
+            allow_hdr: true,
+            playback_mode: "auto".to_string(),
+            sponsorblock: true,
+            sponsorblock_api: crate::sponsorblock::DEFAULT_API_BASE.to_string(),
+            comment_limit: 100,
+            subtitle_language: crate::transcript::DEFAULT_LANGUAGE.to_string(),
+            thumbnails: "off".to_string(),
+            hide_stream_vods: false,
+            archive_after_days: 365,
+            ytdlp_path: "yt-dlp".to_string(),
+            ytdlp_args: Vec::new(),
+            ytdlp_concurrency: 4,
+            ytdlp_requests_per_minute: 60,
+            ytdlp_retries: 3,
+            ytdlp_backoff_secs: 5,
+            feed_concurrency: 20,
+            cookies_file: None,
+            cookies_from_browser: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Apply one stored setting, given its key and JSON-encoded value. Returns
+    /// false for unknown keys and values of the wrong type, which are ignored.
+    pub fn apply(&mut self, key: &str, value: &str) -> bool {
+        match key {
+            "player" => match serde_json::from_str::<String>(value) {
+                Ok(v) => {
+                    self.player = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "playerCommand" => match serde_json::from_str::<Option<String>>(value) {
+                Ok(v) => {
+                    self.player_command = v.filter(|s| !s.trim().is_empty());
+                    true
+                }
+                Err(_) => false,
+            },
+            "videosPerChannel" => match serde_json::from_str::<i64>(value) {
+                Ok(v) => {
+                    self.videos_per_channel = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "hideShorts" => match serde_json::from_str::<bool>(value) {
+                Ok(v) => {
+                    self.hide_shorts = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "hideStreamVods" => match serde_json::from_str::<bool>(value) {
+                Ok(v) => {
+                    self.hide_stream_vods = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "maxResolution" => match serde_json::from_str::<String>(value) {
+                Ok(v) => {
+                    self.max_resolution = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "preferredCodecs" => match serde_json::from_str::<Vec<String>>(value) {
+                Ok(v) => {
+                    self.preferred_codecs = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "maxFps" => match serde_json::from_str::<Option<u32>>(value) {
+                Ok(v) => {
+                    self.max_fps = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "allowHdr" => match serde_json::from_str::<bool>(value) {
+                Ok(v) => {
+                    self.allow_hdr = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "playbackMode" => match serde_json::from_str::<String>(value) {
+                Ok(v) => {
+                    self.playback_mode = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "sponsorBlock" => match serde_json::from_str::<bool>(value) {
+                Ok(v) => {
+                    self.sponsorblock = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "sponsorBlockApi" => match serde_json::from_str::<String>(value) {
+                Ok(v) => {
+                    self.sponsorblock_api = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "commentLimit" => match serde_json::from_str::<u32>(value) {
+                Ok(v) => {
+                    self.comment_limit = v.max(1);
+                    true
+                }
+                Err(_) => false,
+            },
+            "archiveAfterDays" => match serde_json::from_str::<u32>(value) {
+                Ok(v) => {
+                    self.archive_after_days = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "ytdlpPath" => match serde_json::from_str::<String>(value) {
+                Ok(v) => {
+                    if !v.trim().is_empty() {
+                        self.ytdlp_path = v.trim().to_string();
+                    }
+                    true
+                }
+                Err(_) => false,
+            },
+            "ytdlpArgs" => match serde_json::from_str::<Vec<String>>(value) {
+                Ok(v) => {
+                    self.ytdlp_args = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "ytdlpConcurrency" => match serde_json::from_str::<u32>(value) {
+                Ok(v) => {
+                    self.ytdlp_concurrency = v.max(1);
+                    true
+                }
+                Err(_) => false,
+            },
+            "ytdlpRequestsPerMinute" => match serde_json::from_str::<u32>(value) {
+                Ok(v) => {
+                    self.ytdlp_requests_per_minute = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "ytdlpRetries" => match serde_json::from_str::<u32>(value) {
+                Ok(v) => {
+                    self.ytdlp_retries = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "ytdlpBackoffSecs" => match serde_json::from_str::<u64>(value) {
+                Ok(v) => {
+                    self.ytdlp_backoff_secs = v.max(1);
+                    true
+                }
+                Err(_) => false,
+            },
+            "feedConcurrency" => match serde_json::from_str::<u32>(value) {
+                Ok(v) => {
+                    self.feed_concurrency = v.max(1);
+                    true
+                }
+                Err(_) => false,
+            },
+            "cookiesFile" => match serde_json::from_str::<Option<String>>(value) {
+                Ok(v) => {
+                    self.cookies_file = v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+                    true
+                }
+                Err(_) => false,
+            },
+            "cookiesFromBrowser" => match serde_json::from_str::<Option<String>>(value) {
+                Ok(v) => {
+                    self.cookies_from_browser =
+                        v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+                    true
+                }
+                Err(_) => false,
+            },
+            "thumbnails" => match serde_json::from_str::<String>(value) {
+                Ok(v) => {
+                    self.thumbnails = v;
+                    true
+                }
+                Err(_) => false,
+            },
+            "subtitleLanguage" => match serde_json::from_str::<String>(value) {
+                Ok(v) => {
+                    if !v.trim().is_empty() {
+                        self.subtitle_language = v.trim().to_string();
+                    }
+                    true
+                }
+                Err(_) => false,
+            },
+            _ => false,
         }
     }
+
+    /// Every setting under its stored key, for `settings get`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "player": self.player,
+            "playerCommand": self.player_command,
+            "videosPerChannel": self.videos_per_channel,
+            "hideShorts": self.hide_shorts,
+            "hideStreamVods": self.hide_stream_vods,
+            "maxResolution": self.max_resolution,
+            "preferredCodecs": self.preferred_codecs,
+            "maxFps": self.max_fps,
+            "allowHdr": self.allow_hdr,
+            "playbackMode": self.playback_mode,
+            "sponsorBlock": self.sponsorblock,
+            "sponsorBlockApi": self.sponsorblock_api,
+            "commentLimit": self.comment_limit,
+            "thumbnails": self.thumbnails,
+            "subtitleLanguage": self.subtitle_language,
+            "archiveAfterDays": self.archive_after_days,
+            "ytdlpPath": self.ytdlp_path,
+            "ytdlpArgs": self.ytdlp_args,
+            "ytdlpConcurrency": self.ytdlp_concurrency,
+            "ytdlpRequestsPerMinute": self.ytdlp_requests_per_minute,
+            "ytdlpRetries": self.ytdlp_retries,
+            "ytdlpBackoffSecs": self.ytdlp_backoff_secs,
+            "feedConcurrency": self.feed_concurrency,
+            "cookiesFile": self.cookies_file,
+            "cookiesFromBrowser": self.cookies_from_browser,
+        })
+    }
+
+    /// The configured cookies, preferring a file over a browser.
+    pub fn cookies(&self) -> Option<Cookies> {
+        self.cookies_file
+            .clone()
+            .map(Cookies::File)
+            .or_else(|| self.cookies_from_browser.clone().map(Cookies::Browser))
+    }
+}
+
+impl Subscription {
+    /// The name to show: the user's choice, else the upstream one.
+    pub fn label(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "name": self.label(),
+            "upstreamName": self.name,
+            "url": self.url,
+            "kind": self.kind.as_str(),
+            "addedAt": self.added_at,
+            "notify": self.notify,
+            "archived": self.archived,
+            "members": self.members,
+        })
+    }
+}
+
+impl Video {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "title": self.title,
+            "url": self.url,
+            "channelId": self.channel_id,
+            "channelName": self.channel_name,
+            "publishedDate": self.published_date.map(|d| d.to_rfc3339()),
+            "duration": self.duration,
+            "viewCount": self.view_count,
+            "isShort": self.is_short,
+            "liveStatus": self.live_status.map(|s| s.as_str()),
+        })
+    }
+}
+
+/// What `gc` removed, or would remove on a dry run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GcReport {
+    /// Videos no subscription lists, archived or not, and not kept at removal.
+    pub orphaned_videos: usize,
+    /// Watch history of those videos.
+    pub watched: usize,
+    /// Cached details, comments, transcripts and segments of videos no longer stored.
+    pub cached: usize,
+    pub channel_views: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
 }
 
 pub struct PaginatedResult {
@@ -62,12 +628,15 @@ pub struct ChannelStats {
     pub latest_date: Option<String>,
 }
 
+/// Inconclusive Shorts checks allowed before a video keeps its guessed flag.
+const SHORT_CHECK_ATTEMPTS: i64 = 3;
+
 pub struct Database {
     conn: Connection,
     db_path: PathBuf,
 }
 
-fn db_dir() -> PathBuf {
+pub fn db_dir() -> PathBuf {
     let home = dirs::home_dir().expect("Could not find home directory");
     home.join(".feeding-tube")
 }
@@ -115,6 +684,16 @@ impl Database {
         db.create_schema()?;
         db.migrate_from_json()?;
         db.migrate_add_video_metadata()?;
+        db.migrate_add_subscription_kind()?;
+        db.migrate_add_live_status()?;
+        db.migrate_add_short_checked()?;
+        db.migrate_add_subscription_notify()?;
+        db.migrate_add_display_name()?;
+        db.migrate_add_archived()?;
+        db.migrate_add_next_check()?;
+        db.migrate_add_members()?;
+        db.migrate_add_video_sources()?;
+        db.migrate_add_thumbnail()?;
         Ok(db)
     }
 
@@ -126,7 +705,13 @@ impl Database {
                 id TEXT PRIMARY KEY,
                 name TEXT NOT NULL,
                 url TEXT NOT NULL,
-                added_at TEXT DEFAULT CURRENT_TIMESTAMP
+                added_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                kind TEXT NOT NULL DEFAULT 'channel',
+                notify INTEGER NOT NULL DEFAULT 0,
+                display_name TEXT,
+                archived INTEGER NOT NULL DEFAULT 0,
+                next_check_at TEXT,
+                members INTEGER NOT NULL DEFAULT 0
             );
 
             CREATE TABLE IF NOT EXISTS videos (
@@ -139,12 +724,28 @@ impl Database {
                 published_date TEXT,
                 stored_at TEXT DEFAULT CURRENT_TIMESTAMP,
                 duration INTEGER,
-                view_count INTEGER
+                view_count INTEGER,
+                live_status TEXT,
+                short_checked INTEGER DEFAULT 0,
+                thumbnail TEXT
             );
 
             CREATE INDEX IF NOT EXISTS idx_videos_channel ON videos(channel_id);
             CREATE INDEX IF NOT EXISTS idx_videos_published ON videos(published_date DESC);
 
+            CREATE TABLE IF NOT EXISTS video_sources (
+                source_id TEXT NOT NULL,
+                video_id TEXT NOT NULL,
+                PRIMARY KEY (source_id, video_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_video_sources_video ON video_sources(video_id);
+
+            CREATE TABLE IF NOT EXISTS kept_sources (
+                source_id TEXT PRIMARY KEY,
+                removed_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+
             CREATE TABLE IF NOT EXISTS watched (
                 video_id TEXT PRIMARY KEY,
                 watched_at TEXT DEFAULT CURRENT_TIMESTAMP
@@ -160,6 +761,52 @@ impl Database {
                 last_viewed_at TEXT DEFAULT CURRENT_TIMESTAMP
             );
 
+            CREATE TABLE IF NOT EXISTS sponsor_segments (
+                video_id TEXT NOT NULL,
+                start_time REAL NOT NULL,
+                end_time REAL NOT NULL,
+                category TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_sponsor_segments_video ON sponsor_segments(video_id);
+
+            CREATE TABLE IF NOT EXISTS sponsor_checked (
+                video_id TEXT PRIMARY KEY,
+                checked_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS video_details (
+                video_id TEXT PRIMARY KEY,
+                data TEXT NOT NULL,
+                fetched_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS video_comments (
+                video_id TEXT PRIMARY KEY,
+                data TEXT NOT NULL,
+                fetched_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS transcripts (
+                video_id TEXT PRIMARY KEY,
+                language TEXT NOT NULL,
+                automatic INTEGER DEFAULT 0,
+                fetched_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS transcript_lines (
+                video_id TEXT NOT NULL,
+                start_time REAL NOT NULL,
+                text TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_transcript_lines_video ON transcript_lines(video_id);
+
+            CREATE VIRTUAL TABLE IF NOT EXISTS transcript_fts USING fts5(
+                video_id UNINDEXED,
+                text
+            );
+
             CREATE TABLE IF NOT EXISTS migrations (
                 name TEXT PRIMARY KEY,
                 applied_at TEXT DEFAULT CURRENT_TIMESTAMP
@@ -204,6 +851,119 @@ impl Database {
         Ok(())
     }
 
+    fn migrate_add_subscription_kind(&self) -> Result<(), String> {
+        if self.has_migration("add_subscription_kind") {
+            return Ok(());
+        }
+        let _ = self.conn.execute_batch(
+            "ALTER TABLE subscriptions ADD COLUMN kind TEXT NOT NULL DEFAULT 'channel';",
+        );
+        self.mark_migration("add_subscription_kind")?;
+        Ok(())
+    }
+
+    fn migrate_add_live_status(&self) -> Result<(), String> {
+        if self.has_migration("add_live_status") {
+            return Ok(());
+        }
+        let _ = self
+            .conn
+            .execute_batch("ALTER TABLE videos ADD COLUMN live_status TEXT;");
+        self.mark_migration("add_live_status")?;
+        Ok(())
+    }
+
+    fn migrate_add_short_checked(&self) -> Result<(), String> {
+        if self.has_migration("add_short_checked") {
+            return Ok(());
+        }
+        let _ = self
+            .conn
+            .execute_batch("ALTER TABLE videos ADD COLUMN short_checked INTEGER DEFAULT 0;");
+        self.mark_migration("add_short_checked")?;
+        Ok(())
+    }
+
+    fn migrate_add_subscription_notify(&self) -> Result<(), String> {
+        if self.has_migration("add_subscription_notify") {
+            return Ok(());
+        }
+        let _ = self.conn.execute_batch(
+            "ALTER TABLE subscriptions ADD COLUMN notify INTEGER NOT NULL DEFAULT 0;",
+        );
+        self.mark_migration("add_subscription_notify")?;
+        Ok(())
+    }
+
+    fn migrate_add_display_name(&self) -> Result<(), String> {
+        if self.has_migration("add_display_name") {
+            return Ok(());
+        }
+        let _ = self
+            .conn
+            .execute_batch("ALTER TABLE subscriptions ADD COLUMN display_name TEXT;");
+        self.mark_migration("add_display_name")?;
+        Ok(())
+    }
+
+    fn migrate_add_archived(&self) -> Result<(), String> {
+        if self.has_migration("add_archived") {
+            return Ok(());
+        }
+        let _ = self.conn.execute_batch(
+            "ALTER TABLE subscriptions ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;",
+        );
+        self.mark_migration("add_archived")?;
+        Ok(())
+    }
+
+    fn migrate_add_next_check(&self) -> Result<(), String> {
+        if self.has_migration("add_next_check") {
+            return Ok(());
+        }
+        let _ = self
+            .conn
+            .execute_batch("ALTER TABLE subscriptions ADD COLUMN next_check_at TEXT;");
+        self.mark_migration("add_next_check")?;
+        Ok(())
+    }
+
+    fn migrate_add_members(&self) -> Result<(), String> {
+        if self.has_migration("add_members") {
+            return Ok(());
+        }
+        let _ = self.conn.execute_batch(
+            "ALTER TABLE subscriptions ADD COLUMN members INTEGER NOT NULL DEFAULT 0;",
+        );
+        self.mark_migration("add_members")?;
+        Ok(())
+    }
+
+    fn migrate_add_thumbnail(&self) -> Result<(), String> {
+        if self.has_migration("add_thumbnail") {
+            return Ok(());
+        }
+        let _ = self.conn.execute_batch("ALTER TABLE videos ADD COLUMN thumbnail TEXT;");
+        self.mark_migration("add_thumbnail")?;
+        Ok(())
+    }
+
+    /// Link stored videos to the source they were stored under, which until
+    /// now was kept in `channel_id`.
+    fn migrate_add_video_sources(&self) -> Result<(), String> {
+        if self.has_migration("add_video_sources") {
+            return Ok(());
+        }
+        self.conn
+            .execute_batch(
+                "INSERT OR IGNORE INTO video_sources (source_id, video_id)
+                 SELECT channel_id, id FROM videos WHERE channel_id IS NOT NULL;",
+            )
+            .map_err(|e| format!("Migration failed: {e}"))?;
+        self.mark_migration("add_video_sources")?;
+        Ok(())
+    }
+
     fn migrate_from_json(&mut self) -> Result<(), String> {
         if self.has_migration("json_import") {
             return Ok(());
@@ -334,16 +1094,40 @@ impl Database {
     // ── Subscriptions ──────────────────────────────────────────
 
     pub fn get_subscriptions(&self) -> Vec<Subscription> {
+        self.query_subscriptions(false)
+    }
+
+    pub fn get_archived_subscriptions(&self) -> Vec<Subscription> {
+        self.query_subscriptions(true)
+    }
+
+    /// Active subscriptions followed by archived ones.
+    pub fn get_all_subscriptions(&self) -> Vec<Subscription> {
+        let mut subs = self.query_subscriptions(false);
+        subs.extend(self.query_subscriptions(true));
+        subs
+    }
+
+    fn query_subscriptions(&self, archived: bool) -> Vec<Subscription> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, name, url, added_at FROM subscriptions ORDER BY name COLLATE NOCASE")
+            .prepare(
+                "SELECT id, name, url, added_at, kind, notify, display_name, archived, members FROM subscriptions
+                 WHERE archived = ?
+                 ORDER BY COALESCE(display_name, name) COLLATE NOCASE",
+            )
             .unwrap();
-        stmt.query_map([], |row| {
+        stmt.query_map(params![archived as i32], |row| {
             Ok(Subscription {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 url: row.get(2)?,
                 added_at: row.get(3)?,
+                kind: SourceKind::parse(&row.get::<_, String>(4)?),
+                notify: row.get::<_, i32>(5)? != 0,
+                display_name: row.get(6)?,
+                archived: row.get::<_, i32>(7)? != 0,
+                members: row.get::<_, i32>(8)? != 0,
             })
         })
         .unwrap()
@@ -352,6 +1136,22 @@ impl Database {
     }
 
     pub fn add_subscription(&self, sub: &Subscription) -> Result<(), String> {
+        // Videos kept from an earlier removal belong to it again
+        let _ = self
+            .conn
+            .execute("DELETE FROM kept_sources WHERE source_id = ?", params![sub.id]);
+        // Subscribing again to an archived channel brings it back
+        let restored = self
+            .conn
+            .execute(
+                "UPDATE subscriptions SET archived = 0 WHERE id = ? AND archived = 1",
+                params![sub.id],
+            )
+            .unwrap_or(0);
+        if restored > 0 {
+            return Ok(());
+        }
+
         let exists: bool = self
             .conn
             .query_row(
@@ -367,8 +1167,17 @@ impl Database {
 
         self.conn
             .execute(
-                "INSERT INTO subscriptions (id, name, url) VALUES (?, ?, ?)",
-                params![sub.id, sub.name, sub.url],
+                "INSERT INTO subscriptions (id, name, url, kind, notify, display_name, members)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    sub.id,
+                    sub.name,
+                    sub.url,
+                    sub.kind.as_str(),
+                    sub.notify as i32,
+                    sub.display_name,
+                    sub.members as i32
+                ],
             )
             .map_err(|e| format!("Failed to add subscription: {e}"))?;
         Ok(())
@@ -385,55 +1194,137 @@ impl Database {
         Ok(())
     }
 
-    // ── Settings ───────────────────────────────────────────────
-
-    pub fn get_settings(&self) -> Settings {
-        let mut settings = Settings::default();
-        let mut stmt = self
+    /// Set the name shown for a subscription; None goes back to the upstream name.
+    pub fn rename_subscription(&self, id: &str, display_name: Option<&str>) -> Result<(), String> {
+        let rows = self
             .conn
-            .prepare("SELECT key, value FROM settings")
-            .unwrap();
-        let rows = stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                ))
-            })
-            .unwrap();
-
-        for row in rows.flatten() {
-            let (key, value) = row;
-            match key.as_str() {
-                "player" => {
-                    if let Ok(v) = serde_json::from_str::<String>(&value) {
-                        settings.player = v;
-                    }
-                }
-                "videosPerChannel" => {
-                    if let Ok(v) = serde_json::from_str::<i64>(&value) {
-                        settings.videos_per_channel = v;
-                    }
-                }
-                "hideShorts" => {
-                    if let Ok(v) = serde_json::from_str::<bool>(&value) {
-                        settings.hide_shorts = v;
-                    }
-                }
-                "maxResolution" => {
-                    if let Ok(v) = serde_json::from_str::<String>(&value) {
-                        settings.max_resolution = v;
-                    }
-                }
-                _ => {}
-            }
+            .execute(
+                "UPDATE subscriptions SET display_name = ? WHERE id = ?",
+                params![display_name, id],
+            )
+            .map_err(|e| format!("Failed to rename: {e}"))?;
+        if rows == 0 {
+            return Err("Subscription not found".to_string());
         }
-        settings
+        Ok(())
     }
 
-    pub fn update_setting(&self, key: &str, value: &str) {
-        let _ = self.conn.execute(
-            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+    /// Archive a subscription so refreshes skip it, or bring it back.
+    pub fn set_subscription_archived(&self, id: &str, archived: bool) -> Result<(), String> {
+        let rows = self
+            .conn
+            .execute(
+                "UPDATE subscriptions SET archived = ? WHERE id = ?",
+                params![archived as i32, id],
+            )
+            .map_err(|e| format!("Failed to archive: {e}"))?;
+        if rows == 0 {
+            return Err("Subscription not found".to_string());
+        }
+        Ok(())
+    }
+
+    /// Remove a subscription, handling its stored data as `policy` says.
+    /// Returns how many videos were deleted.
+    pub fn remove_subscription_with(&self, id: &str, policy: DeletePolicy) -> Result<usize, String> {
+        match policy {
+            DeletePolicy::Keep => {
+                self.remove_subscription(id)?;
+                self.conn
+                    .execute("INSERT OR IGNORE INTO kept_sources (source_id) VALUES (?)", params![id])
+                    .map_err(|e| format!("Failed to keep videos: {e}"))?;
+                Ok(0)
+            }
+            DeletePolicy::Archive => self.set_subscription_archived(id, true).map(|_| 0),
+            DeletePolicy::Purge => {
+                self.remove_subscription(id)?;
+                Ok(self.purge_channel_videos(id))
+            }
+        }
+    }
+
+    /// Delete a source's stored videos with their watch state and cached
+    /// details, comments, transcripts and segments. Videos another
+    /// subscription also lists are kept. Returns the videos removed.
+    pub fn purge_channel_videos(&self, channel_id: &str) -> usize {
+        const SOLE: &str = "SELECT video_id FROM video_sources WHERE source_id = ?1
+             AND video_id NOT IN (
+                SELECT video_id FROM video_sources
+                WHERE source_id != ?1 AND source_id IN (SELECT id FROM subscriptions))";
+        let per_video = std::iter::once("watched").chain(VIDEO_CACHE_TABLES);
+        for table in per_video {
+            let _ = self.conn.execute(
+                &format!("DELETE FROM {} WHERE video_id IN ({})", table, SOLE),
+                params![channel_id],
+            );
+        }
+        let _ = self
+            .conn
+            .execute("DELETE FROM channel_views WHERE channel_id = ?", params![channel_id]);
+        let removed = self
+            .conn
+            .execute(&format!("DELETE FROM videos WHERE id IN ({})", SOLE), params![channel_id])
+            .unwrap_or(0);
+        let _ = self
+            .conn
+            .execute("DELETE FROM video_sources WHERE source_id = ?", params![channel_id]);
+        removed
+    }
+
+    pub fn set_subscription_notify(&self, id: &str, notify: bool) -> Result<(), String> {
+        let rows = self
+            .conn
+            .execute(
+                "UPDATE subscriptions SET notify = ? WHERE id = ?",
+                params![notify as i32, id],
+            )
+            .map_err(|e| format!("Failed to update subscription: {e}"))?;
+        if rows == 0 {
+            return Err("Subscription not found".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn set_subscription_members(&self, id: &str, members: bool) -> Result<(), String> {
+        let rows = self
+            .conn
+            .execute(
+                "UPDATE subscriptions SET members = ? WHERE id = ?",
+                params![members as i32, id],
+            )
+            .map_err(|e| format!("Failed to update subscription: {e}"))?;
+        if rows == 0 {
+            return Err("Subscription not found".to_string());
+        }
+        Ok(())
+    }
+
+    // ── Settings ───────────────────────────────────────────────
+
+    pub fn get_settings(&self) -> Settings {
+        let mut settings = Settings::default();
+        let mut stmt = self
+            .conn
+            .prepare("SELECT key, value FROM settings")
+            .unwrap();
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                ))
+            })
+            .unwrap();
+
+        for (key, value) in rows.flatten() {
+            settings.apply(&key, &value);
+        }
+        settings
+    }
+
+    pub fn update_setting(&self, key: &str, value: &str) {
+        let _ = self.conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
             params![key, value],
         );
     }
@@ -447,6 +1338,12 @@ impl Database {
         );
     }
 
+    pub fn unmark_watched(&self, video_id: &str) {
+        let _ = self
+            .conn
+            .execute("DELETE FROM watched WHERE video_id = ?", params![video_id]);
+    }
+
     pub fn get_watched_ids(&self) -> HashSet<String> {
         let mut stmt = self
             .conn
@@ -513,11 +1410,18 @@ impl Database {
                 .map(|d| d.to_rfc3339())
                 .unwrap_or_default();
             let result = self.conn.execute(
-                "INSERT INTO videos (id, title, url, is_short, channel_name, channel_id, published_date, duration, view_count)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "INSERT INTO videos (id, title, url, is_short, channel_name, channel_id, published_date, duration, view_count, live_status, thumbnail)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                  ON CONFLICT(id) DO UPDATE SET
+                   channel_name = COALESCE(excluded.channel_name, channel_name),
+                   channel_id = COALESCE(excluded.channel_id, channel_id),
                    duration = COALESCE(excluded.duration, duration),
-                   view_count = COALESCE(excluded.view_count, view_count)",
+                   view_count = COALESCE(excluded.view_count, view_count),
+                   live_status = COALESCE(excluded.live_status, live_status),
+                   thumbnail = COALESCE(excluded.thumbnail, thumbnail),
+                   published_date = CASE
+                     WHEN excluded.live_status = 'is_upcoming' AND excluded.published_date != ''
+                     THEN excluded.published_date ELSE published_date END",
                 params![
                     v.id,
                     v.title,
@@ -528,18 +1432,62 @@ impl Database {
                     pub_date,
                     v.duration,
                     v.view_count.map(|c| c as i64),
+                    v.live_status.map(|s| s.as_str()),
+                    v.thumbnail,
                 ],
             );
             if let Ok(rows) = result {
                 count += rows;
             }
+            if let Some(ref channel_id) = v.channel_id {
+                self.link_video(channel_id, &v.id);
+            }
+        }
+        count
+    }
+
+    /// Store videos listed by a subscription. Besides its uploader's channel,
+    /// each video is then also listed under `source_id`, so one video can show
+    /// under several channels and playlists.
+    pub fn store_source_videos(&self, source_id: &str, videos: &[Video]) -> usize {
+        let count = self.store_videos(videos);
+        for v in videos {
+            self.link_video(source_id, &v.id);
         }
         count
     }
 
+    fn link_video(&self, source_id: &str, video_id: &str) {
+        let _ = self.conn.execute(
+            "INSERT OR IGNORE INTO video_sources (source_id, video_id) VALUES (?, ?)",
+            params![source_id, video_id],
+        );
+    }
+
+    /// Which of these video IDs are already stored.
+    pub fn get_known_video_ids(&self, ids: &[String]) -> HashSet<String> {
+        let mut stmt = self.conn.prepare("SELECT 1 FROM videos WHERE id = ?").unwrap();
+        ids.iter()
+            .filter(|id| stmt.exists(params![id]).unwrap_or(false))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_video(&self, video_id: &str) -> Option<Video> {
+        self.conn
+            .query_row(
+                "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count, live_status, thumbnail FROM videos WHERE id = ?",
+                params![video_id],
+                |row| Ok(hydrate_video(row)),
+            )
+            .ok()
+    }
+
     pub fn get_stored_videos(&self, channel_id: &str) -> Vec<Video> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count FROM videos WHERE channel_id = ? ORDER BY published_date DESC"
+            "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count, live_status, thumbnail FROM videos
+             WHERE id IN (SELECT video_id FROM video_sources WHERE source_id = ?)
+             ORDER BY published_date DESC"
         ).unwrap();
         stmt.query_map(params![channel_id], |row| Ok(hydrate_video(row)))
             .unwrap()
@@ -547,6 +1495,27 @@ impl Database {
             .collect()
     }
 
+    /// The newest videos of one source, or of all subscriptions as in All
+    /// Videos, optionally only unwatched ones.
+    pub fn list_videos(&self, source_id: Option<&str>, unwatched: bool, limit: usize) -> Vec<Video> {
+        let mut sql = String::from(
+            "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count, live_status, thumbnail FROM videos
+             WHERE id IN (SELECT video_id FROM video_sources
+                          WHERE source_id = ?1 OR (?1 IS NULL AND source_id IN (SELECT id FROM subscriptions)))",
+        );
+        if unwatched {
+            sql.push_str(" AND id NOT IN (SELECT video_id FROM watched)");
+        }
+        sql.push_str(" ORDER BY published_date DESC LIMIT ?2");
+        let mut stmt = self.conn.prepare(&sql).unwrap();
+        stmt.query_map(params![source_id, limit as i64], |row| Ok(hydrate_video(row)))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect()
+    }
+
+    /// A page of the given sources' videos, or with None of All Videos: every
+    /// subscription's, archived ones included.
     pub fn get_stored_videos_paginated(
         &self,
         channel_ids: Option<&[String]>,
@@ -569,7 +1538,8 @@ impl Database {
             let ph_str = placeholders.join(",");
 
             let count_sql = format!(
-                "SELECT COUNT(*) FROM videos WHERE channel_id IN ({})",
+                "SELECT COUNT(*) FROM videos
+                 WHERE id IN (SELECT video_id FROM video_sources WHERE source_id IN ({}))",
                 ph_str
             );
             let mut count_stmt = self.conn.prepare(&count_sql).unwrap();
@@ -578,7 +1548,9 @@ impl Database {
                 .unwrap_or(0);
 
             let select_sql = format!(
-                "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count FROM videos WHERE channel_id IN ({}) ORDER BY published_date DESC LIMIT ?{} OFFSET ?{}",
+                "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count, live_status, thumbnail FROM videos
+                 WHERE id IN (SELECT video_id FROM video_sources WHERE source_id IN ({}))
+                 ORDER BY published_date DESC LIMIT ?{} OFFSET ?{}",
                 ph_str,
                 ids.len() + 1,
                 ids.len() + 2,
@@ -603,12 +1575,16 @@ impl Database {
         } else {
             let total: usize = self
                 .conn
-                .query_row("SELECT COUNT(*) FROM videos", [], |row| row.get(0))
+                .query_row(&format!("SELECT COUNT(*) FROM videos WHERE {}", SUBSCRIBED), [], |row| {
+                    row.get(0)
+                })
                 .unwrap_or(0);
 
-            let mut stmt = self.conn.prepare(
-                "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count FROM videos ORDER BY published_date DESC LIMIT ? OFFSET ?"
-            ).unwrap();
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT id, title, url, is_short, channel_name, channel_id, published_date, stored_at, duration, view_count, live_status, thumbnail FROM videos
+                 WHERE {} ORDER BY published_date DESC LIMIT ? OFFSET ?",
+                SUBSCRIBED
+            )).unwrap();
             let videos: Vec<Video> = stmt
                 .query_map(params![safe_page_size as i64, offset as i64], |row| {
                     Ok(hydrate_video(row))
@@ -628,6 +1604,210 @@ impl Database {
         }
     }
 
+    // ── Video Details ──────────────────────────────────────────
+
+    pub fn store_video_details(&self, video_id: &str, details: &VideoDescription) {
+        if let Ok(data) = serde_json::to_string(details) {
+            let _ = self.conn.execute(
+                "INSERT OR REPLACE INTO video_details (video_id, data, fetched_at) VALUES (?, ?, CURRENT_TIMESTAMP)",
+                params![video_id, data],
+            );
+        }
+    }
+
+    /// Cached details, or None if they should be (re)fetched: never fetched,
+    /// or fetched over a week ago so the counts have gone stale.
+    pub fn get_video_details(&self, video_id: &str) -> Option<VideoDescription> {
+        let data: String = self
+            .conn
+            .query_row(
+                "SELECT data FROM video_details WHERE video_id = ?
+                   AND fetched_at > datetime('now', '-7 days')",
+                params![video_id],
+                |row| row.get(0),
+            )
+            .ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    // ── Comments ───────────────────────────────────────────────
+
+    pub fn store_comments(&self, video_id: &str, comments: &[Comment]) {
+        if let Ok(data) = serde_json::to_string(comments) {
+            let _ = self.conn.execute(
+                "INSERT OR REPLACE INTO video_comments (video_id, data, fetched_at) VALUES (?, ?, CURRENT_TIMESTAMP)",
+                params![video_id, data],
+            );
+        }
+    }
+
+    /// Cached comments, or None if they should be (re)fetched: never fetched,
+    /// or fetched over a day ago and likely missing newer ones.
+    pub fn get_comments(&self, video_id: &str) -> Option<Vec<Comment>> {
+        let data: String = self
+            .conn
+            .query_row(
+                "SELECT data FROM video_comments WHERE video_id = ?
+                   AND fetched_at > datetime('now', '-1 day')",
+                params![video_id],
+                |row| row.get(0),
+            )
+            .ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    // ── Transcripts ────────────────────────────────────────────
+
+    /// Store a transcript, replacing any earlier one, and index its text for search.
+    pub fn store_transcript(&self, video_id: &str, transcript: &Transcript) {
+        for table in ["transcript_lines", "transcript_fts"] {
+            let _ = self.conn.execute(
+                &format!("DELETE FROM {} WHERE video_id = ?", table),
+                params![video_id],
+            );
+        }
+        let _ = self.conn.execute(
+            "INSERT OR REPLACE INTO transcripts (video_id, language, automatic, fetched_at) VALUES (?, ?, ?, CURRENT_TIMESTAMP)",
+            params![video_id, transcript.language, transcript.automatic],
+        );
+        for line in &transcript.lines {
+            let _ = self.conn.execute(
+                "INSERT INTO transcript_lines (video_id, start_time, text) VALUES (?, ?, ?)",
+                params![video_id, line.start, line.text],
+            );
+        }
+        let full_text: Vec<&str> = transcript.lines.iter().map(|l| l.text.as_str()).collect();
+        let _ = self.conn.execute(
+            "INSERT INTO transcript_fts (video_id, text) VALUES (?, ?)",
+            params![video_id, full_text.join("\n")],
+        );
+    }
+
+    pub fn get_transcript(&self, video_id: &str) -> Option<Transcript> {
+        let (language, automatic): (String, bool) = self
+            .conn
+            .query_row(
+                "SELECT language, automatic FROM transcripts WHERE video_id = ?",
+                params![video_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .ok()?;
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT start_time, text FROM transcript_lines WHERE video_id = ? ORDER BY rowid")
+            .unwrap();
+        let lines = stmt
+            .query_map(params![video_id], |row| {
+                Ok(TranscriptLine {
+                    start: row.get(0)?,
+                    text: row.get(1)?,
+                })
+            })
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Some(Transcript {
+            language,
+            automatic,
+            lines,
+        })
+    }
+
+    /// IDs of videos whose transcript contains every word of `query`. Words are
+    /// matched as prefixes so results update sensibly while typing.
+    pub fn search_transcripts(&self, query: &str) -> HashSet<String> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|w| format!("\"{}\"*", w.replace('"', "\"\"")))
+            .collect();
+        if terms.is_empty() {
+            return HashSet::new();
+        }
+
+        let mut stmt = match self
+            .conn
+            .prepare("SELECT video_id FROM transcript_fts WHERE transcript_fts MATCH ?")
+        {
+            Ok(stmt) => stmt,
+            Err(_) => return HashSet::new(),
+        };
+        stmt.query_map(params![terms.join(" ")], |row| row.get::<_, String>(0))
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
+    // ── SponsorBlock ───────────────────────────────────────────
+
+    pub fn store_sponsor_segments(&self, video_id: &str, segments: &[SponsorSegment]) {
+        let _ = self.conn.execute(
+            "DELETE FROM sponsor_segments WHERE video_id = ?",
+            params![video_id],
+        );
+        for seg in segments {
+            let _ = self.conn.execute(
+                "INSERT INTO sponsor_segments (video_id, start_time, end_time, category) VALUES (?, ?, ?, ?)",
+                params![video_id, seg.start, seg.end, seg.category],
+            );
+        }
+        let _ = self.conn.execute(
+            "INSERT OR REPLACE INTO sponsor_checked (video_id) VALUES (?)",
+            params![video_id],
+        );
+    }
+
+    /// Cached segments for a video, or None if it should be (re)fetched: never
+    /// checked, or checked over a day ago with nothing submitted yet.
+    pub fn get_sponsor_segments(&self, video_id: &str) -> Option<Vec<SponsorSegment>> {
+        let checked: bool = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM sponsor_checked WHERE video_id = ?1
+                   AND (checked_at > datetime('now', '-1 day')
+                        OR EXISTS (SELECT 1 FROM sponsor_segments WHERE video_id = ?1))",
+                params![video_id],
+                |_| Ok(true),
+            )
+            .unwrap_or(false);
+        if !checked {
+            return None;
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT start_time, end_time, category FROM sponsor_segments WHERE video_id = ? ORDER BY start_time")
+            .unwrap();
+        Some(
+            stmt.query_map(params![video_id], |row| {
+                Ok(SponsorSegment {
+                    start: row.get(0)?,
+                    end: row.get(1)?,
+                    category: row.get(2)?,
+                })
+            })
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect(),
+        )
+    }
+
+    pub fn get_sponsor_totals(&self) -> HashMap<String, f64> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT video_id, SUM(end_time - start_time) FROM sponsor_segments GROUP BY video_id")
+            .unwrap();
+        stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, f64>(1)?,
+            ))
+        })
+        .unwrap()
+        .filter_map(|r| r.ok())
+        .collect()
+    }
+
     // ── Channel Views ──────────────────────────────────────────
 
     pub fn update_channel_last_viewed(&self, channel_id: &str) {
@@ -648,21 +1828,22 @@ impl Database {
         }
     }
 
-    pub fn get_new_video_counts(&self, hide_shorts: bool) -> HashMap<String, usize> {
-        let short_filter = if hide_shorts {
-            "AND v.is_short = 0"
-        } else {
-            ""
-        };
+    pub fn get_new_video_counts(
+        &self,
+        hide_shorts: bool,
+        hide_stream_vods: bool,
+    ) -> HashMap<String, usize> {
         let now = Utc::now().to_rfc3339();
         let sql = format!(
-            "SELECT v.channel_id, COUNT(*) as count FROM videos v
-             LEFT JOIN channel_views cv ON v.channel_id = cv.channel_id
-             WHERE v.published_date IS NOT NULL AND v.channel_id IS NOT NULL {}
+            "SELECT s.source_id, COUNT(*) as count FROM videos v
+             JOIN video_sources s ON s.video_id = v.id
+             LEFT JOIN channel_views cv ON s.source_id = cv.channel_id
+             WHERE v.published_date IS NOT NULL {}
                AND (cv.last_viewed_at IS NULL OR v.published_date > cv.last_viewed_at)
                AND v.published_date <= ?
-             GROUP BY v.channel_id",
-            short_filter
+               AND COALESCE(v.live_status, '') != 'is_upcoming'
+             GROUP BY s.source_id",
+            video_filter(hide_shorts, hide_stream_vods)
         );
         let mut stmt = self.conn.prepare(&sql).unwrap();
         stmt.query_map(params![now], |row| {
@@ -676,21 +1857,23 @@ impl Database {
         .collect()
     }
 
-    pub fn get_upcoming_video_counts(&self, hide_shorts: bool) -> HashMap<String, usize> {
-        let short_filter = if hide_shorts {
-            "AND v.is_short = 0"
-        } else {
-            ""
-        };
+    /// Unwatched videos per channel that are scheduled but not out yet: either a
+    /// future publish date or a stream/premiere yt-dlp reports as upcoming.
+    pub fn get_upcoming_video_counts(
+        &self,
+        hide_shorts: bool,
+        hide_stream_vods: bool,
+    ) -> HashMap<String, usize> {
         let now = Utc::now().to_rfc3339();
         let sql = format!(
-            "SELECT v.channel_id, COUNT(*) as count FROM videos v
+            "SELECT s.source_id, COUNT(*) as count FROM videos v
+             JOIN video_sources s ON s.video_id = v.id
              LEFT JOIN watched w ON v.id = w.video_id
-             WHERE v.published_date IS NOT NULL AND v.channel_id IS NOT NULL {}
-               AND v.published_date > ?
+             WHERE v.published_date IS NOT NULL {}
+               AND (v.published_date > ? OR v.live_status = 'is_upcoming')
                AND w.video_id IS NULL
-             GROUP BY v.channel_id",
-            short_filter
+             GROUP BY s.source_id",
+            video_filter(hide_shorts, hide_stream_vods)
         );
         let mut stmt = self.conn.prepare(&sql).unwrap();
         stmt.query_map(params![now], |row| {
@@ -704,17 +1887,81 @@ impl Database {
         .collect()
     }
 
-    pub fn get_channel_stats(&self, hide_shorts: bool) -> HashMap<String, ChannelStats> {
-        let short_filter = if hide_shorts {
-            "WHERE v.is_short = 0"
-        } else {
-            ""
-        };
+    // ── Refresh Schedule ───────────────────────────────────────
+
+    /// When each subscription is next due for a feed check. Subscriptions
+    /// never scheduled are missing and count as due.
+    pub fn get_next_checks(&self) -> HashMap<String, DateTime<Utc>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, next_check_at FROM subscriptions WHERE next_check_at IS NOT NULL")
+            .unwrap();
+        stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .unwrap()
+        .filter_map(|r| r.ok())
+        .filter_map(|(id, at)| {
+            DateTime::parse_from_rfc3339(&at)
+                .ok()
+                .map(|dt| (id, dt.with_timezone(&Utc)))
+        })
+        .collect()
+    }
+
+    pub fn set_next_check(&self, id: &str, at: DateTime<Utc>) {
+        let _ = self.conn.execute(
+            "UPDATE subscriptions SET next_check_at = ? WHERE id = ?",
+            params![at.to_rfc3339(), id],
+        );
+    }
+
+    /// Publish dates of each channel's latest `per_channel` uploads, newest
+    /// first. Scheduled premieres and streams are left out.
+    pub fn get_recent_upload_dates(&self, per_channel: usize) -> HashMap<String, Vec<DateTime<Utc>>> {
+        let now = Utc::now().to_rfc3339();
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT source_id, published_date FROM (
+                    SELECT s.source_id, v.published_date, ROW_NUMBER() OVER (
+                        PARTITION BY s.source_id ORDER BY v.published_date DESC
+                    ) AS rn
+                    FROM videos v JOIN video_sources s ON s.video_id = v.id
+                    WHERE v.published_date IS NOT NULL
+                      AND v.published_date <= ?
+                      AND (v.live_status IS NULL OR v.live_status != 'is_upcoming')
+                 )
+                 WHERE rn <= ?
+                 ORDER BY source_id, published_date DESC",
+            )
+            .unwrap();
+        let mut dates: HashMap<String, Vec<DateTime<Utc>>> = HashMap::new();
+        let rows = stmt
+            .query_map(params![now, per_channel as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .unwrap()
+            .filter_map(|r| r.ok());
+        for (channel_id, date) in rows {
+            if let Ok(dt) = DateTime::parse_from_rfc3339(&date) {
+                dates.entry(channel_id).or_default().push(dt.with_timezone(&Utc));
+            }
+        }
+        dates
+    }
+
+    pub fn get_channel_stats(
+        &self,
+        hide_shorts: bool,
+        hide_stream_vods: bool,
+    ) -> HashMap<String, ChannelStats> {
         let sql = format!(
-            "SELECT v.channel_id, COUNT(*) as cnt, MAX(v.published_date) as latest
-             FROM videos v {}
-             GROUP BY v.channel_id",
-            short_filter
+            "SELECT s.source_id, COUNT(*) as cnt, MAX(v.published_date) as latest
+             FROM videos v JOIN video_sources s ON s.video_id = v.id
+             WHERE 1 = 1 {}
+             GROUP BY s.source_id",
+            video_filter(hide_shorts, hide_stream_vods)
         );
         let mut stmt = self.conn.prepare(&sql).unwrap();
         stmt.query_map([], |row| {
@@ -728,17 +1975,18 @@ impl Database {
         .collect()
     }
 
-    pub fn get_fully_watched_channels(&self, hide_shorts: bool) -> HashSet<String> {
-        let short_filter = if hide_shorts {
-            "WHERE v.is_short = 0"
-        } else {
-            ""
-        };
+    pub fn get_fully_watched_channels(
+        &self,
+        hide_shorts: bool,
+        hide_stream_vods: bool,
+    ) -> HashSet<String> {
         let sql = format!(
-            "SELECT v.channel_id, COUNT(*) as total, SUM(CASE WHEN w.video_id IS NOT NULL THEN 1 ELSE 0 END) as watched
-             FROM videos v LEFT JOIN watched w ON v.id = w.video_id {}
-             GROUP BY v.channel_id HAVING total > 0 AND total = watched",
-            short_filter
+            "SELECT s.source_id, COUNT(*) as total, SUM(CASE WHEN w.video_id IS NOT NULL THEN 1 ELSE 0 END) as watched
+             FROM videos v JOIN video_sources s ON s.video_id = v.id
+             LEFT JOIN watched w ON v.id = w.video_id
+             WHERE 1 = 1 {}
+             GROUP BY s.source_id HAVING total > 0 AND total = watched",
+            video_filter(hide_shorts, hide_stream_vods)
         );
         let mut stmt = self.conn.prepare(&sql).unwrap();
         stmt.query_map([], |row| row.get::<_, String>(0))
@@ -746,19 +1994,247 @@ impl Database {
             .filter_map(|r| r.ok())
             .collect()
     }
-}
 
-fn hydrate_video(row: &rusqlite::Row) -> Video {
-    let published_str: Option<String> = row.get(6).unwrap_or(None);
-    let published_date = published_str
-        .as_deref()
-        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-        .map(|d| d.with_timezone(&Utc))
-        .or_else(|| {
-            published_str
-                .as_deref()
-                .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.fZ").ok())
-                .map(|d| d.and_utc())
+    // ── Live Status ────────────────────────────────────────────
+
+    /// Channels worth re-checking for live state: those with a live or upcoming
+    /// video, or with videos stored in the last day whose state is still unknown.
+    pub fn get_live_check_channel_ids(&self) -> Vec<String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT DISTINCT channel_id FROM videos
+                 WHERE channel_id IS NOT NULL
+                   AND (live_status IN ('is_live', 'is_upcoming')
+                        OR (live_status IS NULL AND stored_at > datetime('now', '-1 day')))",
+            )
+            .unwrap();
+        stmt.query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect()
+    }
+
+    /// After a channel's streams tab was listed, recent videos missing from it
+    /// are regular uploads.
+    pub fn mark_not_live(&self, channel_id: &str, stream_ids: &HashSet<String>) {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id FROM videos WHERE channel_id = ? AND live_status IS NULL
+                   AND stored_at > datetime('now', '-1 day')",
+            )
+            .unwrap();
+        let ids: Vec<String> = stmt
+            .query_map(params![channel_id], |row| row.get::<_, String>(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .filter(|id| !stream_ids.contains(id))
+            .collect();
+        for id in ids {
+            let _ = self.conn.execute(
+                "UPDATE videos SET live_status = 'not_live' WHERE id = ?",
+                params![id],
+            );
+        }
+    }
+
+    // ── Shorts Detection ───────────────────────────────────────
+
+    /// Videos whose Shorts flag is still a guess, newest first. `short_checked`
+    /// is 1 once settled and counts down on each inconclusive check; a video is
+    /// given up on after `SHORT_CHECK_ATTEMPTS` failures.
+    pub fn get_unchecked_short_ids(&self, limit: usize) -> Vec<String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id FROM videos
+                 WHERE short_checked <= 0 AND short_checked > ?
+                 ORDER BY short_checked DESC, published_date DESC LIMIT ?",
+            )
+            .unwrap();
+        stmt.query_map(params![-SHORT_CHECK_ATTEMPTS, limit as i64], |row| {
+            row.get::<_, String>(0)
+        })
+        .unwrap()
+        .filter_map(|r| r.ok())
+        .collect()
+    }
+
+    /// Record Shorts checks: a definite answer settles the flag, None counts as
+    /// a failed attempt.
+    pub fn set_short_flags(&self, results: &[(String, Option<bool>)]) {
+        for (id, result) in results {
+            let _ = match result {
+                Some(is_short) => self.conn.execute(
+                    "UPDATE videos SET is_short = ?, short_checked = 1 WHERE id = ?",
+                    params![*is_short as i32, id],
+                ),
+                None => self.conn.execute(
+                    "UPDATE videos SET short_checked = short_checked - 1
+                     WHERE id = ? AND short_checked <= 0",
+                    params![id],
+                ),
+            };
+        }
+    }
+
+    // ── Maintenance ────────────────────────────────────────────
+
+    /// Find, and unless `dry_run` delete, data nothing refers to any more:
+    /// videos no subscription lists (with their watch history),
+    /// cached data of videos no longer stored, and stale channel views.
+    /// Videos of subscriptions removed with `DeletePolicy::Keep` stay. The
+    /// database is vacuumed afterwards, and the report holds what was deleted.
+    pub fn gc(&self, dry_run: bool) -> Result<GcReport, String> {
+        let count = |sql: String| -> usize {
+            self.conn
+                .query_row(&sql, [], |row| row.get::<_, i64>(0))
+                .unwrap_or(0) as usize
+        };
+        let mut report = GcReport {
+            orphaned_videos: count(format!("SELECT COUNT(*) FROM ({})", GC_ORPHANED)),
+            watched: count(format!(
+                "SELECT COUNT(*) FROM watched WHERE video_id IN ({})",
+                GC_ORPHANED
+            )),
+            cached: VIDEO_CACHE_TABLES
+                .iter()
+                .map(|t| count(format!("SELECT COUNT(*) FROM {} WHERE video_id NOT IN ({})", t, GC_LISTED)))
+                .sum(),
+            channel_views: count(format!(
+                "SELECT COUNT(*) FROM channel_views WHERE channel_id NOT IN ({})",
+                GC_SOURCES
+            )),
+            bytes_before: self.database_size(),
+            bytes_after: 0,
+        };
+        if dry_run {
+            report.bytes_after = report.bytes_before;
+            return Ok(report);
+        }
+
+        // All or nothing, so the report never claims deletions that failed
+        self.conn
+            .execute_batch("SAVEPOINT gc;")
+            .map_err(|e| format!("Cleanup failed: {e}"))?;
+        match self.delete_orphans(&mut report) {
+            Ok(()) => {
+                self.conn
+                    .execute_batch("RELEASE gc;")
+                    .map_err(|e| format!("Cleanup failed: {e}"))?;
+            }
+            Err(e) => {
+                let _ = self.conn.execute_batch("ROLLBACK TO gc; RELEASE gc;");
+                return Err(e);
+            }
+        }
+        self.conn
+            .execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")
+            .map_err(|e| format!("Compacting the database failed: {e}"))?;
+        report.bytes_after = self.database_size();
+        Ok(report)
+    }
+
+    /// The deleting half of `gc`, counting the rows actually removed.
+    fn delete_orphans(&self, report: &mut GcReport) -> Result<(), String> {
+        let delete = |sql: String| -> Result<usize, String> {
+            self.conn.execute(&sql, []).map_err(|e| format!("Cleanup failed: {e}"))
+        };
+        report.watched = delete(format!("DELETE FROM watched WHERE video_id IN ({})", GC_ORPHANED))?;
+        report.orphaned_videos = delete(format!("DELETE FROM videos WHERE id IN ({})", GC_ORPHANED))?;
+        delete(format!(
+            "DELETE FROM video_sources
+             WHERE video_id NOT IN (SELECT id FROM videos) OR source_id NOT IN ({})",
+            GC_SOURCES
+        ))?;
+        report.cached = 0;
+        for table in VIDEO_CACHE_TABLES {
+            report.cached +=
+                delete(format!("DELETE FROM {} WHERE video_id NOT IN ({})", table, GC_LISTED))?;
+        }
+        report.channel_views = delete(format!(
+            "DELETE FROM channel_views WHERE channel_id NOT IN ({})",
+            GC_SOURCES
+        ))?;
+        Ok(())
+    }
+
+    fn database_size(&self) -> u64 {
+        self.conn
+            .query_row(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap_or(0) as u64
+    }
+
+    /// Try a write without keeping it, to catch read-only or locked databases.
+    pub fn check_writable(&self) -> Result<(), String> {
+        self.conn
+            .execute_batch(
+                "SAVEPOINT write_check;
+                 INSERT OR REPLACE INTO settings (key, value) VALUES ('writeCheck', '');
+                 ROLLBACK TO write_check;
+                 RELEASE write_check;",
+            )
+            .map_err(|e| {
+                let _ = self.conn.execute_batch("ROLLBACK TO write_check; RELEASE write_check;");
+                format!("Database is not writable: {e}")
+            })
+    }
+}
+
+
+/// Videos listed by a subscription, archived or not. Those of subscriptions
+/// removed with `DeletePolicy::Keep` are out of All Videos until re-added.
+const SUBSCRIBED: &str =
+    "id IN (SELECT video_id FROM video_sources WHERE source_id IN (SELECT id FROM subscriptions))";
+
+/// Sources whose videos `gc` leaves alone: subscriptions, archived or not,
+/// and those removed with `DeletePolicy::Keep`.
+const GC_SOURCES: &str = "SELECT id FROM subscriptions UNION SELECT source_id FROM kept_sources";
+const GC_LISTED: &str = "SELECT video_id FROM video_sources WHERE source_id IN (
+        SELECT id FROM subscriptions UNION SELECT source_id FROM kept_sources)";
+const GC_ORPHANED: &str = "SELECT id FROM videos WHERE id NOT IN (
+        SELECT video_id FROM video_sources WHERE source_id IN (
+            SELECT id FROM subscriptions UNION SELECT source_id FROM kept_sources))";
+
+/// Tables holding fetched data about a single video, re-fetchable on demand.
+const VIDEO_CACHE_TABLES: [&str; 7] = [
+    "sponsor_segments",
+    "sponsor_checked",
+    "video_details",
+    "video_comments",
+    "transcripts",
+    "transcript_lines",
+    "transcript_fts",
+];
+
+/// Extra `AND` conditions on `videos v` for the list filters.
+fn video_filter(hide_shorts: bool, hide_stream_vods: bool) -> String {
+    let mut sql = String::new();
+    if hide_shorts {
+        sql.push_str(" AND v.is_short = 0");
+    }
+    if hide_stream_vods {
+        sql.push_str(" AND COALESCE(v.live_status, '') != 'was_live'");
+    }
+    sql
+}
+
+fn hydrate_video(row: &rusqlite::Row) -> Video {
+    let published_str: Option<String> = row.get(6).unwrap_or(None);
+    let published_date = published_str
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|d| d.with_timezone(&Utc))
+        .or_else(|| {
+            published_str
+                .as_deref()
+                .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.fZ").ok())
+                .map(|d| d.and_utc())
         })
         .or_else(|| {
             published_str
@@ -774,6 +2250,7 @@ fn hydrate_video(row: &rusqlite::Row) -> Video {
     let duration: Option<i64> = row.get(8).unwrap_or(None);
     let duration_string = Some(format_duration(duration));
     let view_count: Option<i64> = row.get(9).unwrap_or(None);
+    let live_status: Option<String> = row.get(10).unwrap_or(None);
 
     Video {
         id: row.get(0).unwrap_or_default(),
@@ -788,6 +2265,8 @@ fn hydrate_video(row: &rusqlite::Row) -> Video {
         duration,
         duration_string,
         view_count: view_count.map(|c| c as u64),
+        live_status: live_status.as_deref().and_then(LiveStatus::parse),
+        thumbnail: row.get(11).unwrap_or(None),
     }
 }
 
@@ -1041,6 +2520,11 @@ mod tests {
             name: name.to_string(),
             url: format!("https://youtube.com/channel/{}", id),
             added_at: None,
+            kind: SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
         }
     }
 
@@ -1058,6 +2542,8 @@ mod tests {
             duration: None,
             duration_string: None,
             view_count: None,
+            live_status: None,
+            thumbnail: None,
         }
     }
 
@@ -1073,6 +2559,28 @@ mod tests {
         assert_eq!(subs[0].id, "ch1");
     }
 
+    #[test]
+    fn test_subscription_kind_round_trip() {
+        let db = test_db();
+        db.add_subscription(&make_sub("UCchan", "Channel")).unwrap();
+        db.add_subscription(&Subscription {
+            id: "PLlist".to_string(),
+            name: "Playlist".to_string(),
+            url: "https://www.youtube.com/playlist?list=PLlist".to_string(),
+            added_at: None,
+            kind: SourceKind::Playlist,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
+        })
+        .unwrap();
+
+        let subs = db.get_subscriptions();
+        assert_eq!(subs[0].kind, SourceKind::Channel);
+        assert_eq!(subs[1].kind, SourceKind::Playlist);
+    }
+
     #[test]
     fn test_add_duplicate_subscription() {
         let db = test_db();
@@ -1203,6 +2711,22 @@ mod tests {
         assert_eq!(settings.videos_per_channel, 15);
         assert!(settings.hide_shorts);
         assert_eq!(settings.max_resolution, "1080");
+        assert!(settings.preferred_codecs.is_empty());
+        assert_eq!(settings.max_fps, None);
+        assert!(settings.allow_hdr);
+        assert!(settings.player_command.is_none());
+        assert_eq!(settings.playback_mode, "auto");
+    }
+
+    #[test]
+    fn test_player_command_setting() {
+        let db = test_db();
+        db.update_setting("playerCommand", "\"celluloid {url}\"");
+        assert_eq!(db.get_settings().player_command.as_deref(), Some("celluloid {url}"));
+
+        // Blank templates fall back to the built-in players
+        db.update_setting("playerCommand", "\"  \"");
+        assert!(db.get_settings().player_command.is_none());
     }
 
     #[test]
@@ -1234,6 +2758,278 @@ mod tests {
         assert_eq!(settings.max_resolution, "1080");
     }
 
+    #[test]
+    fn test_format_preference_settings() {
+        let db = test_db();
+        db.update_setting("preferredCodecs", "[\"avc1\",\"vp9\"]");
+        db.update_setting("maxFps", "30");
+        db.update_setting("allowHdr", "false");
+
+        let settings = db.get_settings();
+        assert_eq!(settings.preferred_codecs, vec!["avc1", "vp9"]);
+        assert_eq!(settings.max_fps, Some(30));
+        assert!(!settings.allow_hdr);
+    }
+
+    #[test]
+    fn test_sponsorblock_settings() {
+        let db = test_db();
+        let settings = db.get_settings();
+        assert!(settings.sponsorblock);
+        assert_eq!(settings.sponsorblock_api, "https://sponsor.ajay.app");
+
+        db.update_setting("sponsorBlock", "false");
+        db.update_setting("sponsorBlockApi", "\"http://127.0.0.1:8080\"");
+        let settings = db.get_settings();
+        assert!(!settings.sponsorblock);
+        assert_eq!(settings.sponsorblock_api, "http://127.0.0.1:8080");
+    }
+
+    #[test]
+    fn test_video_details_roundtrip() {
+        let db = test_db();
+        assert!(db.get_video_details("v1").is_none());
+
+        let details = VideoDescription {
+            title: "Title".to_string(),
+            description: "See https://example.com".to_string(),
+            channel_name: "Channel".to_string(),
+            upload_date: Some("2024-01-15".to_string()),
+            duration: Some(754),
+            view_count: Some(1200),
+            like_count: None,
+            tags: vec!["rust".to_string()],
+            categories: vec!["Education".to_string()],
+            chapters: vec![Chapter { start: 0.0, end: 60.0, title: "Intro".to_string() }],
+            links: vec!["https://example.com".to_string()],
+        };
+        db.store_video_details("v1", &details);
+        assert_eq!(db.get_video_details("v1"), Some(details.clone()));
+
+        let updated = VideoDescription { view_count: Some(5000), ..details };
+        db.store_video_details("v1", &updated);
+        assert_eq!(db.get_video_details("v1").unwrap().view_count, Some(5000));
+
+        db.conn
+            .execute("UPDATE video_details SET fetched_at = datetime('now', '-8 days')", [])
+            .unwrap();
+        assert!(db.get_video_details("v1").is_none());
+    }
+
+    #[test]
+    fn test_comments_cache() {
+        let db = test_db();
+        assert!(db.get_comments("v1").is_none());
+
+        let comments = vec![
+            Comment {
+                id: "c1".to_string(),
+                author: "@alice".to_string(),
+                text: "Great video".to_string(),
+                like_count: Some(12),
+                timestamp: Some(1_700_000_000),
+                is_reply: false,
+                is_uploader: false,
+            },
+            Comment {
+                id: "c1.r1".to_string(),
+                author: "@creator".to_string(),
+                text: "Thanks!".to_string(),
+                like_count: None,
+                timestamp: None,
+                is_reply: true,
+                is_uploader: true,
+            },
+        ];
+        db.store_comments("v1", &comments);
+        assert_eq!(db.get_comments("v1"), Some(comments));
+
+        db.store_comments("v2", &[]);
+        assert_eq!(db.get_comments("v2"), Some(Vec::new()));
+
+        db.conn
+            .execute("UPDATE video_comments SET fetched_at = datetime('now', '-2 days')", [])
+            .unwrap();
+        assert!(db.get_comments("v1").is_none());
+    }
+
+    #[test]
+    fn test_comment_limit_setting() {
+        let db = test_db();
+        assert_eq!(db.get_settings().comment_limit, 100);
+        db.update_setting("commentLimit", "250");
+        assert_eq!(db.get_settings().comment_limit, 250);
+        db.update_setting("commentLimit", "0");
+        assert_eq!(db.get_settings().comment_limit, 1);
+    }
+
+    #[test]
+    fn test_ytdlp_limit_settings() {
+        let db = test_db();
+        let settings = db.get_settings();
+        assert_eq!(settings.ytdlp_concurrency, 4);
+        assert_eq!(settings.ytdlp_requests_per_minute, 60);
+        db.update_setting("ytdlpConcurrency", "0");
+        db.update_setting("ytdlpRequestsPerMinute", "0");
+        db.update_setting("ytdlpRetries", "5");
+        db.update_setting("feedConcurrency", "8");
+        db.update_setting("ytdlpPath", "\"/opt/bin/yt-dlp\"");
+        db.update_setting("ytdlpArgs", "[\"--proxy\", \"socks5://127.0.0.1:9050\"]");
+        let settings = db.get_settings();
+        assert_eq!(settings.ytdlp_path, "/opt/bin/yt-dlp");
+        assert_eq!(settings.ytdlp_args, vec!["--proxy", "socks5://127.0.0.1:9050"]);
+        assert_eq!(settings.ytdlp_concurrency, 1);
+        assert_eq!(settings.ytdlp_requests_per_minute, 0);
+        assert_eq!(settings.ytdlp_retries, 5);
+        assert_eq!(settings.feed_concurrency, 8);
+    }
+
+    #[test]
+    fn test_cookies_settings() {
+        let db = test_db();
+        assert!(db.get_settings().cookies().is_none());
+        db.update_setting("cookiesFromBrowser", "\"chrome:Profile 1\"");
+        let cookies = db.get_settings().cookies().unwrap();
+        assert_eq!(cookies.ytdlp_args(), vec!["--cookies-from-browser", "chrome:Profile 1"]);
+        db.update_setting("cookiesFile", "\" /home/me/cookies.txt \"");
+        let cookies = db.get_settings().cookies().unwrap();
+        assert_eq!(cookies, Cookies::File("/home/me/cookies.txt".to_string()));
+        assert_eq!(cookies.ytdl_raw_option(), "cookies=/home/me/cookies.txt");
+        db.update_setting("cookiesFile", "null");
+        assert!(matches!(db.get_settings().cookies(), Some(Cookies::Browser(_))));
+    }
+
+    #[test]
+    fn test_cookies_redaction() {
+        let file = Cookies::File("/home/me/cookies.txt".to_string());
+        assert_eq!(
+            file.redact("ERROR: /home/me/cookies.txt does not look like a cookies file"),
+            "ERROR: <redacted> does not look like a cookies file"
+        );
+        assert_eq!(format!("{:?}", file), "File(<redacted>)");
+        let browser = Cookies::Browser("firefox:work-profile".to_string());
+        assert_eq!(browser.redact("no profile work-profile in firefox"), "no profile <redacted> in firefox");
+        assert_eq!(browser.redact("using firefox:work-profile"), "using firefox:<redacted>");
+        assert_eq!(format!("{:?}", browser), "Browser(firefox)");
+        assert_eq!(Cookies::Browser("safari".to_string()).redact("safari"), "safari");
+    }
+
+    #[test]
+    fn test_archive_after_days_setting() {
+        let db = test_db();
+        assert_eq!(db.get_settings().archive_after_days, 365);
+        db.update_setting("archiveAfterDays", "0");
+        assert_eq!(db.get_settings().archive_after_days, 0);
+    }
+
+    fn transcript(lines: &[(f64, &str)]) -> Transcript {
+        Transcript {
+            language: "en".to_string(),
+            automatic: true,
+            lines: lines
+                .iter()
+                .map(|&(start, text)| TranscriptLine { start, text: text.to_string() })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_transcript_roundtrip() {
+        let db = test_db();
+        assert!(db.get_transcript("v1").is_none());
+
+        let t = transcript(&[(0.0, "hello there"), (2.5, "general kenobi")]);
+        db.store_transcript("v1", &t);
+        assert_eq!(db.get_transcript("v1"), Some(t));
+
+        let replaced = transcript(&[(1.0, "only line")]);
+        db.store_transcript("v1", &replaced);
+        assert_eq!(db.get_transcript("v1").unwrap().lines.len(), 1);
+    }
+
+    #[test]
+    fn test_search_transcripts() {
+        let db = test_db();
+        db.store_transcript("v1", &transcript(&[(0.0, "Today we build a parser"), (3.0, "in Rust")]));
+        db.store_transcript("v2", &transcript(&[(0.0, "Cooking pasta at home")]));
+
+        assert_eq!(db.search_transcripts("parser rust"), HashSet::from(["v1".to_string()]));
+        assert_eq!(db.search_transcripts("past"), HashSet::from(["v2".to_string()]));
+        assert!(db.search_transcripts("parser pasta").is_empty());
+        assert!(db.search_transcripts("  ").is_empty());
+        assert!(db.search_transcripts("\"unbalanced").is_empty());
+    }
+
+    #[test]
+    fn test_search_transcripts_after_replace() {
+        let db = test_db();
+        db.store_transcript("v1", &transcript(&[(0.0, "old words")]));
+        db.store_transcript("v1", &transcript(&[(0.0, "new words")]));
+        assert!(db.search_transcripts("old").is_empty());
+        assert_eq!(db.search_transcripts("new").len(), 1);
+    }
+
+    #[test]
+    fn test_thumbnails_setting() {
+        let db = test_db();
+        assert_eq!(db.get_settings().thumbnails, "off");
+        db.update_setting("thumbnails", "\"auto\"");
+        assert_eq!(db.get_settings().thumbnails, "auto");
+    }
+
+    #[test]
+    fn test_subtitle_language_setting() {
+        let db = test_db();
+        assert_eq!(db.get_settings().subtitle_language, "en");
+        db.update_setting("subtitleLanguage", "\"de\"");
+        assert_eq!(db.get_settings().subtitle_language, "de");
+    }
+
+    #[test]
+    fn test_sponsor_segments_cache() {
+        let db = test_db();
+        assert!(db.get_sponsor_segments("v1").is_none());
+
+        let segments = vec![
+            SponsorSegment { start: 30.0, end: 60.0, category: "sponsor".to_string() },
+            SponsorSegment { start: 5.0, end: 10.0, category: "intro".to_string() },
+        ];
+        db.store_sponsor_segments("v1", &segments);
+
+        let cached = db.get_sponsor_segments("v1").unwrap();
+        assert_eq!(cached.len(), 2);
+        assert_eq!(cached[0].start, 5.0); // ordered by start
+        assert_eq!(db.get_sponsor_totals().get("v1").copied(), Some(35.0));
+
+        // Re-storing replaces rather than appends
+        db.store_sponsor_segments("v1", &segments[..1]);
+        assert_eq!(db.get_sponsor_segments("v1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_sponsor_segments_checked_but_empty() {
+        let db = test_db();
+        db.store_sponsor_segments("v1", &[]);
+        assert_eq!(db.get_sponsor_segments("v1"), Some(vec![]));
+        assert!(!db.get_sponsor_totals().contains_key("v1"));
+    }
+
+    #[test]
+    fn test_sponsor_segments_stale_empty_result_refetched() {
+        let db = test_db();
+        db.store_sponsor_segments("v1", &[]);
+        db.store_sponsor_segments(
+            "v2",
+            &[SponsorSegment { start: 1.0, end: 2.0, category: "sponsor".to_string() }],
+        );
+        db.conn
+            .execute("UPDATE sponsor_checked SET checked_at = datetime('now', '-2 days')", [])
+            .unwrap();
+
+        assert!(db.get_sponsor_segments("v1").is_none());
+        assert_eq!(db.get_sponsor_segments("v2").unwrap().len(), 1);
+    }
+
     #[test]
     fn test_paginated_videos() {
         let db = test_db();
@@ -1270,14 +3066,38 @@ mod tests {
     #[test]
     fn test_paginated_videos_all() {
         let db = test_db();
-        let videos = vec![make_video("v1", "ch1"), make_video("v2", "ch2")];
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        db.add_subscription(&make_sub("ch2", "Two")).unwrap();
+        let videos = vec![make_video("v1", "ch1"), make_video("v2", "ch2"), make_video("v3", "other")];
         db.store_videos(&videos);
 
+        // Only subscriptions' videos are in All Videos
         let result = db.get_stored_videos_paginated(None, 0, 10);
         assert_eq!(result.total, 2);
         assert_eq!(result.videos.len(), 2);
     }
 
+    #[test]
+    fn test_list_videos() {
+        let db = test_db();
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        db.add_subscription(&make_sub("ch2", "Two")).unwrap();
+        let mut videos = Vec::new();
+        for i in 0..5 {
+            let mut v = make_video(&format!("v{}", i), if i < 3 { "ch1" } else { "ch2" });
+            v.published_date = Some(Utc::now() - chrono::Duration::hours(i as i64));
+            videos.push(v);
+        }
+        db.store_videos(&videos);
+        db.mark_as_watched("v0");
+
+        let ids = |list: Vec<Video>| list.into_iter().map(|v| v.id).collect::<Vec<_>>();
+        assert_eq!(ids(db.list_videos(None, false, 10)), vec!["v0", "v1", "v2", "v3", "v4"]);
+        assert_eq!(ids(db.list_videos(None, true, 2)), vec!["v1", "v2"]);
+        assert_eq!(ids(db.list_videos(Some("ch1"), true, 10)), vec!["v1", "v2"]);
+        assert_eq!(ids(db.list_videos(Some("ch2"), false, 1)), vec!["v3"]);
+    }
+
     #[test]
     fn test_channel_views() {
         let db = test_db();
@@ -1289,12 +3109,12 @@ mod tests {
         db.store_videos(&[video]);
 
         // Before viewing, should have new count
-        let counts = db.get_new_video_counts(false);
+        let counts = db.get_new_video_counts(false, false);
         assert!(counts.get("ch1").copied().unwrap_or(0) > 0);
 
         // After viewing, the count should be 0 (video published before last_viewed)
         db.update_channel_last_viewed("ch1");
-        let counts = db.get_new_video_counts(false);
+        let counts = db.get_new_video_counts(false, false);
         assert_eq!(counts.get("ch1").copied().unwrap_or(0), 0);
     }
 
@@ -1305,7 +3125,7 @@ mod tests {
         db.mark_all_channels_viewed(&ids);
 
         // Should not have new counts (no videos exist yet)
-        let counts = db.get_new_video_counts(false);
+        let counts = db.get_new_video_counts(false, false);
         assert!(counts.is_empty());
     }
 
@@ -1316,13 +3136,13 @@ mod tests {
         db.store_videos(&videos);
 
         // Not watched yet
-        let fully = db.get_fully_watched_channels(false);
+        let fully = db.get_fully_watched_channels(false, false);
         assert!(!fully.contains("ch1"));
 
         // Watch all
         db.mark_as_watched("v1");
         db.mark_as_watched("v2");
-        let fully = db.get_fully_watched_channels(false);
+        let fully = db.get_fully_watched_channels(false, false);
         assert!(fully.contains("ch1"));
     }
 
@@ -1336,11 +3156,11 @@ mod tests {
 
         // Watch only the non-short
         db.mark_as_watched("v1");
-        let fully = db.get_fully_watched_channels(true);
+        let fully = db.get_fully_watched_channels(true, false);
         assert!(fully.contains("ch1"));
 
         // With shorts shown, not fully watched
-        let fully = db.get_fully_watched_channels(false);
+        let fully = db.get_fully_watched_channels(false, false);
         assert!(!fully.contains("ch1"));
     }
 
@@ -1352,6 +3172,10 @@ mod tests {
             "INSERT INTO videos (id, title, url, is_short, channel_name, channel_id, published_date) VALUES (?, ?, ?, ?, ?, ?, ?)",
             params!["v1", "Test", "https://youtube.com/watch?v=v1", 0, "Ch", "ch1", "2024-01-15T10:30:00+00:00"],
         ).unwrap();
+        db.conn.execute(
+            "INSERT INTO video_sources (source_id, video_id) VALUES ('ch1', 'v1')",
+            [],
+        ).unwrap();
 
         let videos = db.get_stored_videos("ch1");
         assert_eq!(videos.len(), 1);
@@ -1404,7 +3228,7 @@ mod tests {
         v3.published_date = Some(Utc::now());
         db.store_videos(&[v1, v2, v3]);
 
-        let stats = db.get_channel_stats(false);
+        let stats = db.get_channel_stats(false, false);
         assert_eq!(stats.get("ch1").unwrap().video_count, 2);
         assert!(stats.get("ch1").unwrap().latest_date.is_some());
         assert_eq!(stats.get("ch2").unwrap().video_count, 1);
@@ -1418,10 +3242,432 @@ mod tests {
         v2.is_short = true;
         db.store_videos(&[v1, v2]);
 
-        let stats = db.get_channel_stats(true);
+        let stats = db.get_channel_stats(true, false);
         assert_eq!(stats.get("ch1").unwrap().video_count, 1);
 
-        let stats = db.get_channel_stats(false);
+        let stats = db.get_channel_stats(false, false);
         assert_eq!(stats.get("ch1").unwrap().video_count, 2);
     }
+
+    #[test]
+    fn test_video_listed_under_channel_and_playlist() {
+        let db = test_db();
+        db.add_subscription(&make_sub("ch1", "Uploader")).unwrap();
+        db.add_subscription(&Subscription {
+            kind: SourceKind::Playlist,
+            ..make_sub("PL1", "Mix")
+        })
+        .unwrap();
+        let mut v = make_video("v1", "ch1");
+        v.channel_name = Some("Uploader".to_string());
+
+        db.store_source_videos("ch1", &[v.clone()]);
+        db.store_source_videos("PL1", &[v]);
+
+        for source in ["ch1", "PL1"] {
+            let videos = db.get_stored_videos(source);
+            assert_eq!(videos.len(), 1, "{}", source);
+            assert_eq!(videos[0].channel_name.as_deref(), Some("Uploader"));
+            assert_eq!(videos[0].channel_id.as_deref(), Some("ch1"));
+        }
+        let ids = ["ch1".to_string(), "PL1".to_string()];
+        assert_eq!(db.get_stored_videos_paginated(Some(&ids), 0, 10).total, 1);
+        let stats = db.get_channel_stats(false, false);
+        assert_eq!(stats["ch1"].video_count, 1);
+        assert_eq!(stats["PL1"].video_count, 1);
+
+        // Purging the playlist leaves the video its channel still lists
+        assert_eq!(db.purge_channel_videos("PL1"), 0);
+        assert!(db.get_stored_videos("PL1").is_empty());
+        assert_eq!(db.get_stored_videos("ch1").len(), 1);
+    }
+
+    // ── Live status tests ─────────────────────────────────────
+
+    #[test]
+    fn test_live_status_parse() {
+        assert_eq!(LiveStatus::parse("is_live"), Some(LiveStatus::Live));
+        assert_eq!(LiveStatus::parse("post_live"), Some(LiveStatus::WasLive));
+        assert_eq!(LiveStatus::parse("not_live"), Some(LiveStatus::NotLive));
+        assert_eq!(LiveStatus::parse("whatever"), None);
+    }
+
+    #[test]
+    fn test_live_status_upsert() {
+        let db = test_db();
+        let mut v = make_video("v1", "ch1");
+        v.published_date = Some(Utc::now() + chrono::Duration::hours(3));
+        v.live_status = Some(LiveStatus::Upcoming);
+        db.store_videos(&[v.clone()]);
+
+        // A later RSS sighting knows nothing about live state
+        let mut rss = make_video("v1", "ch1");
+        rss.published_date = Some(Utc::now() - chrono::Duration::days(1));
+        db.store_videos(&[rss]);
+        let stored = &db.get_stored_videos("ch1")[0];
+        assert_eq!(stored.live_status, Some(LiveStatus::Upcoming));
+        assert!(stored.published_date.unwrap() > Utc::now());
+
+        // Rescheduled, then live
+        let later = Utc::now() + chrono::Duration::hours(5);
+        v.published_date = Some(later);
+        db.store_videos(&[v.clone()]);
+        let stored = &db.get_stored_videos("ch1")[0];
+        assert_eq!(stored.published_date.unwrap().timestamp(), later.timestamp());
+
+        v.live_status = Some(LiveStatus::Live);
+        db.store_videos(&[v]);
+        assert_eq!(db.get_stored_videos("ch1")[0].live_status, Some(LiveStatus::Live));
+    }
+
+    #[test]
+    fn test_upcoming_counts_use_live_status() {
+        let db = test_db();
+        let mut premiere = make_video("v1", "ch1");
+        premiere.published_date = Some(Utc::now() - chrono::Duration::minutes(5));
+        premiere.live_status = Some(LiveStatus::Upcoming);
+        db.store_videos(&[premiere, make_video("v2", "ch1")]);
+
+        assert_eq!(db.get_upcoming_video_counts(false, false).get("ch1"), Some(&1));
+        assert_eq!(db.get_new_video_counts(false, false).get("ch1"), Some(&1));
+    }
+
+    #[test]
+    fn test_hide_stream_vods() {
+        let db = test_db();
+        let mut vod = make_video("v1", "ch1");
+        vod.live_status = Some(LiveStatus::WasLive);
+        db.store_videos(&[vod, make_video("v2", "ch1")]);
+
+        assert_eq!(db.get_channel_stats(false, false).get("ch1").unwrap().video_count, 2);
+        assert_eq!(db.get_channel_stats(false, true).get("ch1").unwrap().video_count, 1);
+        assert_eq!(db.get_new_video_counts(false, true).get("ch1"), Some(&1));
+    }
+
+    #[test]
+    fn test_live_check_channels_and_mark_not_live() {
+        let db = test_db();
+        let mut live = make_video("v1", "ch1");
+        live.live_status = Some(LiveStatus::Live);
+        let mut done = make_video("v2", "ch2");
+        done.live_status = Some(LiveStatus::NotLive);
+        db.store_videos(&[live, done, make_video("v3", "ch3"), make_video("v4", "ch3")]);
+
+        let mut due = db.get_live_check_channel_ids();
+        due.sort();
+        assert_eq!(due, vec!["ch1".to_string(), "ch3".to_string()]);
+
+        let streams: HashSet<String> = ["v3".to_string()].into_iter().collect();
+        db.mark_not_live("ch3", &streams);
+        let videos = db.get_stored_videos("ch3");
+        let v4 = videos.iter().find(|v| v.id == "v4").unwrap();
+        let v3 = videos.iter().find(|v| v.id == "v3").unwrap();
+        assert_eq!(v4.live_status, Some(LiveStatus::NotLive));
+        assert_eq!(v3.live_status, None);
+    }
+
+    #[test]
+    fn test_hide_stream_vods_setting() {
+        let db = test_db();
+        assert!(!db.get_settings().hide_stream_vods);
+        db.update_setting("hideStreamVods", "true");
+        assert!(db.get_settings().hide_stream_vods);
+    }
+
+    #[test]
+    fn test_subscription_notify_flag() {
+        let db = test_db();
+        db.add_subscription(&make_sub("UC1", "One")).unwrap();
+        assert!(!db.get_subscriptions()[0].notify);
+        db.set_subscription_notify("UC1", true).unwrap();
+        assert!(db.get_subscriptions()[0].notify);
+        assert!(db.set_subscription_notify("UCnope", true).is_err());
+    }
+
+    #[test]
+    fn test_subscription_members_flag() {
+        let db = test_db();
+        db.add_subscription(&make_sub("UC1", "One")).unwrap();
+        assert!(!db.get_subscriptions()[0].members);
+        db.set_subscription_members("UC1", true).unwrap();
+        assert!(db.get_subscriptions()[0].members);
+        assert_eq!(db.get_subscriptions()[0].to_json()["members"], true);
+        assert!(db.set_subscription_members("UCnope", true).is_err());
+    }
+
+    #[test]
+    fn test_get_known_video_ids() {
+        let db = test_db();
+        db.store_videos(&[make_video("v1", "ch1")]);
+        let known = db.get_known_video_ids(&["v1".to_string(), "v2".to_string()]);
+        assert_eq!(known, ["v1".to_string()].into_iter().collect());
+    }
+
+    #[test]
+    fn test_rename_subscription() {
+        let db = test_db();
+        db.add_subscription(&make_sub("UC1", "Foo - Topic")).unwrap();
+        db.rename_subscription("UC1", Some("Foo")).unwrap();
+        let sub = &db.get_subscriptions()[0];
+        assert_eq!(sub.name, "Foo - Topic");
+        assert_eq!(sub.label(), "Foo");
+
+        db.rename_subscription("UC1", None).unwrap();
+        assert_eq!(db.get_subscriptions()[0].label(), "Foo - Topic");
+        assert!(db.rename_subscription("UCnope", Some("x")).is_err());
+    }
+
+    #[test]
+    fn test_subscriptions_sorted_by_label() {
+        let db = test_db();
+        db.add_subscription(&make_sub("UC1", "Alpha")).unwrap();
+        db.add_subscription(&make_sub("UC2", "Beta")).unwrap();
+        db.rename_subscription("UC2", Some("Aardvark")).unwrap();
+        let ids: Vec<String> = db.get_subscriptions().into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["UC2".to_string(), "UC1".to_string()]);
+    }
+
+    #[test]
+    fn test_purge_channel_videos() {
+        let db = test_db();
+        db.store_videos(&[make_video("v1", "ch1"), make_video("v2", "ch1"), make_video("v3", "ch2")]);
+        db.mark_as_watched("v1");
+        db.mark_as_watched("v3");
+        db.update_channel_last_viewed("ch1");
+
+        assert_eq!(db.purge_channel_videos("ch1"), 2);
+        assert!(db.get_stored_videos("ch1").is_empty());
+        assert_eq!(db.get_stored_videos("ch2").len(), 1);
+        assert_eq!(db.get_watched_ids(), ["v3".to_string()].into_iter().collect());
+    }
+
+    #[test]
+    fn test_unmark_watched() {
+        let db = test_db();
+        db.mark_as_watched("v1");
+        db.unmark_watched("v1");
+        assert!(db.get_watched_ids().is_empty());
+    }
+
+    #[test]
+    fn test_get_video() {
+        let db = test_db();
+        db.store_videos(&[make_video("v1", "ch1")]);
+        assert_eq!(db.get_video("v1").unwrap().channel_id.as_deref(), Some("ch1"));
+        assert!(db.get_video("v2").is_none());
+    }
+
+    #[test]
+    fn test_settings_json_keys_apply() {
+        let settings = Settings::default();
+        let json = settings.to_json();
+        let mut applied = Settings::default();
+        for (key, value) in json.as_object().unwrap() {
+            assert!(applied.apply(key, &value.to_string()), "{} not applied", key);
+        }
+        assert!(!applied.apply("noSuchKey", "true"));
+        assert!(!applied.apply("hideShorts", "\"yes\""));
+    }
+
+    // ── Shorts detection tests ─────────────────────────────────
+
+    #[test]
+    fn test_unchecked_short_ids_and_flags() {
+        let db = test_db();
+        db.store_videos(&[make_video("v1", "ch1"), make_video("v2", "ch1")]);
+        let mut ids = db.get_unchecked_short_ids(10);
+        ids.sort();
+        assert_eq!(ids, vec!["v1".to_string(), "v2".to_string()]);
+
+        db.set_short_flags(&[("v1".to_string(), Some(true))]);
+        assert_eq!(db.get_unchecked_short_ids(10), vec!["v2".to_string()]);
+        let videos = db.get_stored_videos("ch1");
+        assert!(videos.iter().find(|v| v.id == "v1").unwrap().is_short);
+
+        // Failed checks are retried, then given up on.
+        for _ in 0..SHORT_CHECK_ATTEMPTS {
+            assert_eq!(db.get_unchecked_short_ids(10), vec!["v2".to_string()]);
+            db.set_short_flags(&[("v2".to_string(), None)]);
+        }
+        assert!(db.get_unchecked_short_ids(10).is_empty());
+    }
+
+    #[test]
+    fn test_short_flag_survives_upsert() {
+        let db = test_db();
+        db.store_videos(&[make_video("v1", "ch1")]);
+        db.set_short_flags(&[("v1".to_string(), Some(true))]);
+        db.store_videos(&[make_video("v1", "ch1")]);
+        let videos = db.get_stored_videos("ch1");
+        assert!(videos[0].is_short);
+        assert!(db.get_unchecked_short_ids(10).is_empty());
+    }
+
+    // ── Removal policy tests ───────────────────────────────────
+
+    #[test]
+    fn test_delete_policy_parse() {
+        for policy in [DeletePolicy::Keep, DeletePolicy::Archive, DeletePolicy::Purge] {
+            assert_eq!(DeletePolicy::parse(policy.as_str()), Some(policy));
+        }
+        assert_eq!(DeletePolicy::parse("nuke"), None);
+    }
+
+    #[test]
+    fn test_remove_with_archive_policy() {
+        let db = test_db();
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        db.add_subscription(&make_sub("ch2", "Two")).unwrap();
+        db.store_videos(&[make_video("v1", "ch1"), make_video("v2", "ch2")]);
+        db.mark_as_watched("v1");
+
+        assert_eq!(db.remove_subscription_with("ch1", DeletePolicy::Archive), Ok(0));
+        let subs = db.get_subscriptions();
+        assert_eq!(subs.len(), 1);
+        assert_eq!(subs[0].id, "ch2");
+        // History is kept and still listed
+        assert_eq!(db.get_stored_videos("ch1").len(), 1);
+        assert!(db.get_watched_ids().contains("v1"));
+        assert_eq!(db.get_stored_videos_paginated(None, 0, 10).total, 2);
+        let archived = db.get_archived_subscriptions();
+        assert_eq!(archived.len(), 1);
+        assert!(archived[0].archived);
+        // Nothing of an archived channel counts as orphaned
+        assert_eq!(db.gc(true).unwrap().orphaned_videos, 0);
+
+        // Subscribing again restores it
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        assert_eq!(db.get_subscriptions().len(), 2);
+    }
+
+    #[test]
+    fn test_set_subscription_archived() {
+        let db = test_db();
+        db.add_subscription(&make_sub("ch1", "Beta")).unwrap();
+        db.add_subscription(&make_sub("ch2", "Alpha")).unwrap();
+
+        db.set_subscription_archived("ch2", true).unwrap();
+        let ids: Vec<String> = db.get_all_subscriptions().into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["ch1", "ch2"]);
+        assert_eq!(db.get_subscriptions().len(), 1);
+
+        db.set_subscription_archived("ch2", false).unwrap();
+        assert!(db.get_archived_subscriptions().is_empty());
+        assert!(db.set_subscription_archived("missing", true).is_err());
+    }
+
+    #[test]
+    fn test_remove_with_keep_and_purge_policies() {
+        let db = test_db();
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        db.add_subscription(&make_sub("ch2", "Two")).unwrap();
+        db.store_videos(&[make_video("v1", "ch1"), make_video("v2", "ch2")]);
+
+        db.mark_as_watched("v1");
+
+        assert_eq!(db.remove_subscription_with("ch1", DeletePolicy::Keep), Ok(0));
+        assert_eq!(db.get_stored_videos("ch1").len(), 1);
+        // Kept videos are out of All Videos and survive gc
+        let all = db.get_stored_videos_paginated(None, 0, 10);
+        assert_eq!(all.total, 1);
+        assert_eq!(all.videos[0].id, "v2");
+        assert_eq!(db.gc(false).unwrap().orphaned_videos, 0);
+        assert_eq!(db.get_stored_videos("ch1").len(), 1);
+        assert!(db.get_watched_ids().contains("v1"));
+        // and are back once it is added again
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        assert_eq!(db.get_stored_videos_paginated(None, 0, 10).total, 2);
+        db.remove_subscription("ch1").unwrap();
+        assert_eq!(db.gc(true).unwrap().orphaned_videos, 1);
+
+        assert_eq!(db.remove_subscription_with("ch2", DeletePolicy::Purge), Ok(1));
+        assert!(db.get_stored_videos("ch2").is_empty());
+        assert!(db.remove_subscription_with("ch2", DeletePolicy::Archive).is_err());
+    }
+
+    // ── Refresh schedule tests ─────────────────────────────────
+
+    #[test]
+    fn test_next_checks() {
+        let db = test_db();
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        db.add_subscription(&make_sub("ch2", "Two")).unwrap();
+        assert!(db.get_next_checks().is_empty());
+
+        let at = Utc::now() + chrono::Duration::hours(3);
+        db.set_next_check("ch1", at);
+        let checks = db.get_next_checks();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks["ch1"].timestamp(), at.timestamp());
+    }
+
+    #[test]
+    fn test_recent_upload_dates() {
+        let db = test_db();
+        let now = Utc::now();
+        let mut videos: Vec<Video> = (1..=4)
+            .map(|i| Video {
+                published_date: Some(now - chrono::Duration::days(i)),
+                ..make_video(&format!("v{}", i), "ch1")
+            })
+            .collect();
+        videos.push(Video {
+            published_date: Some(now + chrono::Duration::days(2)),
+            live_status: Some(LiveStatus::Upcoming),
+            ..make_video("premiere", "ch1")
+        });
+        videos.push(make_video("other", "ch2"));
+        db.store_videos(&videos);
+
+        let dates = db.get_recent_upload_dates(3);
+        assert_eq!(dates["ch1"].len(), 3);
+        assert!(dates["ch1"][0] > dates["ch1"][1]);
+        assert!(dates["ch1"][0] < now);
+        assert_eq!(dates["ch2"].len(), 1);
+    }
+
+    // ── gc tests ───────────────────────────────────────────────
+
+    #[test]
+    fn test_gc() {
+        let db = test_db();
+        db.add_subscription(&make_sub("ch1", "One")).unwrap();
+        db.store_videos(&[make_video("v1", "ch1"), make_video("v2", "gone"), make_video("v3", "gone")]);
+        db.mark_as_watched("v1");
+        db.mark_as_watched("v2");
+        db.mark_as_watched("search-only");
+        let details = VideoDescription {
+            title: "Title".to_string(),
+            description: String::new(),
+            channel_name: "Gone".to_string(),
+            upload_date: None,
+            duration: None,
+            view_count: None,
+            like_count: None,
+            tags: Vec::new(),
+            categories: Vec::new(),
+            chapters: Vec::new(),
+            links: Vec::new(),
+        };
+        db.store_video_details("v2", &details);
+        db.store_video_details("unstored", &details);
+        db.update_channel_last_viewed("gone");
+
+        let dry = db.gc(true).unwrap();
+        assert_eq!(dry.orphaned_videos, 2);
+        assert_eq!(dry.watched, 1);
+        assert_eq!(dry.cached, 2);
+        assert_eq!(dry.channel_views, 1);
+        assert_eq!(db.get_stored_videos("gone").len(), 2);
+
+        let done = db.gc(false).unwrap();
+        assert_eq!((done.orphaned_videos, done.watched, done.cached), (2, 1, 2));
+        assert!(db.get_stored_videos("gone").is_empty());
+        assert_eq!(db.get_stored_videos("ch1").len(), 1);
+        assert!(db.get_video_details("v2").is_none());
+        // Watch history of videos never stored (played from search) is left alone
+        let watched = db.get_watched_ids();
+        assert!(watched.contains("v1") && watched.contains("search-only"));
+        assert_eq!(db.gc(true).unwrap().orphaned_videos, 0);
+    }
 }
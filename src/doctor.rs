@@ -0,0 +1,362 @@
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate};
+use serde_json::{json, Value};
+
+use crate::db::{self, Cookies, Database, Settings};
+use crate::player;
+use crate::runner::{self, Runner};
+
+/// yt-dlp releases older than this are likely to trip over YouTube changes.
+const STALE_AFTER_DAYS: i64 = 60;
+
+const UPDATE: &str = "yt-dlp can't keep up with a YouTube change; update it with yt-dlp -U";
+const RATE_LIMITED: &str = "YouTube is rate limiting (HTTP 429); wait a while or lower ytdlpRequestsPerMinute";
+const MEMBERS_ONLY: &str = "Members-only video; set cookiesFromBrowser to a member's browser";
+
+/// Known yt-dlp failures, matched in lowercase stderr, and what to do about them.
+const SIGNATURES: &[(&str, &str)] = &[
+    (
+        "confirm your age",
+        "Age-restricted video; set cookiesFile or cookiesFromBrowser to a signed-in session",
+    ),
+    (
+        "sign in to confirm",
+        "YouTube wants a signed-in session; set cookiesFile or cookiesFromBrowser",
+    ),
+    ("http error 429", RATE_LIMITED),
+    ("too many requests", RATE_LIMITED),
+    ("nsig extraction failed", UPDATE),
+    ("signature extraction failed", UPDATE),
+    ("unable to extract", UPDATE),
+    ("extractorerror", UPDATE),
+    ("members-only", MEMBERS_ONLY),
+    ("join this channel", MEMBERS_ONLY),
+    ("private video", "This video is private"),
+    ("video unavailable", "This video is unavailable"),
+];
+
+// ── Failure Signatures ─────────────────────────────────────
+
+/// The fix for a recognised yt-dlp failure.
+pub fn diagnose(stderr: &str) -> Option<&'static str> {
+    let lower = stderr.to_lowercase();
+    SIGNATURES
+        .iter()
+        .find(|(pattern, _)| lower.contains(pattern))
+        .map(|(_, fix)| *fix)
+}
+
+/// A yt-dlp failure in one line: the fix when the failure is known,
+/// otherwise its last error line without warnings and progress noise.
+pub fn explain(stderr: &str) -> String {
+    if let Some(fix) = diagnose(stderr) {
+        return fix.to_string();
+    }
+    let lines: Vec<&str> = stderr.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    lines
+        .iter()
+        .rev()
+        .find(|l| l.starts_with("ERROR:"))
+        .or(lines.last())
+        .map(|l| l.trim_start_matches("ERROR:").trim().to_string())
+        .unwrap_or_else(|| "no error output".to_string())
+}
+
+// ── Checks ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Warn => "warn",
+            Status::Fail => "fail",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: String) -> Self {
+        Check { name, status: Status::Ok, detail, fix: None }
+    }
+
+    fn problem(name: &'static str, status: Status, detail: String, fix: &str) -> Self {
+        Check { name, status, detail, fix: Some(fix.to_string()) }
+    }
+}
+
+/// Release date of a yt-dlp version such as `2024.08.06` or the nightly
+/// `2024.08.06.232437`.
+pub fn release_date(version: &str) -> Option<NaiveDate> {
+    let mut parts = version.trim().split('.');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Judge a yt-dlp version by how long ago it was released.
+pub fn version_check(version: &str, today: NaiveDate) -> Check {
+    let version = version.trim();
+    let Some(released) = release_date(version) else {
+        return Check::ok("yt-dlp", format!("version {}", version));
+    };
+    let age = (today - released).num_days().max(0);
+    let detail = format!("version {} ({} days old)", version, age);
+    if age > STALE_AFTER_DAYS {
+        Check::problem("yt-dlp", Status::Warn, detail, "Update it with yt-dlp -U or your package manager")
+    } else {
+        Check::ok("yt-dlp", detail)
+    }
+}
+
+pub async fn check_ytdlp(runner: &Runner, today: NaiveDate) -> Check {
+    let program = &runner.config().program;
+    match runner.output(&["--version"], Duration::from_secs(15)).await {
+        Ok(output) if output.success => {
+            version_check(&String::from_utf8_lossy(&output.stdout), today)
+        }
+        Ok(output) => Check::problem(
+            "yt-dlp",
+            Status::Fail,
+            explain(&String::from_utf8_lossy(&output.stderr)),
+            "Check ytdlpPath and ytdlpArgs",
+        ),
+        Err(e) => Check::problem(
+            "yt-dlp",
+            Status::Fail,
+            e,
+            &format!("Install yt-dlp or point ytdlpPath at it (now {})", program),
+        ),
+    }
+}
+
+/// Whether the configured player, or the program of a custom player
+/// command, is installed.
+pub async fn check_configured_player(settings: &Settings) -> Check {
+    let program = settings
+        .player_command
+        .as_deref()
+        .and_then(|c| c.split_whitespace().next())
+        .unwrap_or(&settings.player)
+        .to_string();
+    if player::check_player(&program).await {
+        Check::ok("player", format!("{} found", program))
+    } else {
+        Check::problem(
+            "player",
+            Status::Fail,
+            format!("{} not found", program),
+            "Install it or pick another player in settings",
+        )
+    }
+}
+
+/// Whether configured cookies can be found. Never shows the path or profile.
+pub fn check_cookies(settings: &Settings) -> Check {
+    match settings.cookies() {
+        None => Check::ok("cookies", "none; age-restricted and members-only videos will fail".to_string()),
+        Some(Cookies::File(ref path)) if !Path::new(path).is_file() => Check::problem(
+            "cookies",
+            Status::Fail,
+            "cookies file not found".to_string(),
+            "Point cookiesFile at a Netscape cookies.txt export",
+        ),
+        Some(cookies) => Check::ok("cookies", cookies.describe()),
+    }
+}
+
+pub fn check_database(db: &Database) -> Check {
+    let location = db::db_dir().display().to_string();
+    match db.check_writable() {
+        Ok(()) => Check::ok("database", format!("{} is writable", location)),
+        Err(e) => Check::problem(
+            "database",
+            Status::Fail,
+            e,
+            &format!("Check permissions and free space in {}", location),
+        ),
+    }
+}
+
+/// The checks that need no database, cheap enough to run at every launch.
+pub async fn tool_checks(settings: &Settings) -> Vec<Check> {
+    let today = Local::now().date_naive();
+    vec![
+        check_ytdlp(runner::global(), today).await,
+        check_configured_player(settings).await,
+        check_cookies(settings),
+    ]
+}
+
+// ── Report ─────────────────────────────────────────────────
+
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub async fn run(db: &Database) -> Self {
+        let mut checks = tool_checks(&db.get_settings()).await;
+        checks.push(check_database(db));
+        Report { checks }
+    }
+
+    /// 1 when a check failed, 0 otherwise; warnings don't fail.
+    pub fn exit_code(&self) -> i32 {
+        i32::from(self.checks.iter().any(|c| c.status == Status::Fail))
+    }
+
+    pub fn to_json(&self) -> Value {
+        let checks: Vec<Value> = self
+            .checks
+            .iter()
+            .map(|c| {
+                json!({
+                    "name": c.name,
+                    "status": c.status.as_str(),
+                    "detail": c.detail,
+                    "fix": c.fix,
+                })
+            })
+            .collect();
+        json!({ "checks": checks, "ok": self.exit_code() == 0 })
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        for c in &self.checks {
+            lines.push(format!("[{:<4}] {}: {}", c.status.as_str(), c.name, c.detail));
+            if let Some(ref fix) = c.fix {
+                lines.push(format!("       {}", fix));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// One status bar line for the worst of the problems found, if any.
+pub fn status_line(checks: &[Check]) -> Option<(String, bool)> {
+    let worst = checks
+        .iter()
+        .filter(|c| c.status != Status::Ok)
+        .max_by_key(|c| c.status == Status::Fail)?;
+    let fix = worst.fix.as_deref().unwrap_or("run --doctor for details");
+    Some((
+        format!("{}: {}; {}", worst.name, worst.detail, fix),
+        worst.status == Status::Fail,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    // ── Signature tests ──────────────────────────────────────
+
+    #[test]
+    fn test_diagnose_known_failures() {
+        let bot = "ERROR: [youtube] abc: Sign in to confirm you're not a bot. Use --cookies-from-browser";
+        assert!(diagnose(bot).unwrap().contains("cookies"));
+        let nsig = "WARNING: [youtube] nsig extraction failed: You may experience throttling";
+        assert!(diagnose(nsig).unwrap().contains("yt-dlp -U"));
+        assert!(diagnose("ERROR: [youtube] x: Unable to extract uploader id").unwrap().contains("yt-dlp -U"));
+        assert!(diagnose("ERROR: something new").is_none());
+        // A 429 elsewhere in the output, such as in a video ID, is no rate limit
+        assert!(diagnose("ERROR: [youtube] a429bc: Video unavailable").unwrap().contains("unavailable"));
+    }
+
+    #[test]
+    fn test_explain() {
+        let rate = "ERROR: [youtube:tab] @x: HTTP Error 429: Too Many Requests";
+        assert!(explain(rate).contains("429"));
+        let unknown = "WARNING: falling back\nERROR: [generic] Unsupported URL: https://x\n";
+        assert_eq!(explain(unknown), "[generic] Unsupported URL: https://x");
+        assert_eq!(explain("just noise"), "just noise");
+        assert_eq!(explain("  \n"), "no error output");
+    }
+
+    // ── Check tests ──────────────────────────────────────────
+
+    #[test]
+    fn test_release_date() {
+        assert_eq!(release_date("2024.08.06\n"), Some(date(2024, 8, 6)));
+        assert_eq!(release_date("2024.08.06.232437"), Some(date(2024, 8, 6)));
+        assert_eq!(release_date("master"), None);
+    }
+
+    #[test]
+    fn test_version_check_age() {
+        let today = date(2024, 10, 1);
+        let fresh = version_check("2024.09.27", today);
+        assert_eq!(fresh.status, Status::Ok);
+        assert_eq!(fresh.detail, "version 2024.09.27 (4 days old)");
+        let stale = version_check("2024.01.01", today);
+        assert_eq!(stale.status, Status::Warn);
+        assert!(stale.fix.unwrap().contains("yt-dlp -U"));
+        assert_eq!(version_check("custom", today).status, Status::Ok);
+    }
+
+    #[test]
+    fn test_check_cookies_hides_path() {
+        let mut settings = Settings::default();
+        assert_eq!(check_cookies(&settings).status, Status::Ok);
+        settings.cookies_file = Some("/nonexistent/secret-cookies.txt".to_string());
+        let missing = check_cookies(&settings);
+        assert_eq!(missing.status, Status::Fail);
+        assert!(!missing.detail.contains("secret"));
+        settings.cookies_file = None;
+        settings.cookies_from_browser = Some("firefox:secret-profile".to_string());
+        assert_eq!(check_cookies(&settings).detail, "cookies from firefox");
+    }
+
+    #[test]
+    fn test_check_database() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(check_database(&db).status, Status::Ok);
+    }
+
+    // ── Report tests ─────────────────────────────────────────
+
+    #[test]
+    fn test_report_and_status_line() {
+        let today = date(2024, 10, 1);
+        let mut report = Report {
+            checks: vec![version_check("2024.01.01", today), Check::ok("database", "fine".to_string())],
+        };
+        assert_eq!(report.exit_code(), 0);
+        let (line, is_error) = status_line(&report.checks).unwrap();
+        assert!(line.starts_with("yt-dlp: version 2024.01.01"));
+        assert!(!is_error);
+
+        report.checks.push(Check::problem("player", Status::Fail, "mpv not found".to_string(), "Install it"));
+        assert_eq!(report.exit_code(), 1);
+        assert_eq!(status_line(&report.checks).unwrap(), ("player: mpv not found; Install it".to_string(), true));
+        let json = report.to_json();
+        assert_eq!(json["ok"], false);
+        assert_eq!(json["checks"][0]["status"], "warn");
+        assert!(report.summary().contains("[fail] player: mpv not found"));
+
+        assert!(status_line(&report.checks[1..2]).is_none());
+    }
+}
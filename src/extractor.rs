@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncBufRead, AsyncReadExt, BufReader};
+use tokio::process::Command;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// What a finished extractor run left behind.
+#[derive(Debug, Clone, Default)]
+pub struct Output {
+    pub success: bool,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// How a streamed run ended.
+#[derive(Debug, Clone, Default)]
+pub struct Exit {
+    pub success: bool,
+    pub stderr: String,
+}
+
+/// A running extractor whose output is read as it arrives. Await `exit`
+/// once `stdout` is drained.
+pub struct Process {
+    pub stdout: Pin<Box<dyn AsyncBufRead + Send>>,
+    pub exit: BoxFuture<'static, Result<Exit, String>>,
+}
+
+/// Something that answers yt-dlp command lines: the real binary, or recorded
+/// output in tests.
+pub trait Extractor: Send + Sync {
+    fn output<'a>(&'a self, args: &'a [&'a str]) -> BoxFuture<'a, Result<Output, String>>;
+
+    fn spawn(&self, args: &[&str]) -> Result<Process, String>;
+}
+
+/// A shared extractor, so a test can keep a handle on one a runner owns.
+impl<E: Extractor + ?Sized> Extractor for Arc<E> {
+    fn output<'a>(&'a self, args: &'a [&'a str]) -> BoxFuture<'a, Result<Output, String>> {
+        (**self).output(args)
+    }
+
+    fn spawn(&self, args: &[&str]) -> Result<Process, String> {
+        (**self).spawn(args)
+    }
+}
+
+// ── yt-dlp ─────────────────────────────────────────────────
+
+/// Runs the yt-dlp binary, with any extra arguments placed before each call's own.
+pub struct YtDlp {
+    program: String,
+    extra_args: Vec<String>,
+}
+
+impl YtDlp {
+    pub fn new(program: &str, extra_args: &[String]) -> Self {
+        YtDlp {
+            program: program.to_string(),
+            extra_args: extra_args.to_vec(),
+        }
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.extra_args).args(args);
+        command
+    }
+
+    fn run_error(&self, e: std::io::Error) -> String {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            format!("{} not found", self.program)
+        } else {
+            format!("Failed to run {}: {e}", self.program)
+        }
+    }
+}
+
+impl Extractor for YtDlp {
+    fn output<'a>(&'a self, args: &'a [&'a str]) -> BoxFuture<'a, Result<Output, String>> {
+        Box::pin(async move {
+            let output = self
+                .command(args)
+                .output()
+                .await
+                .map_err(|e| self.run_error(e))?;
+            Ok(Output {
+                success: output.status.success(),
+                stdout: output.stdout,
+                stderr: output.stderr,
+            })
+        })
+    }
+
+    fn spawn(&self, args: &[&str]) -> Result<Process, String> {
+        let mut child = self
+            .command(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| self.run_error(e))?;
+        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+        let mut stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+        let exit = Box::pin(async move {
+            let mut errors = String::new();
+            let _ = stderr.read_to_string(&mut errors).await;
+            let status = child.wait().await.map_err(|e| format!("Process error: {e}"))?;
+            Ok(Exit {
+                success: status.success(),
+                stderr: errors,
+            })
+        });
+        Ok(Process {
+            stdout: Box::pin(BufReader::new(stdout)),
+            exit,
+        })
+    }
+}
+
+// ── Fixtures ───────────────────────────────────────────────
+
+/// Replays recorded yt-dlp output instead of touching the network. Each call
+/// is matched on its target (the URL or `ytsearch` query) to a file in `dir`;
+/// a `.err` file is replayed as a failure with its contents on stderr.
+/// Unknown targets fail.
+pub struct FixtureExtractor {
+    dir: PathBuf,
+    routes: HashMap<String, String>,
+    calls: Mutex<Vec<Vec<String>>>,
+}
+
+impl FixtureExtractor {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FixtureExtractor {
+            dir: dir.into(),
+            routes: HashMap::new(),
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Answer calls for `target` with the fixture file `name`.
+    pub fn route(mut self, target: &str, name: &str) -> Self {
+        self.routes.insert(target.to_string(), name.to_string());
+        self
+    }
+
+    /// Every command line received so far.
+    pub fn calls(&self) -> Vec<Vec<String>> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn replay(&self, args: &[&str]) -> Result<Output, String> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(args.iter().map(|a| a.to_string()).collect());
+        let target = args
+            .iter()
+            .find(|a| a.starts_with("http") || a.starts_with("ytsearch"))
+            .ok_or("No target in command line")?;
+        let Some(name) = self.routes.get(*target) else {
+            return Ok(Output {
+                success: false,
+                stdout: Vec::new(),
+                stderr: format!("ERROR: no fixture for {}", target).into_bytes(),
+            });
+        };
+        let path = self.dir.join(name);
+        let data = std::fs::read(&path)
+            .map_err(|e| format!("Failed to read fixture {}: {e}", path.display()))?;
+        if name.ends_with(".err") {
+            Ok(Output { success: false, stdout: Vec::new(), stderr: data })
+        } else {
+            Ok(Output { success: true, stdout: data, stderr: Vec::new() })
+        }
+    }
+}
+
+impl Extractor for FixtureExtractor {
+    fn output<'a>(&'a self, args: &'a [&'a str]) -> BoxFuture<'a, Result<Output, String>> {
+        Box::pin(async move { self.replay(args) })
+    }
+
+    fn spawn(&self, args: &[&str]) -> Result<Process, String> {
+        let output = self.replay(args)?;
+        let exit = Exit {
+            success: output.success,
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        };
+        Ok(Process {
+            stdout: Box::pin(std::io::Cursor::new(output.stdout)),
+            exit: Box::pin(async move { Ok(exit) }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncBufReadExt;
+
+    fn fixtures() -> FixtureExtractor {
+        FixtureExtractor::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ytdlp"))
+    }
+
+    // ── FixtureExtractor tests ───────────────────────────────
+
+    #[tokio::test]
+    async fn test_fixture_output_and_calls() {
+        let fake = fixtures().route("ytsearch3:rust", "search_rust.jsonl");
+        let output = fake.output(&["ytsearch3:rust", "--dump-json"]).await.unwrap();
+        assert!(output.success);
+        assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 3);
+        assert_eq!(fake.calls(), vec![vec!["ytsearch3:rust", "--dump-json"]]);
+
+        let missing = fake.output(&["https://example.com"]).await.unwrap();
+        assert!(!missing.success);
+    }
+
+    #[tokio::test]
+    async fn test_fixture_spawn_streams_lines() {
+        let fake = fixtures()
+            .route("https://www.youtube.com/@fixture/videos", "channel_videos.jsonl")
+            .route("https://www.youtube.com/@limited/videos", "rate_limited.err");
+        let process = fake.spawn(&["https://www.youtube.com/@fixture/videos"]).unwrap();
+        let mut lines = process.stdout.lines();
+        let mut count = 0;
+        while lines.next_line().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        assert!(process.exit.await.unwrap().success);
+
+        let failed = fake.spawn(&["https://www.youtube.com/@limited/videos"]).unwrap();
+        let exit = failed.exit.await.unwrap();
+        assert!(!exit.success);
+        assert!(exit.stderr.contains("429"));
+    }
+
+    #[tokio::test]
+    async fn test_ytdlp_extra_args_come_first() {
+        let echo = YtDlp::new("echo", &["--proxy".to_string(), "socks5://x".to_string()]);
+        let output = echo.output(&["--dump-json", "URL"]).await.unwrap();
+        assert!(output.success);
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "--proxy socks5://x --dump-json URL"
+        );
+    }
+}
@@ -0,0 +1,372 @@
+use crate::db::Settings;
+
+/// Height caps offered by the resolution toggle, lowest first. "max" means uncapped.
+pub const RESOLUTION_STEPS: &[&str] = &["360", "480", "720", "1080", "1440", "2160", "max"];
+
+const MIN_HEIGHT: u32 = 360;
+const MAX_HEIGHT: u32 = 2160;
+
+// ── Codecs ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoCodec {
+    Avc,
+    Vp9,
+    Av1,
+}
+
+impl VideoCodec {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "avc" | "avc1" | "h264" => Some(VideoCodec::Avc),
+            "vp9" | "vp09" => Some(VideoCodec::Vp9),
+            "av1" | "av01" => Some(VideoCodec::Av1),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VideoCodec::Avc => "avc1",
+            VideoCodec::Vp9 => "vp9",
+            VideoCodec::Av1 => "av1",
+        }
+    }
+
+    const ALL: [VideoCodec; 3] = [VideoCodec::Avc, VideoCodec::Vp9, VideoCodec::Av1];
+
+    fn filter(&self) -> &'static str {
+        match self {
+            VideoCodec::Avc => "[vcodec^=avc1]",
+            VideoCodec::Vp9 => "[vcodec~='^vp0?9']",
+            VideoCodec::Av1 => "[vcodec^=av01]",
+        }
+    }
+
+    fn exclusion(&self) -> &'static str {
+        match self {
+            VideoCodec::Avc => "[vcodec!^=avc1]",
+            VideoCodec::Vp9 => "[vcodec!~='^vp0?9']",
+            VideoCodec::Av1 => "[vcodec!^=av01]",
+        }
+    }
+}
+
+// ── Preferences ────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatPrefs {
+    pub max_height: Option<u32>,
+    pub codecs: Vec<VideoCodec>,
+    pub max_fps: Option<u32>,
+    pub allow_hdr: bool,
+}
+
+impl Default for FormatPrefs {
+    fn default() -> Self {
+        FormatPrefs {
+            max_height: Some(1080),
+            codecs: Vec::new(),
+            max_fps: None,
+            allow_hdr: true,
+        }
+    }
+}
+
+impl FormatPrefs {
+    pub fn from_settings(settings: &Settings, max_resolution: &str) -> Self {
+        FormatPrefs {
+            max_height: parse_height(max_resolution),
+            codecs: settings
+                .preferred_codecs
+                .iter()
+                .filter_map(|c| VideoCodec::parse(c))
+                .collect(),
+            max_fps: settings.max_fps.filter(|&f| f > 0),
+            allow_hdr: settings.allow_hdr,
+        }
+    }
+
+    fn video_filters(&self) -> String {
+        let mut filters = String::new();
+        if let Some(h) = self.max_height {
+            filters.push_str(&format!("[height<={}]", h));
+        }
+        if let Some(fps) = self.max_fps {
+            filters.push_str(&format!("[fps<={}]", fps));
+        }
+        if !self.allow_hdr {
+            filters.push_str("[dynamic_range=SDR]");
+        }
+        filters
+    }
+
+    /// Filters ruling out the codecs missing from a non-empty preference list,
+    /// so falling back past the preferred ones can't pick e.g. AV1 anyway.
+    fn codec_exclusions(&self) -> String {
+        if self.codecs.is_empty() {
+            return String::new();
+        }
+        VideoCodec::ALL
+            .iter()
+            .filter(|c| !self.codecs.contains(c))
+            .map(VideoCodec::exclusion)
+            .collect()
+    }
+
+    /// yt-dlp `-f` selector for these preferences, or None when nothing is restricted
+    /// and yt-dlp's own default should be used.
+    pub fn format_string(&self) -> Option<String> {
+        let filters = self.video_filters();
+        if filters.is_empty() && self.codecs.is_empty() {
+            return None;
+        }
+
+        let mut alternatives: Vec<String> = self
+            .codecs
+            .iter()
+            .map(|c| format!("bestvideo{}{}+bestaudio", c.filter(), filters))
+            .collect();
+        let exclusions = self.codec_exclusions();
+        alternatives.push(format!("bestvideo{}{}+bestaudio", exclusions, filters));
+        if !filters.is_empty() || !exclusions.is_empty() {
+            alternatives.push(format!("best{}{}", exclusions, filters));
+        }
+        alternatives.push("best".to_string());
+        Some(alternatives.join("/"))
+    }
+
+    /// Selector for resolving direct stream URLs, which always needs an explicit format.
+    pub fn stream_format_string(&self) -> String {
+        self.format_string()
+            .unwrap_or_else(|| "bestvideo+bestaudio/best".to_string())
+    }
+
+    /// Selector restricted to muxed formats, for players that can only take one URL.
+    pub fn combined_format_string(&self) -> String {
+        let filters = self.video_filters();
+        let mut alternatives: Vec<String> = self
+            .codecs
+            .iter()
+            .map(|c| format!("best{}{}", c.filter(), filters))
+            .collect();
+        let exclusions = self.codec_exclusions();
+        if !filters.is_empty() || !exclusions.is_empty() {
+            alternatives.push(format!("best{}{}", exclusions, filters));
+        }
+        alternatives.push("best".to_string());
+        alternatives.join("/")
+    }
+}
+
+/// Parse a stored resolution setting. "max" (or anything unparsable) is uncapped,
+/// numeric caps are clamped to 360–2160.
+pub fn parse_height(value: &str) -> Option<u32> {
+    value
+        .trim()
+        .trim_end_matches('p')
+        .parse::<u32>()
+        .ok()
+        .map(|h| h.clamp(MIN_HEIGHT, MAX_HEIGHT))
+}
+
+/// The resolution step that follows `current`, wrapping from "max" back to the lowest cap.
+pub fn next_resolution(current: &str) -> &'static str {
+    match parse_height(current) {
+        None => RESOLUTION_STEPS[0],
+        Some(h) => RESOLUTION_STEPS
+            .iter()
+            .find(|s| parse_height(s).map(|sh| sh > h).unwrap_or(true))
+            .copied()
+            .unwrap_or("max"),
+    }
+}
+
+pub fn resolution_label(value: &str) -> String {
+    match parse_height(value) {
+        Some(h) => format!("{}p", h),
+        None => "max res".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs(max_height: Option<u32>) -> FormatPrefs {
+        FormatPrefs {
+            max_height,
+            ..FormatPrefs::default()
+        }
+    }
+
+    // ── format_string tests ──────────────────────────────────
+
+    #[test]
+    fn test_default_matches_legacy_1080_string() {
+        assert_eq!(
+            FormatPrefs::default().format_string().as_deref(),
+            Some("bestvideo[height<=1080]+bestaudio/best[height<=1080]/best")
+        );
+    }
+
+    #[test]
+    fn test_unrestricted_has_no_format() {
+        assert_eq!(prefs(None).format_string(), None);
+    }
+
+    #[test]
+    fn test_height_cap() {
+        assert_eq!(
+            prefs(Some(720)).format_string().as_deref(),
+            Some("bestvideo[height<=720]+bestaudio/best[height<=720]/best")
+        );
+    }
+
+    #[test]
+    fn test_fps_cap() {
+        let p = FormatPrefs {
+            max_fps: Some(30),
+            ..prefs(Some(1080))
+        };
+        assert_eq!(
+            p.format_string().as_deref(),
+            Some("bestvideo[height<=1080][fps<=30]+bestaudio/best[height<=1080][fps<=30]/best")
+        );
+    }
+
+    #[test]
+    fn test_hdr_off() {
+        let p = FormatPrefs {
+            allow_hdr: false,
+            ..prefs(None)
+        };
+        assert_eq!(
+            p.format_string().as_deref(),
+            Some("bestvideo[dynamic_range=SDR]+bestaudio/best[dynamic_range=SDR]/best")
+        );
+    }
+
+    #[test]
+    fn test_codec_preference_order() {
+        let p = FormatPrefs {
+            codecs: vec![VideoCodec::Avc, VideoCodec::Vp9],
+            ..prefs(Some(1440))
+        };
+        assert_eq!(
+            p.format_string().as_deref(),
+            Some(
+                "bestvideo[vcodec^=avc1][height<=1440]+bestaudio\
+                 /bestvideo[vcodec~='^vp0?9'][height<=1440]+bestaudio\
+                 /bestvideo[vcodec!^=av01][height<=1440]+bestaudio\
+                 /best[vcodec!^=av01][height<=1440]/best"
+            )
+        );
+    }
+
+    #[test]
+    fn test_codec_preference_without_cap() {
+        let p = FormatPrefs {
+            codecs: vec![VideoCodec::Av1],
+            ..prefs(None)
+        };
+        assert_eq!(
+            p.format_string().as_deref(),
+            Some(
+                "bestvideo[vcodec^=av01]+bestaudio\
+                 /bestvideo[vcodec!^=avc1][vcodec!~='^vp0?9']+bestaudio\
+                 /best[vcodec!^=avc1][vcodec!~='^vp0?9']/best"
+            )
+        );
+    }
+
+    #[test]
+    fn test_stream_format_string_unrestricted() {
+        assert_eq!(prefs(None).stream_format_string(), "bestvideo+bestaudio/best");
+    }
+
+    #[test]
+    fn test_combined_format_string() {
+        assert_eq!(
+            prefs(Some(720)).combined_format_string(),
+            "best[height<=720]/best"
+        );
+        assert_eq!(prefs(None).combined_format_string(), "best");
+    }
+
+    #[test]
+    fn test_combined_format_string_with_codecs() {
+        let p = FormatPrefs {
+            codecs: vec![VideoCodec::Avc],
+            ..prefs(Some(480))
+        };
+        assert_eq!(
+            p.combined_format_string(),
+            "best[vcodec^=avc1][height<=480]/best[vcodec!~='^vp0?9'][vcodec!^=av01][height<=480]/best"
+        );
+    }
+
+    // ── from_settings tests ──────────────────────────────────
+
+    #[test]
+    fn test_from_settings() {
+        let settings = Settings {
+            preferred_codecs: vec!["h264".to_string(), "bogus".to_string(), "vp9".to_string()],
+            max_fps: Some(60),
+            allow_hdr: false,
+            ..Settings::default()
+        };
+        let p = FormatPrefs::from_settings(&settings, "720");
+        assert_eq!(p.max_height, Some(720));
+        assert_eq!(p.codecs, vec![VideoCodec::Avc, VideoCodec::Vp9]);
+        assert_eq!(p.max_fps, Some(60));
+        assert!(!p.allow_hdr);
+    }
+
+    #[test]
+    fn test_from_settings_zero_fps_is_uncapped() {
+        let settings = Settings {
+            max_fps: Some(0),
+            ..Settings::default()
+        };
+        assert_eq!(FormatPrefs::from_settings(&settings, "max").max_fps, None);
+    }
+
+    // ── parse_height / next_resolution tests ─────────────────
+
+    #[test]
+    fn test_parse_height() {
+        assert_eq!(parse_height("1080"), Some(1080));
+        assert_eq!(parse_height("720p"), Some(720));
+        assert_eq!(parse_height("max"), None);
+        assert_eq!(parse_height("144"), Some(360));
+        assert_eq!(parse_height("4320"), Some(2160));
+    }
+
+    #[test]
+    fn test_next_resolution_cycles() {
+        assert_eq!(next_resolution("360"), "480");
+        assert_eq!(next_resolution("1080"), "1440");
+        assert_eq!(next_resolution("2160"), "max");
+        assert_eq!(next_resolution("max"), "360");
+    }
+
+    #[test]
+    fn test_next_resolution_from_off_step_value() {
+        assert_eq!(next_resolution("900"), "1080");
+    }
+
+    #[test]
+    fn test_resolution_label() {
+        assert_eq!(resolution_label("1080"), "1080p");
+        assert_eq!(resolution_label("max"), "max res");
+    }
+
+    #[test]
+    fn test_codec_parse() {
+        assert_eq!(VideoCodec::parse("AV01"), Some(VideoCodec::Av1));
+        assert_eq!(VideoCodec::parse("avc1"), Some(VideoCodec::Avc));
+        assert_eq!(VideoCodec::parse("hevc"), None);
+        assert_eq!(VideoCodec::Vp9.as_str(), "vp9");
+    }
+}
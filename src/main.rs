@@ -1,13 +1,25 @@
 #![allow(dead_code)]
 
 mod app;
+mod cli;
 mod db;
+mod doctor;
+mod extractor;
+mod format;
+mod notify;
 mod player;
+mod runner;
+mod schedule;
+mod shorts;
+mod sponsorblock;
+mod sync;
+mod thumbnail;
+mod transcript;
 mod ui;
 mod ytdlp;
 
 use std::collections::HashSet;
-use std::io;
+use std::io::{self, Write};
 use std::time::Duration;
 
 use clap::Parser;
@@ -15,17 +27,22 @@ use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, Key
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::ExecutableCommand;
 use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
 use ratatui::Terminal;
 
-use app::{App, Mode, Screen};
+use app::{App, InfoTarget, Mode, Screen};
 use db::Database;
+use ytdlp::ChannelTab;
 
 // ── CLI Arguments ──────────────────────────────────────────
 
 #[derive(Parser)]
 #[command(name = "feeding-tube", version, about = "A terminal UI for managing YouTube subscriptions")]
 struct Cli {
-    /// Add a channel URL directly
+    #[command(subcommand)]
+    command: Option<cli::Command>,
+
+    /// Add a channel or playlist URL directly
     #[arg(short, long)]
     add: Option<String>,
 
@@ -40,22 +57,46 @@ struct Cli {
     /// Fetch full history (all or specific channel by index/name)
     #[arg(short, long)]
     prime: Option<Option<String>>,
+
+    /// Fetch new videos for all subscriptions without the TUI
+    #[arg(long)]
+    refresh: bool,
+
+    /// With --refresh, also fetch durations, view counts and Shorts flags
+    #[arg(long, requires = "refresh")]
+    enrich: bool,
+
+    /// Delete data no subscription refers to and compact the database
+    #[arg(long)]
+    gc: bool,
+
+    /// With --gc, only report what would be deleted
+    #[arg(long, requires = "gc")]
+    dry_run: bool,
+
+    /// Check yt-dlp, the player and the database, and suggest fixes
+    #[arg(long)]
+    doctor: bool,
+
+    /// Print machine-readable JSON (subcommands, --refresh, --gc and --doctor)
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Keep refreshing in the background and notify about new uploads
+    /// (interval in minutes, default 30)
+    #[arg(long, value_name = "MINUTES")]
+    watch: Option<Option<u64>>,
 }
 
 // ── Non-interactive Commands ───────────────────────────────
 
 async fn handle_add(url: &str) {
     let db = Database::open().expect("Failed to open database");
-    println!("Fetching channel info for: {}", url);
+    println!("Fetching info for: {}", url);
 
     match ytdlp::get_channel_info(url).await {
         Ok(info) => {
-            let sub = db::Subscription {
-                id: info.id.clone(),
-                name: info.name.clone(),
-                url: info.url.clone(),
-                added_at: None,
-            };
+            let sub = info.to_subscription();
             match db.add_subscription(&sub) {
                 Ok(()) => {
                     println!("Added: {}", info.name);
@@ -68,12 +109,13 @@ async fn handle_add(url: &str) {
                         let existing = db.get_stored_videos(&info.id);
                         let existing_ids: HashSet<String> = existing.iter().filter(|v| v.duration.is_some()).map(|v| v.id.clone()).collect();
                         let name = info.name.clone();
+                        let members = db.get_subscriptions().iter().any(|s| s.id == info.id && s.members);
                         match ytdlp::prime_channel(
-                            &info.id, &info.name, &info.url, &existing_ids, |_| {},
+                            &info.id, &info.name, &info.url, members, &existing_ids, |_| {},
                         ).await {
                             Ok(result) => {
                                 if !result.videos.is_empty() {
-                                    db.store_videos(&result.videos);
+                                    db.store_source_videos(&info.id, &result.videos);
                                 }
                                 println!("{}: {} new videos ({} total)", name, result.added, result.total);
                             }
@@ -88,7 +130,7 @@ async fn handle_add(url: &str) {
             }
         }
         Err(e) => {
-            eprintln!("Failed to add channel: {}", e);
+            eprintln!("Failed to add subscription: {}", e);
             std::process::exit(1);
         }
     }
@@ -103,7 +145,10 @@ fn handle_list() {
     }
     println!("Subscriptions:");
     for (i, sub) in subs.iter().enumerate() {
-        println!("  {}. {}", i + 1, sub.name);
+        match sub.kind {
+            db::SourceKind::Channel => println!("  {}. {}", i + 1, sub.label()),
+            db::SourceKind::Playlist => println!("  {}. {} [playlist]", i + 1, sub.label()),
+        }
         println!("     {}", sub.url);
     }
 }
@@ -130,7 +175,7 @@ async fn handle_prime(query: Option<String>) {
                 let search = q.to_lowercase();
                 let matches: Vec<&db::Subscription> = subs
                     .iter()
-                    .filter(|s| s.name.to_lowercase().contains(&search))
+                    .filter(|s| s.label().to_lowercase().contains(&search))
                     .collect();
                 if matches.is_empty() {
                     eprintln!("No channel found matching \"{}\"", q);
@@ -139,7 +184,7 @@ async fn handle_prime(query: Option<String>) {
                 if matches.len() > 1 {
                     println!("Multiple channels match \"{}\":", q);
                     for (i, m) in matches.iter().enumerate() {
-                        println!("  {}. {}", i + 1, m.name);
+                        println!("  {}. {}", i + 1, m.label());
                     }
                     println!("\nBe more specific or use the index number.");
                     std::process::exit(1);
@@ -154,33 +199,32 @@ async fn handle_prime(query: Option<String>) {
     println!("Priming {} channel(s) with full history...\n", total_channels);
 
     // Gather existing IDs and channel data up front (only skip videos that already have duration)
-    let channel_data: Vec<(String, String, String, HashSet<String>)> = channels_to_prime
+    let channel_data: Vec<(String, String, String, bool, HashSet<String>)> = channels_to_prime
         .iter()
         .map(|ch| {
             let existing = db.get_stored_videos(&ch.id);
             let existing_ids: HashSet<String> = existing.iter().filter(|v| v.duration.is_some()).map(|v| v.id.clone()).collect();
-            (ch.id.clone(), ch.name.clone(), ch.url.clone(), existing_ids)
+            (ch.id.clone(), ch.name.clone(), ch.url.clone(), ch.members, existing_ids)
         })
         .collect();
 
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(String, Result<ytdlp::PrimeResult, String>)>();
-    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(5));
+    let (tx, mut rx) =
+        tokio::sync::mpsc::unbounded_channel::<(String, String, Result<ytdlp::PrimeResult, String>)>();
 
-    for (ch_id, ch_name, ch_url, existing_ids) in channel_data {
+    for (ch_id, ch_name, ch_url, members, existing_ids) in channel_data {
         let tx = tx.clone();
-        let sem = semaphore.clone();
         let name = ch_name.clone();
         tokio::spawn(async move {
-            let _permit = sem.acquire().await.unwrap();
             let result = ytdlp::prime_channel(
                 &ch_id,
                 &ch_name,
                 &ch_url,
+                members,
                 &existing_ids,
                 |_| {},
             )
             .await;
-            let _ = tx.send((name, result));
+            let _ = tx.send((ch_id, name, result));
         });
     }
     drop(tx);
@@ -189,17 +233,20 @@ async fn handle_prime(query: Option<String>) {
     let mut total_added = 0usize;
     let mut failures = 0usize;
 
-    while let Some((name, result)) = rx.recv().await {
+    while let Some((ch_id, name, result)) = rx.recv().await {
         completed += 1;
         match result {
             Ok(r) => {
                 if !r.videos.is_empty() {
-                    db.store_videos(&r.videos);
+                    db.store_source_videos(&ch_id, &r.videos);
                 }
                 println!(
                     "[{}/{}] {}: {} new videos ({} total, {} cached)",
                     completed, total_channels, name, r.added, r.total, r.skipped
                 );
+                if let Some(ref e) = r.members_error {
+                    println!("      members-only videos failed - {}", e);
+                }
                 total_added += r.added;
             }
             Err(e) => {
@@ -213,6 +260,138 @@ async fn handle_prime(query: Option<String>) {
     println!("\nDone! {} videos added{}", total_added, fail_info);
 }
 
+/// Headless refresh for cron and timers. Exits 2 when some subscriptions
+/// failed and 1 when all of them did.
+async fn handle_sync(json: bool, enrich: bool) {
+    let db = match Database::open() {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let subs = db.get_subscriptions();
+    let report = sync::run(&db, &subs, enrich).await;
+    if json {
+        println!("{}", report.to_json());
+    } else if subs.is_empty() {
+        println!("No subscriptions yet. Use --add <url> to add one.");
+    } else {
+        println!("{}", report.summary());
+    }
+    std::process::exit(report.exit_code());
+}
+
+fn handle_gc(dry_run: bool, json: bool) {
+    let db = Database::open().expect("Failed to open database");
+    let report = match db.gc(dry_run) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "dryRun": dry_run,
+                "orphanedVideos": report.orphaned_videos,
+                "watched": report.watched,
+                "cached": report.cached,
+                "channelViews": report.channel_views,
+                "bytesBefore": report.bytes_before,
+                "bytesAfter": report.bytes_after,
+            })
+        );
+        return;
+    }
+    let verb = if dry_run { "Would delete" } else { "Deleted" };
+    println!(
+        "{} {} video(s) no subscription lists and {} watch record(s)",
+        verb, report.orphaned_videos, report.watched
+    );
+    println!(
+        "{} {} cached row(s) and {} channel view(s)",
+        verb, report.cached, report.channel_views
+    );
+    if !dry_run {
+        println!(
+            "Database: {:.1} MB -> {:.1} MB",
+            report.bytes_before as f64 / 1_048_576.0,
+            report.bytes_after as f64 / 1_048_576.0
+        );
+    }
+}
+
+async fn handle_doctor(json: bool) {
+    let db = match Database::open() {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let report = doctor::Report::run(&db).await;
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        println!("{}", report.summary());
+    }
+    std::process::exit(report.exit_code());
+}
+
+// ── Watch Mode ─────────────────────────────────────────────
+
+const DEFAULT_WATCH_MINUTES: u64 = 30;
+
+async fn handle_watch(minutes: Option<u64>) {
+    let db = Database::open().expect("Failed to open database");
+    let minutes = minutes.unwrap_or(DEFAULT_WATCH_MINUTES).max(1);
+    let notifier = match notify::Notifier::connect().await {
+        Ok(n) => n,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Checking for new videos every {} min. Press Ctrl+C to stop.", minutes);
+
+    loop {
+        let subs = db.get_subscriptions();
+        let settings = db.get_settings();
+        let seeded: HashSet<String> = db.get_channel_stats(false, false).into_keys().collect();
+
+        let feeds: Vec<(String, Vec<db::Video>)> = ytdlp::fetch_all_channels_feeds(&subs)
+            .await
+            .into_iter()
+            .filter_map(|(id, result)| result.ok().map(|videos| (id, videos)))
+            .collect();
+        let ids: HashSet<String> = feeds.iter().flat_map(|(_, v)| v).map(|v| v.id.clone()).collect();
+        let ids: Vec<String> = ids.into_iter().collect();
+        let known = db.get_known_video_ids(&ids);
+        let due = notify::pending_notifications(&subs, &feeds, &known, &seeded, settings.hide_shorts);
+        for (id, videos) in &feeds {
+            db.store_source_videos(id, videos);
+        }
+
+        for (sub, videos) in &due {
+            let (summary, body) = notify::new_videos_message(sub.label(), videos);
+            if let Err(e) = notifier.send(&summary, &body).await {
+                eprintln!("{}", e);
+            }
+        }
+        println!(
+            "[{}] {} new video(s), {} notification(s)",
+            chrono::Local::now().format("%H:%M"),
+            ids.len() - known.len(),
+            due.len()
+        );
+
+        tokio::time::sleep(Duration::from_secs(minutes * 60)).await;
+    }
+}
+
 // ── TUI Event Loop ─────────────────────────────────────────
 
 async fn run_tui(initial_channel: Option<db::Subscription>) -> Result<(), Box<dyn std::error::Error>> {
@@ -232,36 +411,52 @@ async fn run_tui(initial_channel: Option<db::Subscription>) -> Result<(), Box<dy
 
     // Initial load
     app.load_subscriptions();
+    // Shown with the health check result, which would otherwise replace it
+    let inactive = app.archive_suggestions().len();
+    let mut archive_hint = (inactive > 0).then(|| {
+        format!(
+            "{} channel(s) without uploads in {} days (🌙); press A to archive",
+            inactive, app.settings.archive_after_days
+        )
+    });
 
     // Background refresh for channels screen
-    let mut bg_refresh: Option<tokio::task::JoinHandle<Vec<db::Video>>> = None;
-    if app.screen == Screen::Channels && !app.subscriptions.is_empty() {
+    // Only channels whose scheduled check has come are fetched
+    type FeedResults = Vec<(String, Result<Vec<db::Video>, String>)>;
+    let mut bg_refresh: Option<tokio::task::JoinHandle<FeedResults>> = None;
+    let due = schedule::due_subscriptions(&app.db, &app.subscriptions, false);
+    if app.screen == Screen::Channels && !due.is_empty() {
         app.loading_message = "Checking for new videos...".to_string();
 
-        let sub_pairs: Vec<(String, String)> = app
-            .subscriptions
-            .iter()
-            .map(|s| (s.id.clone(), s.name.clone()))
-            .collect();
-
         bg_refresh = Some(tokio::spawn(async move {
-            ytdlp::refresh_all_videos(&sub_pairs).await
+            ytdlp::fetch_all_channels_feeds(&due).await
         }));
     }
 
+    // Health check of yt-dlp and the player, reported in the status bar
+    let mut startup_checks = vec![doctor::check_database(&app.db)];
+    if let Some((line, _)) = doctor::status_line(&startup_checks) {
+        app.set_error(&line);
+    }
+    let settings = app.settings.clone();
+    let mut health: Option<tokio::task::JoinHandle<Vec<doctor::Check>>> =
+        Some(tokio::spawn(async move { doctor::tool_checks(&settings).await }));
+
     // If starting on videos screen, load videos
     if app.screen == Screen::Videos {
         load_videos_for_screen(&mut app).await;
     }
 
+    let mut shown_thumbnail: Option<(String, Rect)> = None;
+
     // Main event loop
     loop {
         // Check if background refresh completed
         if let Some(ref handle) = bg_refresh {
             if handle.is_finished() {
                 if let Some(handle) = bg_refresh.take() {
-                    if let Ok(fresh) = handle.await {
-                        app.db.store_videos(&fresh);
+                    if let Ok(results) = handle.await {
+                        schedule::record(&app.db, results);
                         app.refresh_counts();
                         app.has_checked_for_new = true;
                     }
@@ -270,8 +465,80 @@ async fn run_tui(initial_channel: Option<db::Subscription>) -> Result<(), Box<dy
             }
         }
 
+        // Show the outcome of the startup health check
+        if let Some(ref handle) = health {
+            if handle.is_finished() {
+                if let Some(handle) = health.take() {
+                    if let Ok(checks) = handle.await {
+                        startup_checks.extend(checks);
+                    }
+                    let hint = archive_hint.take();
+                    match doctor::status_line(&startup_checks) {
+                        Some((line, is_error)) => {
+                            let line = match hint {
+                                Some(hint) => format!("{} · {}", line, hint),
+                                None => line,
+                            };
+                            if is_error {
+                                app.set_error(&line);
+                            } else {
+                                app.set_message(&line);
+                            }
+                        }
+                        None => {
+                            if let Some(hint) = hint {
+                                app.set_message(&hint);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Re-check live and upcoming streams once the startup refresh is done
+        if bg_refresh.is_none() {
+            poll_live_refresh(&mut app).await;
+            poll_short_check(&mut app).await;
+        }
+
+        // Store SponsorBlock segments fetched in the background
+        if let Some(ref handle) = app.sponsor_prefetch {
+            if handle.is_finished() {
+                if let Some(handle) = app.sponsor_prefetch.take() {
+                    if let Ok(fetched) = handle.await {
+                        for (video_id, segments) in &fetched {
+                            app.db.store_sponsor_segments(video_id, segments);
+                        }
+                        app.refresh_sponsor_totals();
+                    }
+                }
+            }
+        }
+
+        // Keep a finished thumbnail download
+        if let Some(ref handle) = app.thumbnail_fetch {
+            if handle.is_finished() {
+                if let Some(handle) = app.thumbnail_fetch.take() {
+                    if let Ok((video_id, result)) = handle.await {
+                        match result {
+                            Ok(thumb) => {
+                                if app.thumbnails.len() >= MAX_CACHED_THUMBNAILS {
+                                    app.thumbnails.clear();
+                                }
+                                app.thumbnails.insert(video_id, thumb);
+                            }
+                            Err(_) => {
+                                app.thumbnail_failed.insert(video_id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
         app.clear_expired_messages();
         terminal.draw(|f| ui::draw(f, &app))?;
+        show_thumbnail(&mut terminal, &mut app, &mut shown_thumbnail)?;
 
         if event::poll(Duration::from_millis(100))? {
             match event::read()? {
@@ -289,6 +556,9 @@ async fn run_tui(initial_channel: Option<db::Subscription>) -> Result<(), Box<dy
     }
 
     // Cleanup
+    if let Some(seq) = app.thumbnail_protocol.and_then(thumbnail::clear_sequence) {
+        write!(terminal.backend_mut(), "{}", seq)?;
+    }
     disable_raw_mode()?;
     io::stdout().execute(LeaveAlternateScreen)?;
     io::stdout().execute(DisableMouseCapture)?;
@@ -297,6 +567,175 @@ async fn run_tui(initial_channel: Option<db::Subscription>) -> Result<(), Box<dy
     Ok(())
 }
 
+// ── Live Streams ───────────────────────────────────────────
+
+const LIVE_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Store a finished live re-check, and start the next one when it is due.
+async fn poll_live_refresh(app: &mut App) {
+    if let Some(ref handle) = app.live_refresh {
+        if !handle.is_finished() {
+            return;
+        }
+        if let Some(handle) = app.live_refresh.take() {
+            if let Ok(results) = handle.await {
+                for (channel_id, videos) in &results {
+                    app.db.store_source_videos(channel_id, videos);
+                    let ids: HashSet<String> = videos.iter().map(|v| v.id.clone()).collect();
+                    app.db.mark_not_live(channel_id, &ids);
+                    app.apply_live_updates(videos);
+                }
+                app.refresh_counts();
+            }
+        }
+        return;
+    }
+
+    if app
+        .last_live_refresh
+        .is_some_and(|t| t.elapsed() < LIVE_REFRESH_INTERVAL)
+    {
+        return;
+    }
+    app.last_live_refresh = Some(std::time::Instant::now());
+
+    let due: HashSet<String> = app.db.get_live_check_channel_ids().into_iter().collect();
+    let channels: Vec<db::Subscription> = app
+        .subscriptions
+        .iter()
+        .filter(|s| s.kind == db::SourceKind::Channel && due.contains(&s.id))
+        .cloned()
+        .collect();
+    if channels.is_empty() {
+        return;
+    }
+    app.live_refresh = Some(tokio::spawn(async move {
+        ytdlp::refresh_live_channels(&channels).await
+    }));
+}
+
+/// Pause after a Shorts batch with no definite answers, e.g. when offline.
+const SHORT_CHECK_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Settle guessed Shorts flags in the background, one batch at a time.
+async fn poll_short_check(app: &mut App) {
+    if let Some(ref handle) = app.short_check {
+        if !handle.is_finished() {
+            return;
+        }
+        if let Some(handle) = app.short_check.take() {
+            if let Ok(results) = handle.await {
+                if results.iter().any(|(_, r)| r.is_some()) {
+                    app.db.set_short_flags(&results);
+                    app.apply_short_flags(&results);
+                    app.refresh_counts();
+                } else {
+                    app.short_check_idle_until =
+                        Some(std::time::Instant::now() + SHORT_CHECK_BACKOFF);
+                }
+            }
+        }
+        return;
+    }
+
+    if app
+        .short_check_idle_until
+        .is_some_and(|t| std::time::Instant::now() < t)
+    {
+        return;
+    }
+    let ids = app.db.get_unchecked_short_ids(shorts::BATCH_SIZE);
+    if ids.is_empty() {
+        app.short_check_idle_until = Some(std::time::Instant::now() + SHORT_CHECK_BACKOFF);
+        return;
+    }
+    app.short_check = Some(tokio::spawn(shorts::classify(ids)));
+}
+
+// ── Thumbnails ─────────────────────────────────────────────
+
+const MAX_CACHED_THUMBNAILS: usize = 64;
+
+fn request_thumbnail(app: &mut App, video_id: &str) {
+    if app.thumbnail_fetch.is_some()
+        || app.thumbnails.contains_key(video_id)
+        || app.thumbnail_failed.contains(video_id)
+    {
+        return;
+    }
+    let url = app
+        .filtered_videos()
+        .iter()
+        .find(|v| v.id == video_id)
+        .and_then(|v| v.thumbnail.clone());
+    let video_id = video_id.to_string();
+    app.thumbnail_fetch = Some(tokio::spawn(async move {
+        let result = thumbnail::fetch(&video_id, url.as_deref()).await;
+        (video_id, result)
+    }));
+}
+
+/// Fetch the thumbnail for the current slot and, for terminal graphics protocols,
+/// paint it over the cells ratatui left blank. Images are only re-sent when the
+/// video or position changes.
+fn show_thumbnail(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    shown: &mut Option<(String, Rect)>,
+) -> io::Result<()> {
+    let size = terminal.size()?;
+    let slot = ui::thumbnail_slot(app, Rect::new(0, 0, size.width, size.height));
+    if let Some((_, ref video_id)) = slot {
+        request_thumbnail(app, video_id);
+    }
+
+    let protocol = match app.thumbnail_protocol {
+        Some(p) if p.is_graphics() => p,
+        _ => return Ok(()),
+    };
+    let wanted = slot
+        .filter(|(_, id)| app.thumbnails.contains_key(id))
+        .map(|(rect, id)| (id, rect));
+    if wanted == *shown {
+        return Ok(());
+    }
+
+    if shown.is_some() {
+        match thumbnail::clear_sequence(protocol) {
+            Some(seq) => write!(terminal.backend_mut(), "{}", seq)?,
+            None => {
+                // Cell-based images are only removed by repainting the cells
+                terminal.clear()?;
+                terminal.draw(|f| ui::draw(f, app))?;
+            }
+        }
+    }
+
+    if let Some((ref video_id, rect)) = wanted {
+        let cell_px = crossterm::terminal::window_size()
+            .ok()
+            .filter(|w| w.columns > 0 && w.rows > 0)
+            .map(|w| (w.width / w.columns, w.height / w.rows))
+            .unwrap_or((0, 0));
+        if let Some(seq) = thumbnail::escape_sequence(
+            protocol,
+            &app.thumbnails[video_id],
+            rect.width,
+            rect.height,
+            cell_px,
+        ) {
+            terminal
+                .backend_mut()
+                .execute(crossterm::cursor::MoveTo(rect.x, rect.y))?;
+            write!(terminal.backend_mut(), "{}", seq)?;
+        }
+    }
+
+    terminal.backend_mut().flush()?;
+    *shown = wanted;
+    Ok(())
+}
+
 // ── Key Event Handler ──────────────────────────────────────
 
 async fn handle_key_event(
@@ -321,10 +760,12 @@ async fn handle_key_event(
             }
             KeyCode::Backspace => {
                 app.filter_text.pop();
+                app.update_transcript_matches();
                 app.reset_scroll();
             }
             KeyCode::Char(c) => {
                 app.filter_text.push(c);
+                app.update_transcript_matches();
                 app.reset_scroll();
             }
             _ => {}
@@ -332,8 +773,31 @@ async fn handle_key_event(
         return Ok(false);
     }
 
-    // Input mode handling (Add, GlobalSearch, NewSearch)
-    if matches!(app.mode, Mode::Add | Mode::GlobalSearch | Mode::NewSearch) {
+    if app.mode == Mode::TranscriptSearch {
+        match key {
+            KeyCode::Esc => {
+                app.mode = Mode::List;
+                app.transcript_query.clear();
+            }
+            KeyCode::Enter => {
+                app.mode = Mode::List;
+                if !app.transcript_query.trim().is_empty() && !app.transcript_find(true) {
+                    app.set_error(&format!("No match for \"{}\"", app.transcript_query));
+                }
+            }
+            KeyCode::Backspace => {
+                app.transcript_query.pop();
+            }
+            KeyCode::Char(c) => {
+                app.transcript_query.push(c);
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Input mode handling (Add, GlobalSearch, NewSearch, Rename)
+    if matches!(app.mode, Mode::Add | Mode::GlobalSearch | Mode::NewSearch | Mode::Rename) {
         match key {
             KeyCode::Esc => {
                 app.mode = Mode::List;
@@ -342,7 +806,9 @@ async fn handle_key_event(
             KeyCode::Enter => {
                 let text = app.input_text.clone();
                 let text = text.trim().to_string();
-                if !text.is_empty() {
+                if app.mode == Mode::Rename {
+                    app.rename_selected(&text);
+                } else if !text.is_empty() {
                     match app.mode {
                         Mode::Add => {
                             handle_add_channel(app, &text, terminal).await;
@@ -380,7 +846,13 @@ async fn handle_key_event(
         Mode::ConfirmDelete => {
             match key {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
-                    handle_delete_channel(app);
+                    handle_delete_channel(app, db::DeletePolicy::Keep);
+                }
+                KeyCode::Char('a') | KeyCode::Char('A') => {
+                    handle_delete_channel(app, db::DeletePolicy::Archive);
+                }
+                KeyCode::Char('p') | KeyCode::Char('P') => {
+                    handle_delete_channel(app, db::DeletePolicy::Purge);
                 }
                 _ => {
                     app.mode = Mode::List;
@@ -454,6 +926,22 @@ async fn handle_key_event(
             }
             return Ok(false);
         }
+        Mode::ConfirmSubscribeLink => {
+            match key {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    if let Some(url) = app.pending_link.take() {
+                        app.show_description = false;
+                        app.description = None;
+                        handle_add_channel(app, &url, terminal).await;
+                    }
+                }
+                _ => {
+                    app.pending_link = None;
+                    app.mode = Mode::List;
+                }
+            }
+            return Ok(false);
+        }
         Mode::ConfirmChannelWatched => {
             match key {
                 KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
@@ -531,9 +1019,9 @@ async fn handle_channel_keys(
             app.navigate_to_videos(None, app.channel_selected);
             load_videos_for_screen(app).await;
         }
-        KeyCode::Char('r') => {
+        KeyCode::Char('r') | KeyCode::Char('R') => {
             if !app.subscriptions.is_empty() && !app.loading {
-                handle_refresh(app, terminal).await;
+                handle_refresh(app, key == KeyCode::Char('R'), terminal).await;
             }
         }
         KeyCode::Char('s') => {
@@ -561,7 +1049,22 @@ async fn handle_channel_keys(
             app.mode = Mode::ConfirmMarkAll;
         }
         KeyCode::Char('h') => {
-            app.toggle_resolution();
+            app.cycle_resolution();
+        }
+        KeyCode::Char('n') => {
+            app.toggle_selected_notify();
+        }
+        KeyCode::Char('M') => {
+            app.toggle_selected_members();
+        }
+        KeyCode::Char('e') => {
+            app.start_rename();
+        }
+        KeyCode::Char('A') => {
+            app.toggle_selected_archived();
+        }
+        KeyCode::Tab => {
+            app.toggle_archived_section();
         }
         _ => {}
     }
@@ -575,6 +1078,10 @@ async fn handle_video_keys(
 ) -> Result<bool, Box<dyn std::error::Error>> {
     let filtered_len = app.filtered_videos().len();
     let visible_count = terminal.size()?.height.saturating_sub(7) as usize;
+    let has_tabs = app
+        .current_channel
+        .as_ref()
+        .is_some_and(|c| c.kind == db::SourceKind::Channel);
 
     match key {
         KeyCode::Char('q') => return Ok(true),
@@ -582,19 +1089,79 @@ async fn handle_video_keys(
             if !app.filter_text.is_empty() {
                 app.filter_text.clear();
                 app.reset_scroll();
+            } else if app.open_playlist.is_some() {
+                app.close_playlist();
             } else {
                 app.navigate_back();
             }
         }
+        KeyCode::Tab | KeyCode::BackTab if has_tabs && !app.loading => {
+            let tab = if key == KeyCode::Tab {
+                app.channel_tab.next()
+            } else {
+                app.channel_tab.prev()
+            };
+            app.switch_channel_tab(tab);
+            load_channel_tab(app, terminal).await;
+        }
+        KeyCode::Enter if app.showing_playlists() && !app.loading && app.open_selected_playlist() => {
+            load_channel_tab(app, terminal).await;
+        }
+        KeyCode::Char('e') if !app.loading => {
+            handle_queue(app).await;
+        }
+        KeyCode::Up | KeyCode::Char('k') if app.show_transcript => {
+            app.transcript_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.show_transcript => {
+            app.transcript_down();
+        }
+        KeyCode::Char('/') if app.show_transcript => {
+            app.mode = Mode::TranscriptSearch;
+            app.transcript_query.clear();
+        }
+        KeyCode::Char('n') if app.show_transcript => {
+            app.transcript_find(true);
+        }
+        KeyCode::Char('N') if app.show_transcript => {
+            app.transcript_find(false);
+        }
+        KeyCode::Enter if app.show_transcript && !app.loading => {
+            let start = app.selected_transcript_start();
+            handle_play_video(app, start).await;
+        }
+        KeyCode::Char('t') => {
+            if app.show_transcript {
+                app.close_transcript();
+            } else if filtered_len > 0 {
+                let index = app.video_selected;
+                handle_fetch_transcript(app, terminal, index).await;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') if app.show_comments => {
+            app.comments_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.show_comments => {
+            app.comments_down();
+        }
+        KeyCode::Up | KeyCode::Char('k') if app.show_description => {
+            app.info_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.show_description => {
+            app.info_down();
+        }
         KeyCode::Up | KeyCode::Char('k') => {
             app.move_up();
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.move_down(filtered_len, visible_count);
+            app.move_down(app.video_list_len(), visible_count);
         }
         KeyCode::Enter => {
             if !app.loading {
-                handle_play_video(app).await;
+                match app.selected_info_target() {
+                    Some(InfoTarget::Link(url)) => handle_open_link(app, &url).await,
+                    target => handle_play_video(app, target.and_then(|t| t.start())).await,
+                }
             }
         }
         KeyCode::Char('/') => {
@@ -609,6 +1176,14 @@ async fn handle_video_keys(
                 handle_fetch_description(app, terminal).await;
             }
         }
+        KeyCode::Char('c') => {
+            if app.show_comments {
+                app.close_comments();
+            } else if filtered_len > 0 {
+                let index = app.video_selected;
+                handle_fetch_comments(app, terminal, index).await;
+            }
+        }
         KeyCode::Char('w') => {
             app.toggle_watched_current();
         }
@@ -635,11 +1210,15 @@ async fn handle_video_keys(
         }
         KeyCode::Char('r') => {
             if !app.loading {
-                load_videos_for_screen(app).await;
+                if app.channel_tab == ChannelTab::Videos {
+                    load_videos_for_screen(app).await;
+                } else {
+                    load_channel_tab(app, terminal).await;
+                }
             }
         }
         KeyCode::Char('h') => {
-            app.toggle_resolution();
+            app.cycle_resolution();
         }
         _ => {}
     }
@@ -659,6 +1238,46 @@ async fn handle_search_keys(
         KeyCode::Esc | KeyCode::Char('b') => {
             app.navigate_back();
         }
+        KeyCode::Up | KeyCode::Char('k') if app.show_transcript => {
+            app.transcript_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.show_transcript => {
+            app.transcript_down();
+        }
+        KeyCode::Char('/') if app.show_transcript => {
+            app.mode = Mode::TranscriptSearch;
+            app.transcript_query.clear();
+        }
+        KeyCode::Char('n') if app.show_transcript => {
+            app.transcript_find(true);
+        }
+        KeyCode::Char('N') if app.show_transcript => {
+            app.transcript_find(false);
+        }
+        KeyCode::Enter if app.show_transcript && !app.loading => {
+            let start = app.selected_transcript_start();
+            handle_play_search_result(app, start).await;
+        }
+        KeyCode::Char('t') => {
+            if app.show_transcript {
+                app.close_transcript();
+            } else if results_len > 0 {
+                let index = app.search_selected;
+                handle_fetch_transcript(app, _terminal, index).await;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') if app.show_comments => {
+            app.comments_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.show_comments => {
+            app.comments_down();
+        }
+        KeyCode::Up | KeyCode::Char('k') if app.show_description => {
+            app.info_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.show_description => {
+            app.info_down();
+        }
         KeyCode::Up | KeyCode::Char('k') => {
             app.move_up();
         }
@@ -667,7 +1286,10 @@ async fn handle_search_keys(
         }
         KeyCode::Enter => {
             if !app.loading {
-                handle_play_search_result(app).await;
+                match app.selected_info_target() {
+                    Some(InfoTarget::Link(url)) => handle_open_link(app, &url).await,
+                    target => handle_play_search_result(app, target.and_then(|t| t.start())).await,
+                }
             }
         }
         KeyCode::Char('g') => {
@@ -694,8 +1316,16 @@ async fn handle_search_keys(
                 handle_fetch_description_search(app, _terminal).await;
             }
         }
+        KeyCode::Char('c') => {
+            if app.show_comments {
+                app.close_comments();
+            } else if results_len > 0 {
+                let index = app.search_selected;
+                handle_fetch_comments(app, _terminal, index).await;
+            }
+        }
         KeyCode::Char('h') => {
-            app.toggle_resolution();
+            app.cycle_resolution();
         }
         _ => {}
     }
@@ -712,7 +1342,7 @@ fn handle_mouse_event(app: &mut App, mouse: crossterm::event::MouseEvent) {
         MouseEventKind::ScrollDown => {
             let len = match app.screen {
                 Screen::Channels => app.filtered_subscriptions().len(),
-                Screen::Videos => app.filtered_videos().len(),
+                Screen::Videos => app.video_list_len(),
                 Screen::Search => app.filtered_videos().len(),
             };
             // Use a reasonable visible count estimate
@@ -728,7 +1358,7 @@ fn handle_mouse_event(app: &mut App, mouse: crossterm::event::MouseEvent) {
                 let target = scroll + click_index;
                 let len = match app.screen {
                     Screen::Channels => app.filtered_subscriptions().len(),
-                    Screen::Videos => app.filtered_videos().len(),
+                    Screen::Videos => app.video_list_len(),
                     Screen::Search => app.filtered_videos().len(),
                 };
                 if target < len {
@@ -752,18 +1382,13 @@ async fn handle_add_channel(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
 ) {
     app.loading = true;
-    app.loading_message = "Fetching channel info...".to_string();
+    app.loading_message = "Fetching subscription info...".to_string();
     app.mode = Mode::List;
     terminal.draw(|f| ui::draw(f, app)).ok();
 
     match ytdlp::get_channel_info(url).await {
         Ok(info) => {
-            let sub = db::Subscription {
-                id: info.id.clone(),
-                name: info.name.clone(),
-                url: info.url.clone(),
-                added_at: None,
-            };
+            let sub = info.to_subscription();
             match app.db.add_subscription(&sub) {
                 Ok(()) => {
                     app.load_subscriptions();
@@ -786,15 +1411,22 @@ async fn handle_add_channel(
     app.input_clear();
 }
 
-fn handle_delete_channel(app: &mut App) {
+fn handle_delete_channel(app: &mut App, policy: db::DeletePolicy) {
     let filtered = app.filtered_subscriptions();
     if let Some(sub) = filtered.get(app.channel_selected) {
         let id = sub.id.clone();
-        let name = sub.name.clone();
-        match app.db.remove_subscription(&id) {
-            Ok(()) => {
+        let name = sub.label().to_string();
+        match app.db.remove_subscription_with(&id, policy) {
+            Ok(count) => {
+                match policy {
+                    db::DeletePolicy::Keep => app.set_message(&format!("Removed: {}", name)),
+                    db::DeletePolicy::Archive => app.set_message(&format!("Archived: {}", name)),
+                    db::DeletePolicy::Purge => {
+                        app.set_message(&format!("Removed: {} ({} videos deleted)", name, count))
+                    }
+                }
                 app.load_subscriptions();
-                app.set_message(&format!("Removed: {}", name));
+                app.refresh_watched();
                 let filtered_len = app.filtered_subscriptions().len();
                 if app.channel_selected >= filtered_len && filtered_len > 0 {
                     app.channel_selected = filtered_len - 1;
@@ -826,12 +1458,15 @@ async fn handle_prime_channel(
         let ch_id = channel.id.clone();
         let ch_name = channel.name.clone();
         let ch_url = channel.url.clone();
+        // A restored subscription keeps its members-only setting
+        let members = app.subscriptions.iter().any(|s| s.id == channel.id && s.members);
 
         let handle = tokio::spawn(async move {
             ytdlp::prime_channel(
                 &ch_id,
                 &ch_name,
                 &ch_url,
+                members,
                 &existing_ids,
                 move |p| {
                     let _ = tx.send(p);
@@ -859,7 +1494,7 @@ async fn handle_prime_channel(
         match handle.await {
             Ok(Ok(result)) => {
                 if !result.videos.is_empty() {
-                    app.db.store_videos(&result.videos);
+                    app.db.store_source_videos(&channel.id, &result.videos);
                 }
                 let skipped_info = if result.skipped > 0 {
                     format!(" ({} already cached)", result.skipped)
@@ -904,33 +1539,32 @@ async fn handle_prime_all(
     terminal.draw(|f| ui::draw(f, app)).ok();
 
     // Gather existing IDs per channel up front (only skip videos that already have duration)
-    let channel_data: Vec<(String, String, String, HashSet<String>)> = subs
+    let channel_data: Vec<(String, String, String, bool, HashSet<String>)> = subs
         .iter()
         .map(|ch| {
             let existing = app.db.get_stored_videos(&ch.id);
             let existing_ids: HashSet<String> = existing.iter().filter(|v| v.duration.is_some()).map(|v| v.id.clone()).collect();
-            (ch.id.clone(), ch.name.clone(), ch.url.clone(), existing_ids)
+            (ch.id.clone(), ch.name.clone(), ch.url.clone(), ch.members, existing_ids)
         })
         .collect();
 
     // Channel for completed results
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Result<ytdlp::PrimeResult, String>>();
-    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(5));
+    let (tx, mut rx) =
+        tokio::sync::mpsc::unbounded_channel::<(String, Result<ytdlp::PrimeResult, String>)>();
 
-    for (ch_id, ch_name, ch_url, existing_ids) in channel_data {
+    for (ch_id, ch_name, ch_url, members, existing_ids) in channel_data {
         let tx = tx.clone();
-        let sem = semaphore.clone();
         tokio::spawn(async move {
-            let _permit = sem.acquire().await.unwrap();
             let result = ytdlp::prime_channel(
                 &ch_id,
                 &ch_name,
                 &ch_url,
+                members,
                 &existing_ids,
                 |_| {},
             )
             .await;
-            let _ = tx.send(result);
+            let _ = tx.send((ch_id, result));
         });
     }
     drop(tx);
@@ -939,22 +1573,26 @@ async fn handle_prime_all(
     let mut total_added = 0usize;
     let mut total_skipped = 0usize;
     let mut failures = 0usize;
+    let mut members_failures = 0usize;
 
     loop {
         // Drain all available results
         let mut got_any = true;
         while got_any {
             match rx.try_recv() {
-                Ok(Ok(result)) => {
+                Ok((ch_id, Ok(result))) => {
                     completed += 1;
                     if !result.videos.is_empty() {
-                        app.db.store_videos(&result.videos);
+                        app.db.store_source_videos(&ch_id, &result.videos);
                     }
                     total_added += result.added;
                     total_skipped += result.skipped;
+                    if result.members_error.is_some() {
+                        members_failures += 1;
+                    }
                     app.loading_message = format!("Priming {}/{}", completed, total_channels);
                 }
-                Ok(Err(_)) => {
+                Ok((_, Err(_))) => {
                     completed += 1;
                     failures += 1;
                     app.loading_message = format!("Priming {}/{}", completed, total_channels);
@@ -977,37 +1615,45 @@ async fn handle_prime_all(
     app.loading_message.clear();
     app.refresh_counts();
 
-    let fail_info = if failures > 0 {
+    let mut fail_info = if failures > 0 {
         format!(", {} failed", failures)
     } else {
         String::new()
     };
+    if members_failures > 0 {
+        fail_info.push_str(&format!(", members-only failed for {}", members_failures));
+    }
     app.set_message(&format!(
         "Primed all: {} videos added ({} cached{})",
         total_added, total_skipped, fail_info
     ));
 }
 
+/// Check the channels that are due, or every channel when `force` is set.
 async fn handle_refresh(
     app: &mut App,
+    force: bool,
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
 ) {
+    let due = schedule::due_subscriptions(&app.db, &app.subscriptions, force);
+    if due.is_empty() {
+        app.set_message("All channels checked recently (R to force)");
+        return;
+    }
     app.loading = true;
     app.loading_message = "Checking for new videos...".to_string();
     terminal.draw(|f| ui::draw(f, app)).ok();
 
-    let sub_pairs: Vec<(String, String)> = app
-        .subscriptions
-        .iter()
-        .map(|s| (s.id.clone(), s.name.clone()))
-        .collect();
-
-    let fresh = ytdlp::refresh_all_videos(&sub_pairs).await;
-    app.db.store_videos(&fresh);
+    let results = ytdlp::fetch_all_channels_feeds(&due).await;
+    schedule::record(&app.db, results);
     app.refresh_counts();
     app.loading = false;
     app.loading_message.clear();
-    app.set_message("Refreshed");
+    app.set_message(&format!(
+        "Refreshed {} of {} channels",
+        due.len(),
+        app.subscriptions.len()
+    ));
 }
 
 async fn handle_global_search(
@@ -1034,7 +1680,7 @@ async fn handle_global_search(
     app.loading_message.clear();
 }
 
-async fn handle_play_video(app: &mut App) {
+async fn handle_play_video(app: &mut App, start: Option<u64>) {
     let filtered = app.filtered_videos();
     if filtered.is_empty() {
         return;
@@ -1052,8 +1698,16 @@ async fn handle_play_video(app: &mut App) {
         app.playing = true;
         app.set_message(&format!("Opening: {}", title));
 
+        let segments = sponsor_segments_for(app, &id).await;
+        let request = player::PlayRequest {
+            url: &url,
+            video_id: Some(&id),
+            title: &title,
+            start,
+            segments: &segments,
+        };
         let (result, _video_id) =
-            player::play_video(&url, Some(&id), &app.settings.player, &app.max_resolution).await;
+            player::play_video(&request, &app.settings, &app.format_prefs()).await;
 
         if result.success {
             app.set_message(&format!("Playing in {}", result.player));
@@ -1065,7 +1719,56 @@ async fn handle_play_video(app: &mut App) {
     }
 }
 
-async fn handle_play_search_result(app: &mut App) {
+/// Queue the highlighted playlist, or the selected video and everything after
+/// it, in one player window. Stream players only get the first few, see
+/// `player::STREAM_QUEUE_LIMIT`.
+async fn handle_queue(app: &mut App) {
+    let (urls, label) = if app.showing_playlists() {
+        match app.filtered_playlists().get(app.video_selected) {
+            Some(p) => (vec![p.url.clone()], p.title.clone()),
+            None => return,
+        }
+    } else {
+        let urls: Vec<String> = app
+            .filtered_videos()
+            .iter()
+            .skip(app.video_selected)
+            .map(|v| v.url.clone())
+            .collect();
+        if urls.is_empty() {
+            return;
+        }
+        let label = format!("{} video{}", urls.len(), if urls.len() == 1 { "" } else { "s" });
+        (urls, label)
+    };
+
+    app.playing = true;
+    app.set_message(&format!("Queueing: {}", label));
+    let queue = player::play_queue(&urls, &app.settings, &app.format_prefs()).await;
+    let result = queue.result;
+    if result.success {
+        let mut message = if app.showing_playlists() || queue.queued == urls.len() {
+            format!("Queued {} in {}", label, result.player)
+        } else {
+            format!("Queued {} of {} in {}", queue.queued, label, result.player)
+        };
+        if let Some((url, err)) = queue.skipped.first() {
+            message.push_str(&format!(
+                "; skipped {} ({}: {})",
+                queue.skipped.len(),
+                url,
+                err
+            ));
+        }
+        app.set_message(&message);
+    } else if let Some(err) = result.error {
+        app.set_error(&format!("Failed to queue: {}", err));
+    }
+    app.playing = false;
+}
+
+
+async fn handle_play_search_result(app: &mut App, start: Option<u64>) {
     let filtered = app.filtered_videos();
     if filtered.is_empty() {
         return;
@@ -1083,8 +1786,16 @@ async fn handle_play_search_result(app: &mut App) {
         app.playing = true;
         app.set_message(&format!("Opening: {}", title));
 
+        let segments = sponsor_segments_for(app, &id).await;
+        let request = player::PlayRequest {
+            url: &url,
+            video_id: Some(&id),
+            title: &title,
+            start,
+            segments: &segments,
+        };
         let (result, _video_id) =
-            player::play_video(&url, Some(&id), &app.settings.player, &app.max_resolution).await;
+            player::play_video(&request, &app.settings, &app.format_prefs()).await;
 
         if result.success {
             app.set_message(&format!("Playing in {}", result.player));
@@ -1109,24 +1820,7 @@ async fn handle_fetch_description(
     let video_title = video.title.clone();
     let ch_name = video.channel_name.clone().unwrap_or_default();
 
-    app.show_description = true;
-    app.loading_description = true;
-    app.description = None;
-    terminal.draw(|f| ui::draw(f, app)).ok();
-
-    match ytdlp::get_video_description(&video_id).await {
-        Ok(desc) => {
-            app.description = Some(desc);
-        }
-        Err(e) => {
-            app.description = Some(ytdlp::VideoDescription {
-                title: video_title,
-                description: format!("Error: {}", e),
-                channel_name: ch_name,
-            });
-        }
-    }
-    app.loading_description = false;
+    load_description(app, terminal, &video_id, video_title, ch_name).await;
 }
 
 async fn handle_fetch_description_search(
@@ -1142,26 +1836,217 @@ async fn handle_fetch_description_search(
     let video_title = video.title.clone();
     let ch_name = video.channel_name.clone().unwrap_or_default();
 
+    load_description(app, terminal, &video_id, video_title, ch_name).await;
+}
+
+/// Follow a link from the info panel. YouTube videos play and channels prompt for a
+/// subscription inside the app; anything else goes to the system browser.
+async fn handle_open_link(app: &mut App, url: &str) {
+    match ytdlp::classify_youtube_link(url) {
+        Some(ytdlp::YoutubeLink::Video { id, start }) => {
+            let video_url = format!("https://www.youtube.com/watch?v={}", id);
+            app.playing = true;
+            app.set_message(&format!("Opening: {}", video_url));
+
+            let segments = sponsor_segments_for(app, &id).await;
+            let request = player::PlayRequest {
+                url: &video_url,
+                video_id: Some(&id),
+                title: &video_url,
+                start,
+                segments: &segments,
+            };
+            let (result, _video_id) =
+                player::play_video(&request, &app.settings, &app.format_prefs()).await;
+
+            if result.success {
+                app.set_message(&format!("Playing in {}", result.player));
+            } else if let Some(err) = result.error {
+                app.set_error(&format!("Failed to play: {}", err));
+            }
+            app.playing = false;
+        }
+        Some(ytdlp::YoutubeLink::Channel(channel_url)) => {
+            app.pending_link = Some(channel_url);
+            app.mode = Mode::ConfirmSubscribeLink;
+        }
+        None => match open::that(url) {
+            Ok(()) => app.set_message("Opened in browser"),
+            Err(e) => app.set_error(&format!("Failed to open link: {}", e)),
+        },
+    }
+}
+
+/// Show the info panel for a video, from the local cache when it has been opened
+/// before and otherwise via yt-dlp. Errors are shown in the panel but not cached.
+async fn load_description(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    video_id: &str,
+    video_title: String,
+    ch_name: String,
+) {
+    app.close_overlays();
     app.show_description = true;
+    app.info_selected = 0;
+
+    if let Some(cached) = app.db.get_video_details(video_id) {
+        app.description = Some(cached);
+        return;
+    }
+
     app.loading_description = true;
     app.description = None;
     terminal.draw(|f| ui::draw(f, app)).ok();
 
-    match ytdlp::get_video_description(&video_id).await {
+    match ytdlp::get_video_description(video_id).await {
         Ok(desc) => {
+            app.db.store_video_details(video_id, &desc);
             app.description = Some(desc);
         }
         Err(e) => {
-            app.description = Some(ytdlp::VideoDescription {
+            app.description = Some(db::VideoDescription {
                 title: video_title,
                 description: format!("Error: {}", e),
                 channel_name: ch_name,
+                upload_date: None,
+                duration: None,
+                view_count: None,
+                like_count: None,
+                tags: Vec::new(),
+                categories: Vec::new(),
+                chapters: Vec::new(),
+                links: Vec::new(),
             });
         }
     }
     app.loading_description = false;
 }
 
+/// Show the comments overlay for the video at `index`, from the local cache when
+/// they were fetched within the last day.
+async fn handle_fetch_comments(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    index: usize,
+) {
+    let video_id = match app.filtered_videos().get(index) {
+        Some(video) => video.id.clone(),
+        None => return,
+    };
+
+    app.close_overlays();
+    app.show_comments = true;
+
+    if let Some(cached) = app.db.get_comments(&video_id) {
+        app.comments = Some(cached);
+        return;
+    }
+
+    app.loading_comments = true;
+    app.comments = None;
+    terminal.draw(|f| ui::draw(f, app)).ok();
+
+    match ytdlp::get_video_comments(&video_id, app.settings.comment_limit).await {
+        Ok(comments) => {
+            app.db.store_comments(&video_id, &comments);
+            app.comments = Some(comments);
+        }
+        Err(e) => {
+            app.show_comments = false;
+            app.set_error(&format!("Failed to load comments: {}", e));
+        }
+    }
+    app.loading_comments = false;
+}
+
+/// Show the transcript panel for the video at `index`. Fetched transcripts are
+/// stored so they open instantly next time and can be matched by the filter.
+async fn handle_fetch_transcript(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    index: usize,
+) {
+    let video_id = match app.filtered_videos().get(index) {
+        Some(video) => video.id.clone(),
+        None => return,
+    };
+
+    app.close_overlays();
+    app.show_transcript = true;
+
+    if let Some(cached) = app.db.get_transcript(&video_id) {
+        app.transcript = Some(cached);
+        return;
+    }
+
+    app.loading_transcript = true;
+    terminal.draw(|f| ui::draw(f, app)).ok();
+
+    match transcript::fetch_transcript(&video_id, &app.settings.subtitle_language).await {
+        Ok(fetched) => {
+            app.db.store_transcript(&video_id, &fetched);
+            app.transcript = Some(fetched);
+        }
+        Err(e) => {
+            app.show_transcript = false;
+            app.set_error(&format!("Failed to load transcript: {}", e));
+        }
+    }
+    app.loading_transcript = false;
+}
+
+/// Segments to skip for a video, from the cache or SponsorBlock. Failures just mean
+/// nothing gets skipped, so they never block playback.
+async fn sponsor_segments_for(app: &mut App, video_id: &str) -> Vec<db::SponsorSegment> {
+    if !app.settings.sponsorblock {
+        return Vec::new();
+    }
+    if let Some(cached) = app.db.get_sponsor_segments(video_id) {
+        return cached;
+    }
+    let client = sponsorblock::SponsorBlockClient::new(&app.settings.sponsorblock_api);
+    match client.fetch_segments(video_id).await {
+        Ok(segments) => {
+            app.db.store_sponsor_segments(video_id, &segments);
+            app.refresh_sponsor_totals();
+            segments
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Look up segments for the newest unwatched, unchecked videos in the list so the
+/// table can show sponsor time before anything is played.
+fn start_sponsor_prefetch(app: &mut App) {
+    if !app.settings.sponsorblock || app.sponsor_prefetch.is_some() {
+        return;
+    }
+    let ids: Vec<String> = app
+        .videos
+        .iter()
+        .filter(|v| !v.is_short && !app.watched_ids.contains(&v.id))
+        .filter(|v| app.db.get_sponsor_segments(&v.id).is_none())
+        .take(20)
+        .map(|v| v.id.clone())
+        .collect();
+    if ids.is_empty() {
+        return;
+    }
+
+    let api_base = app.settings.sponsorblock_api.clone();
+    app.sponsor_prefetch = Some(tokio::spawn(async move {
+        let client = sponsorblock::SponsorBlockClient::new(&api_base);
+        let mut fetched = Vec::new();
+        for id in ids {
+            if let Ok(segments) = client.fetch_segments(&id).await {
+                fetched.push((id, segments));
+            }
+        }
+        fetched
+    }));
+}
+
 fn handle_subscribe_from_search(app: &mut App) {
     let filtered = app.filtered_videos();
     if let Some(video) = filtered.get(app.search_selected) {
@@ -1180,6 +2065,11 @@ fn handle_subscribe_from_search(app: &mut App) {
                         .to_string(),
                     url: format!("https://www.youtube.com/channel/{}", channel_id),
                     added_at: None,
+                    kind: db::SourceKind::Channel,
+                    notify: false,
+                    display_name: None,
+                    archived: false,
+                    members: false,
                 };
                 match app.db.add_subscription(&sub) {
                     Ok(()) => {
@@ -1201,9 +2091,13 @@ async fn load_videos_for_screen(app: &mut App) {
     app.loading_message = "Refreshing...".to_string();
 
     if let Some(ref channel) = app.current_channel.clone() {
-        // Single channel view
-        let fresh = ytdlp::get_channel_videos(&channel.id, &channel.name).await;
-        app.db.store_videos(&fresh);
+        // Single channel view; archived channels only show what is stored
+        let fresh = if channel.archived {
+            Vec::new()
+        } else {
+            ytdlp::get_channel_videos(channel).await
+        };
+        app.db.store_source_videos(&channel.id, &fresh);
 
         let stored = app.db.get_stored_videos(&channel.id);
         // Merge: stored + fresh, deduplicated (prefer stored to preserve DB metadata)
@@ -1223,18 +2117,17 @@ async fn load_videos_for_screen(app: &mut App) {
         app.videos = videos;
         app.total_videos = app.videos.len();
     } else {
-        // All videos view
+        // All videos view, which keeps listing archived subscriptions
         let subs = app.db.get_subscriptions();
         if subs.is_empty() {
             app.videos.clear();
             app.total_videos = 0;
         } else {
-            let sub_pairs: Vec<(String, String)> =
-                subs.iter().map(|s| (s.id.clone(), s.name.clone())).collect();
-            let fresh = ytdlp::refresh_all_videos(&sub_pairs).await;
-            app.db.store_videos(&fresh);
+            let due = schedule::due_subscriptions(&app.db, &subs, false);
+            let results = ytdlp::fetch_all_channels_feeds(&due).await;
+            schedule::record(&app.db, results);
 
-            app.all_channel_ids = subs.iter().map(|s| s.id.clone()).collect();
+            app.all_channel_ids = app.db.get_all_subscriptions().into_iter().map(|s| s.id).collect();
             let result = app.db.get_stored_videos_paginated(
                 Some(&app.all_channel_ids),
                 0,
@@ -1252,6 +2145,63 @@ async fn load_videos_for_screen(app: &mut App) {
     app.video_scroll = 0;
     app.loading = false;
     app.loading_message.clear();
+    start_sponsor_prefetch(app);
+}
+
+/// Load the current channel's selected tab, or the playlist opened from it.
+/// Tab listings are browsed live from yt-dlp and not stored.
+async fn load_channel_tab(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+) {
+    let Some(channel) = app.current_channel.clone() else {
+        return;
+    };
+    let tab = app.channel_tab;
+    if tab == ChannelTab::Videos {
+        load_videos_for_screen(app).await;
+        return;
+    }
+
+    app.loading = true;
+    app.loading_message = match app.open_playlist {
+        Some(ref playlist) => format!("Loading {}...", playlist.title),
+        None => format!("Loading {}...", tab.label()),
+    };
+    terminal.draw(|f| ui::draw(f, app)).ok();
+
+    let result = if let Some(playlist) = app.open_playlist.clone() {
+        ytdlp::get_playlist_videos(&playlist, &channel)
+            .await
+            .map(|videos| app.videos = videos)
+    } else if tab.lists_playlists() {
+        ytdlp::get_channel_playlists(&channel, tab)
+            .await
+            .map(|playlists| app.channel_playlists = playlists)
+    } else {
+        ytdlp::get_channel_tab_videos(&channel, tab)
+            .await
+            .map(|videos| app.videos = videos)
+    };
+    if let Err(e) = result {
+        app.set_error(&e);
+    } else if tab == ChannelTab::Shorts && app.open_playlist.is_none() {
+        // The Shorts tab is authoritative for any of these we already store
+        let flags: Vec<(String, Option<bool>)> =
+            app.videos.iter().map(|v| (v.id.clone(), Some(true))).collect();
+        app.db.set_short_flags(&flags);
+        app.refresh_counts();
+    }
+
+    app.total_videos = app.videos.len();
+    if app.video_selected >= app.video_list_len() {
+        app.video_selected = 0;
+        app.video_scroll = 0;
+    }
+    app.refresh_watched();
+    app.loading = false;
+    app.loading_message.clear();
+    start_sponsor_prefetch(app);
 }
 
 // ── Main ───────────────────────────────────────────────────
@@ -1260,6 +2210,15 @@ async fn load_videos_for_screen(app: &mut App) {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
+    // One set of yt-dlp limits for whichever mode runs
+    if let Ok(db) = Database::open() {
+        runner::configure(runner::RunnerConfig::from_settings(&db.get_settings()));
+    }
+
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(command, cli.json).await);
+    }
+
     if let Some(ref url) = cli.add {
         handle_add(url).await;
         return Ok(());
@@ -1275,6 +2234,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if cli.gc {
+        handle_gc(cli.dry_run, cli.json);
+        return Ok(());
+    }
+
+    if cli.doctor {
+        handle_doctor(cli.json).await;
+        return Ok(());
+    }
+
+    if cli.refresh {
+        handle_sync(cli.json, cli.enrich).await;
+        return Ok(());
+    }
+
+    if let Some(minutes) = cli.watch {
+        handle_watch(minutes).await;
+        return Ok(());
+    }
+
     let initial_channel = if let Some(index) = cli.channel {
         let db = Database::open()?;
         let subs = db.get_subscriptions();
@@ -0,0 +1,280 @@
+use std::collections::{HashMap, HashSet};
+
+use zbus::zvariant::Value;
+use zbus::Connection;
+
+use crate::db::{Subscription, Video};
+
+/// Titles listed in one notification before the rest are summarised.
+const MAX_TITLES: usize = 5;
+
+#[zbus::proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+trait Notifications {
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+}
+
+/// Sends freedesktop notifications over D-Bus.
+pub struct Notifier {
+    conn: Connection,
+}
+
+impl Notifier {
+    /// Connect to the user's session bus.
+    pub async fn connect() -> Result<Self, String> {
+        let conn = Connection::session()
+            .await
+            .map_err(|e| format!("Failed to connect to session bus: {e}"))?;
+        Ok(Notifier { conn })
+    }
+
+    /// Connect to a bus at an explicit address, e.g. a private test bus.
+    pub async fn connect_to(address: &str) -> Result<Self, String> {
+        let conn = zbus::connection::Builder::address(address)
+            .map_err(|e| format!("Invalid bus address: {e}"))?
+            .build()
+            .await
+            .map_err(|e| format!("Failed to connect to {address}: {e}"))?;
+        Ok(Notifier { conn })
+    }
+
+    pub async fn send(&self, summary: &str, body: &str) -> Result<(), String> {
+        let proxy = NotificationsProxy::new(&self.conn)
+            .await
+            .map_err(|e| format!("Notification service unavailable: {e}"))?;
+        proxy
+            .notify("feeding-tube", 0, "", summary, body, &[], HashMap::new(), -1)
+            .await
+            .map_err(|e| format!("Failed to send notification: {e}"))?;
+        Ok(())
+    }
+}
+
+/// New uploads per subscription flagged for notifications, from each
+/// subscription's fetched feed. Subscriptions with nothing stored yet are
+/// skipped so a fresh one doesn't announce its whole feed.
+pub fn pending_notifications<'a>(
+    subs: &'a [Subscription],
+    feeds: &'a [(String, Vec<Video>)],
+    known: &HashSet<String>,
+    seeded: &HashSet<String>,
+    hide_shorts: bool,
+) -> Vec<(&'a Subscription, Vec<&'a Video>)> {
+    subs.iter()
+        .filter(|s| s.notify && seeded.contains(&s.id))
+        .filter_map(|s| {
+            let (_, feed) = feeds.iter().find(|(id, _)| *id == s.id)?;
+            let videos: Vec<&Video> = feed
+                .iter()
+                .filter(|v| !known.contains(&v.id))
+                .filter(|v| !(hide_shorts && v.is_short))
+                .collect();
+            (!videos.is_empty()).then_some((s, videos))
+        })
+        .collect()
+}
+
+/// Summary and body for one channel's new uploads.
+pub fn new_videos_message(channel_name: &str, videos: &[&Video]) -> (String, String) {
+    let summary = match videos.len() {
+        1 => format!("New from {}", channel_name),
+        n => format!("{} new from {}", n, channel_name),
+    };
+    let mut lines: Vec<String> = videos
+        .iter()
+        .take(MAX_TITLES)
+        .map(|v| v.title.clone())
+        .collect();
+    if videos.len() > MAX_TITLES {
+        lines.push(format!("and {} more", videos.len() - MAX_TITLES));
+    }
+    (summary, lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+    use std::sync::{Arc, Mutex};
+
+    fn video(title: &str) -> Video {
+        Video {
+            id: title.to_string(),
+            title: title.to_string(),
+            url: String::new(),
+            is_short: false,
+            channel_name: Some("Chan".to_string()),
+            channel_id: Some("UC1".to_string()),
+            published_date: None,
+            stored_at: None,
+            relative_date: String::new(),
+            duration: None,
+            duration_string: None,
+            view_count: None,
+            live_status: None,
+            thumbnail: None,
+        }
+    }
+
+    fn sub(id: &str, notify: bool) -> Subscription {
+        Subscription {
+            id: id.to_string(),
+            name: id.to_string(),
+            url: String::new(),
+            added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify,
+            display_name: None,
+            archived: false,
+            members: false,
+        }
+    }
+
+    fn video_in(id: &str, channel_id: &str, is_short: bool) -> Video {
+        Video {
+            id: id.to_string(),
+            channel_id: Some(channel_id.to_string()),
+            is_short,
+            ..video(id)
+        }
+    }
+
+    // ── pending_notifications tests ──────────────────────────
+
+    #[test]
+    fn test_pending_notifications() {
+        let subs = vec![sub("UC1", true), sub("UC2", false), sub("UC3", true), sub("PL1", true)];
+        let feeds = vec![
+            (
+                "UC1".to_string(),
+                vec![
+                    video_in("old", "UC1", false),
+                    video_in("new", "UC1", false),
+                    video_in("short", "UC1", true),
+                ],
+            ),
+            ("UC2".to_string(), vec![video_in("quiet", "UC2", false)]),
+            ("UC3".to_string(), vec![video_in("first", "UC3", false)]),
+            // Playlist entries keep their uploader's channel
+            ("PL1".to_string(), vec![video_in("guest", "UC9", false)]),
+        ];
+        let known: HashSet<String> = ["old".to_string()].into_iter().collect();
+        let seeded: HashSet<String> =
+            ["UC1".to_string(), "UC2".to_string(), "PL1".to_string()].into_iter().collect();
+
+        let due = pending_notifications(&subs, &feeds, &known, &seeded, true);
+        assert_eq!(due.len(), 2);
+        assert_eq!(due[0].0.id, "UC1");
+        let ids: Vec<&str> = due[0].1.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["new"]);
+        assert_eq!(due[1].0.id, "PL1");
+        assert_eq!(due[1].1[0].id, "guest");
+
+        let due = pending_notifications(&subs, &feeds, &known, &seeded, false);
+        assert_eq!(due[0].1.len(), 2);
+    }
+
+    // ── new_videos_message tests ─────────────────────────────
+
+    #[test]
+    fn test_new_videos_message_single() {
+        let v = video("Hello");
+        let (summary, body) = new_videos_message("Chan", &[&v]);
+        assert_eq!(summary, "New from Chan");
+        assert_eq!(body, "Hello");
+    }
+
+    #[test]
+    fn test_new_videos_message_truncates() {
+        let videos: Vec<Video> = (1..=7).map(|i| video(&format!("V{}", i))).collect();
+        let refs: Vec<&Video> = videos.iter().collect();
+        let (summary, body) = new_videos_message("Chan", &refs);
+        assert_eq!(summary, "7 new from Chan");
+        assert_eq!(body, "V1\nV2\nV3\nV4\nV5\nand 2 more");
+    }
+
+    // ── Notifier tests ───────────────────────────────────────
+
+    struct FakeServer {
+        received: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.Notifications")]
+    impl FakeServer {
+        #[allow(clippy::too_many_arguments)]
+        fn notify(
+            &self,
+            _app_name: &str,
+            _replaces_id: u32,
+            _app_icon: &str,
+            summary: &str,
+            body: &str,
+            _actions: Vec<String>,
+            _hints: HashMap<String, zbus::zvariant::OwnedValue>,
+            _expire_timeout: i32,
+        ) -> u32 {
+            self.received
+                .lock()
+                .unwrap()
+                .push((summary.to_string(), body.to_string()));
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notifier_on_local_session_bus() {
+        // Needs dbus-daemon; skipped where it isn't installed
+        let Ok(mut daemon) = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address=1"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return;
+        };
+        let mut address = String::new();
+        BufReader::new(daemon.stdout.take().unwrap())
+            .read_line(&mut address)
+            .unwrap();
+        let address = address.trim().to_string();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let _server = zbus::connection::Builder::address(address.as_str())
+            .unwrap()
+            .name("org.freedesktop.Notifications")
+            .unwrap()
+            .serve_at(
+                "/org/freedesktop/Notifications",
+                FakeServer { received: received.clone() },
+            )
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let notifier = Notifier::connect_to(&address).await.unwrap();
+        let result = notifier.send("New from Chan", "Hello").await;
+        daemon.kill().ok();
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![("New from Chan".to_string(), "Hello".to_string())]
+        );
+    }
+}
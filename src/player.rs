@@ -1,6 +1,11 @@
 use std::process::Stdio;
 use tokio::process::Command;
 
+use crate::db::{Cookies, Settings, SponsorSegment};
+use crate::format::FormatPrefs;
+use crate::runner::{self, Runner};
+use crate::{sponsorblock, ytdlp};
+
 const SUPPORTED_PLAYERS: &[&str] = &["mpv", "iina", "vlc"];
 
 fn player_args(player: &str) -> &[&str] {
@@ -12,6 +17,91 @@ fn player_args(player: &str) -> &[&str] {
     }
 }
 
+// ── Playback Modes ─────────────────────────────────────────
+
+/// How a video reaches the player: as a page URL the player resolves itself via
+/// its yt-dlp hook, or as direct stream URLs resolved by us.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackMode {
+    Auto,
+    Ytdl,
+    Stream,
+}
+
+impl PlaybackMode {
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "ytdl" => PlaybackMode::Ytdl,
+            "stream" => PlaybackMode::Stream,
+            _ => PlaybackMode::Auto,
+        }
+    }
+}
+
+fn has_ytdl_hook(player: &str) -> bool {
+    matches!(player, "mpv" | "iina")
+}
+
+fn uses_streams(player: &str, mode: PlaybackMode) -> bool {
+    match mode {
+        PlaybackMode::Auto => !has_ytdl_hook(player),
+        PlaybackMode::Ytdl => false,
+        PlaybackMode::Stream => true,
+    }
+}
+
+fn takes_separate_audio(player: &str) -> bool {
+    matches!(player, "mpv" | "iina" | "vlc")
+}
+
+/// Player arguments for resolved stream URLs: the video URL, plus the player's
+/// way of attaching a separate audio track when yt-dlp returned one.
+fn stream_args(player: &str, urls: &[String]) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(video) = urls.first() {
+        args.push(video.clone());
+    }
+    if let Some(audio) = urls.get(1) {
+        match player {
+            "vlc" => args.push(format!("--input-slave={}", audio)),
+            "mpv" => args.push(format!("--audio-file={}", audio)),
+            "iina" => args.push(format!("--mpv-audio-file={}", audio)),
+            _ => {}
+        }
+    }
+    args
+}
+
+fn format_arg(player: &str, prefs: &FormatPrefs) -> Option<String> {
+    let format = prefs.format_string()?;
+    match player {
+        "mpv" => Some(format!("--ytdl-format={}", format)),
+        "iina" => Some(format!("--mpv-ytdl-format={}", format)),
+        _ => None,
+    }
+}
+
+/// Cookies for the player's own yt-dlp run. `-append` takes a single
+/// option, so a path with commas stays intact. Other players get streams
+/// already resolved with the cookies, and a custom command given `{url}`
+/// has to bring its own.
+fn cookies_arg(player: &str, cookies: &Cookies) -> Option<String> {
+    match player {
+        "mpv" => Some(format!("--ytdl-raw-options-append={}", cookies.ytdl_raw_option())),
+        "iina" => Some(format!("--mpv-ytdl-raw-options-append={}", cookies.ytdl_raw_option())),
+        _ => None,
+    }
+}
+
+fn start_arg(player: &str, seconds: u64) -> Option<String> {
+    match player {
+        "mpv" => Some(format!("--start={}", seconds)),
+        "iina" => Some(format!("--mpv-start={}", seconds)),
+        "vlc" => Some(format!("--start-time={}", seconds)),
+        _ => None,
+    }
+}
+
 fn extract_video_id(url: &str) -> Option<String> {
     let patterns = [
         regex::Regex::new(r"(?:youtube\.com/watch\?v=|youtu\.be/)([a-zA-Z0-9_\-]{11})").unwrap(),
@@ -61,55 +151,408 @@ pub struct PlayResult {
     pub error: Option<String>,
 }
 
+pub struct QueueResult {
+    pub result: PlayResult,
+    /// Entries handed to the player.
+    pub queued: usize,
+    /// Entries left out because they couldn't be resolved, with the reason.
+    pub skipped: Vec<(String, String)>,
+}
+
+pub struct PlayRequest<'a> {
+    pub url: &'a str,
+    pub video_id: Option<&'a str>,
+    pub title: &'a str,
+    pub start: Option<u64>,
+    pub segments: &'a [SponsorSegment],
+}
+
+// ── Command Templates ──────────────────────────────────────
+
+/// Split a player command template into words, honouring single/double quotes
+/// and backslash escapes the way a shell would.
+fn split_command(template: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unterminated quote in player command".to_string());
+    }
+    if in_word {
+        words.push(current);
+    }
+    if words.is_empty() {
+        return Err("Player command is empty".to_string());
+    }
+    Ok(words)
+}
+
+struct TemplateVars<'a> {
+    url: &'a str,
+    stream_url: &'a str,
+    title: &'a str,
+    start: u64,
+    resolution: &'a str,
+}
+
+impl TemplateVars<'_> {
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "url" => Some(self.url.to_string()),
+            "stream_url" => Some(self.stream_url.to_string()),
+            "title" => Some(self.title.to_string()),
+            "start" => Some(self.start.to_string()),
+            "resolution" => Some(self.resolution.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Fill in the placeholders in one pass, so a value that happens to contain
+/// a placeholder (a title like "{url}") is left as it is.
+fn expand_template(words: &[String], vars: &TemplateVars) -> Vec<String> {
+    words
+        .iter()
+        .map(|word| {
+            let mut out = String::new();
+            let mut rest = word.as_str();
+            while let Some(open) = rest.find('{') {
+                out.push_str(&rest[..open]);
+                let after = &rest[open..];
+                let value = after
+                    .find('}')
+                    .and_then(|close| Some((vars.get(&after[1..close])?, close)));
+                match value {
+                    Some((value, close)) => {
+                        out.push_str(&value);
+                        rest = &after[close + 1..];
+                    }
+                    None => {
+                        out.push('{');
+                        rest = &after[1..];
+                    }
+                }
+            }
+            out.push_str(rest);
+            out
+        })
+        .collect()
+}
+
+async fn play_with_template(
+    template: &str,
+    req: &PlayRequest<'_>,
+    format: &FormatPrefs,
+) -> PlayResult {
+    let words = match split_command(template) {
+        Ok(w) => w,
+        Err(e) => {
+            return PlayResult {
+                success: false,
+                player: String::new(),
+                error: Some(e),
+            }
+        }
+    };
+
+    // Only pay for a yt-dlp round trip when the template actually wants a
+    // stream URL. The runner resolves it with the configured cookies.
+    let stream_url = if template.contains("{stream_url}") {
+        match ytdlp::get_stream_url(req.url, &format.combined_format_string()).await {
+            Ok(urls) if !urls.is_empty() => urls[0].clone(),
+            Ok(_) => {
+                return PlayResult {
+                    success: false,
+                    player: String::new(),
+                    error: Some("No stream URL returned".to_string()),
+                }
+            }
+            Err(e) => {
+                return PlayResult {
+                    success: false,
+                    player: String::new(),
+                    error: Some(e),
+                }
+            }
+        }
+    } else {
+        String::new()
+    };
+
+    let resolution = format
+        .max_height
+        .map(|h| h.to_string())
+        .unwrap_or_else(|| "max".to_string());
+    let argv = expand_template(
+        &words,
+        &TemplateVars {
+            url: req.url,
+            stream_url: &stream_url,
+            title: req.title,
+            start: req.start.unwrap_or(0),
+            resolution: &resolution,
+        },
+    );
+
+    let result = std::process::Command::new(&argv[0])
+        .args(&argv[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    match result {
+        Ok(_child) => PlayResult {
+            success: true,
+            player: argv[0].clone(),
+            error: None,
+        },
+        Err(e) => PlayResult {
+            success: false,
+            player: argv[0].clone(),
+            error: Some(format!("{}: {}", argv[0], e)),
+        },
+    }
+}
+
+// ── Playback ───────────────────────────────────────────────
+
+/// The configured player if it is installed, otherwise the first supported one.
+async fn find_player(preferred: &str) -> Result<String, String> {
+    if check_player(preferred).await {
+        return Ok(preferred.to_string());
+    }
+    auto_detect_player()
+        .await
+        .ok_or_else(|| "No video player found. Please install mpv, iina, or vlc.".to_string())
+}
+
+/// Most entries resolved to streams for one queue. Each is a separate yt-dlp
+/// call and stream URLs expire, so longer queues are cut short.
+pub const STREAM_QUEUE_LIMIT: usize = 5;
+
+/// Resolve the first `STREAM_QUEUE_LIMIT` entries to one combined stream each,
+/// in order. Entries that fail are skipped rather than failing the queue.
+async fn resolve_queue(
+    runner: &'static Runner,
+    urls: &[String],
+    selector: &str,
+) -> (Vec<String>, Vec<(String, String)>) {
+    let handles: Vec<_> = urls
+        .iter()
+        .take(STREAM_QUEUE_LIMIT)
+        .cloned()
+        .map(|url| {
+            let (target, selector) = (url.clone(), selector.to_string());
+            let handle = tokio::spawn(async move {
+                ytdlp::get_stream_url_with(runner, &target, &selector).await
+            });
+            (url, handle)
+        })
+        .collect();
+    let mut streams = Vec::new();
+    let mut skipped = Vec::new();
+    for (url, handle) in handles {
+        match handle.await.unwrap_or_else(|e| Err(e.to_string())) {
+            Ok(urls) => match urls.into_iter().next() {
+                Some(stream) => streams.push(stream),
+                None => skipped.push((url, "No stream URL returned".to_string())),
+            },
+            Err(e) => skipped.push((url, e)),
+        }
+    }
+    (streams, skipped)
+}
+
+/// Open several videos (or a playlist URL) in one player window, queued in
+/// order. Players without a yt-dlp hook get the first `STREAM_QUEUE_LIMIT`
+/// entries resolved to streams, and can't queue a playlist. Not available with
+/// a custom player command.
+pub async fn play_queue(urls: &[String], settings: &Settings, format: &FormatPrefs) -> QueueResult {
+    let failed = |player: String, error: String| QueueResult {
+        result: PlayResult {
+            success: false,
+            player,
+            error: Some(error),
+        },
+        queued: 0,
+        skipped: Vec::new(),
+    };
+    if settings.player_command.is_some() {
+        return failed(
+            String::new(),
+            "Queueing is not supported with a custom player command".to_string(),
+        );
+    }
+    let player = match find_player(&settings.player).await {
+        Ok(p) => p,
+        Err(e) => return failed(String::new(), e),
+    };
+
+    let mut cmd_args: Vec<String> = player_args(&player).iter().map(|s| s.to_string()).collect();
+    let mut skipped = Vec::new();
+    let queued;
+    if uses_streams(&player, PlaybackMode::parse(&settings.playback_mode)) {
+        if urls.iter().any(|u| ytdlp::playlist_id(u).is_some()) {
+            return failed(
+                player,
+                "Playlists can only be queued in mpv or iina with playbackMode ytdl".to_string(),
+            );
+        }
+        // A separate audio track can't be paired with each queued entry, so
+        // every video is resolved to one combined stream.
+        let selector = format.combined_format_string();
+        let (streams, failures) = resolve_queue(runner::global(), urls, &selector).await;
+        if streams.is_empty() {
+            let error = failures
+                .first()
+                .map(|(_, e)| e.clone())
+                .unwrap_or_else(|| "Nothing to queue".to_string());
+            return failed(player, error);
+        }
+        queued = streams.len();
+        skipped = failures;
+        cmd_args.extend(streams);
+    } else {
+        if let Some(arg) = format_arg(&player, format) {
+            cmd_args.push(arg);
+        }
+        if let Some(arg) = settings.cookies().and_then(|c| cookies_arg(&player, &c)) {
+            cmd_args.push(arg);
+        }
+        queued = urls.len();
+        cmd_args.extend(urls.iter().cloned());
+    }
+
+    let result = match std::process::Command::new(&player)
+        .args(&cmd_args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(_child) => PlayResult {
+            success: true,
+            player,
+            error: None,
+        },
+        Err(e) => PlayResult {
+            success: false,
+            player,
+            error: Some(e.to_string()),
+        },
+    };
+    QueueResult {
+        result,
+        queued,
+        skipped,
+    }
+}
+
 pub async fn play_video(
-    video_url: &str,
-    video_id: Option<&str>,
-    configured_player: &str,
-    max_resolution: &str,
+    req: &PlayRequest<'_>,
+    settings: &Settings,
+    format: &FormatPrefs,
 ) -> (PlayResult, Option<String>) {
-    let id = video_id
+    let video_url = req.url;
+    let id = req
+        .video_id
         .map(|s| s.to_string())
         .or_else(|| extract_video_id(video_url));
 
-    let mut player = configured_player.to_string();
+    if let Some(ref template) = settings.player_command {
+        return (play_with_template(template, req, format).await, id);
+    }
 
-    if !check_player(&player).await {
-        if let Some(detected) = auto_detect_player().await {
-            player = detected;
-        } else {
+    let player = match find_player(&settings.player).await {
+        Ok(p) => p,
+        Err(e) => {
             return (
                 PlayResult {
                     success: false,
                     player: String::new(),
-                    error: Some(
-                        "No video player found. Please install mpv, iina, or vlc.".to_string(),
-                    ),
+                    error: Some(e),
                 },
                 id,
-            );
+            )
         }
-    }
+    };
 
-    let args = player_args(&player);
-    let mut cmd_args: Vec<&str> = args.to_vec();
+    let mut cmd_args: Vec<String> = player_args(&player).iter().map(|s| s.to_string()).collect();
+    cmd_args.extend(sponsorblock::player_args(&player, req.segments));
+    if let Some(arg) = req.start.filter(|&s| s > 0).and_then(|s| start_arg(&player, s)) {
+        cmd_args.push(arg);
+    }
 
-    let format_arg: String;
-    if max_resolution == "1080" {
-        match player.as_str() {
-            "mpv" => {
-                format_arg = "--ytdl-format=bestvideo[height<=1080]+bestaudio/best[height<=1080]/best".to_string();
-                cmd_args.push(&format_arg);
+    if uses_streams(&player, PlaybackMode::parse(&settings.playback_mode)) {
+        let selector = if takes_separate_audio(&player) {
+            format.stream_format_string()
+        } else {
+            format.combined_format_string()
+        };
+        match ytdlp::get_stream_url(video_url, &selector).await {
+            Ok(urls) if !urls.is_empty() => cmd_args.extend(stream_args(&player, &urls)),
+            Ok(_) => {
+                return (
+                    PlayResult {
+                        success: false,
+                        player,
+                        error: Some("No stream URL returned".to_string()),
+                    },
+                    id,
+                )
             }
-            "iina" => {
-                format_arg = "--mpv-ytdl-format=bestvideo[height<=1080]+bestaudio/best[height<=1080]/best".to_string();
-                cmd_args.push(&format_arg);
+            Err(e) => {
+                return (
+                    PlayResult {
+                        success: false,
+                        player,
+                        error: Some(e),
+                    },
+                    id,
+                )
             }
-            _ => {}
         }
+    } else {
+        if let Some(arg) = format_arg(&player, format) {
+            cmd_args.push(arg);
+        }
+        if let Some(arg) = settings.cookies().and_then(|c| cookies_arg(&player, &c)) {
+            cmd_args.push(arg);
+        }
+        cmd_args.push(video_url.to_string());
     }
 
-    cmd_args.push(video_url);
-
     let result = std::process::Command::new(&player)
         .args(&cmd_args)
         .stdin(Stdio::null())
@@ -160,6 +603,9 @@ pub async fn play_video(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::extractor::FixtureExtractor;
+    use crate::runner::RunnerConfig;
+    use std::sync::Arc;
 
     // ── player_args tests ────────────────────────────────────
 
@@ -244,20 +690,28 @@ mod tests {
         let args = player_args(player);
         let mut cmd_args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
 
-        if max_resolution == "1080" {
-            match player {
-                "mpv" => {
-                    cmd_args.push("--ytdl-format=bestvideo[height<=1080]+bestaudio/best[height<=1080]/best".to_string());
-                }
-                "iina" => {
-                    cmd_args.push("--mpv-ytdl-format=bestvideo[height<=1080]+bestaudio/best[height<=1080]/best".to_string());
-                }
-                _ => {}
-            }
+        let prefs = FormatPrefs::from_settings(&crate::db::Settings::default(), max_resolution);
+        if let Some(arg) = format_arg(player, &prefs) {
+            cmd_args.push(arg);
         }
         cmd_args
     }
 
+    #[test]
+    fn test_cookies_arg() {
+        let file = Cookies::File("/tmp/a,b.txt".to_string());
+        assert_eq!(
+            cookies_arg("mpv", &file).unwrap(),
+            "--ytdl-raw-options-append=cookies=/tmp/a,b.txt"
+        );
+        let browser = Cookies::Browser("firefox:work".to_string());
+        assert_eq!(
+            cookies_arg("iina", &browser).unwrap(),
+            "--mpv-ytdl-raw-options-append=cookies-from-browser=firefox:work"
+        );
+        assert!(cookies_arg("vlc", &file).is_none());
+    }
+
     #[test]
     fn test_resolution_1080_mpv() {
         let args = build_cmd_args("mpv", "1080");
@@ -287,4 +741,202 @@ mod tests {
         let args = build_cmd_args("vlc", "1080");
         assert!(!args.iter().any(|a| a.contains("ytdl-format")));
     }
+
+    #[test]
+    fn test_resolution_720_mpv() {
+        let args = build_cmd_args("mpv", "720");
+        assert!(args.contains(
+            &"--ytdl-format=bestvideo[height<=720]+bestaudio/best[height<=720]/best".to_string()
+        ));
+    }
+
+    // ── Playback mode tests ──────────────────────────────────
+
+    #[test]
+    fn test_playback_mode_parse() {
+        assert_eq!(PlaybackMode::parse("auto"), PlaybackMode::Auto);
+        assert_eq!(PlaybackMode::parse("ytdl"), PlaybackMode::Ytdl);
+        assert_eq!(PlaybackMode::parse("stream"), PlaybackMode::Stream);
+        assert_eq!(PlaybackMode::parse("nonsense"), PlaybackMode::Auto);
+    }
+
+    #[test]
+    fn test_uses_streams_auto() {
+        assert!(!uses_streams("mpv", PlaybackMode::Auto));
+        assert!(!uses_streams("iina", PlaybackMode::Auto));
+        assert!(uses_streams("vlc", PlaybackMode::Auto));
+        assert!(uses_streams("celluloid", PlaybackMode::Auto));
+    }
+
+    #[test]
+    fn test_uses_streams_forced() {
+        assert!(uses_streams("mpv", PlaybackMode::Stream));
+        assert!(!uses_streams("vlc", PlaybackMode::Ytdl));
+    }
+
+    fn urls(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_stream_args_vlc_input_slave() {
+        let args = stream_args("vlc", &urls(&["https://v", "https://a"]));
+        assert_eq!(args, vec!["https://v", "--input-slave=https://a"]);
+    }
+
+    #[test]
+    fn test_stream_args_mpv_audio_file() {
+        let args = stream_args("mpv", &urls(&["https://v", "https://a"]));
+        assert_eq!(args, vec!["https://v", "--audio-file=https://a"]);
+    }
+
+    #[test]
+    fn test_stream_args_single_url() {
+        let args = stream_args("vlc", &urls(&["https://muxed"]));
+        assert_eq!(args, vec!["https://muxed"]);
+    }
+
+    #[test]
+    fn test_stream_args_unknown_player_drops_extra() {
+        let args = stream_args("celluloid", &urls(&["https://v", "https://a"]));
+        assert_eq!(args, vec!["https://v"]);
+    }
+
+    // ── start_arg tests ──────────────────────────────────────
+
+    #[test]
+    fn test_start_arg_per_player() {
+        assert_eq!(start_arg("mpv", 95).as_deref(), Some("--start=95"));
+        assert_eq!(start_arg("iina", 95).as_deref(), Some("--mpv-start=95"));
+        assert_eq!(start_arg("vlc", 95).as_deref(), Some("--start-time=95"));
+        assert_eq!(start_arg("unknown", 95), None);
+    }
+
+    // ── Command template tests ───────────────────────────────
+
+    fn vars() -> TemplateVars<'static> {
+        TemplateVars {
+            url: "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            stream_url: "https://rr1.googlevideo.com/videoplayback?x=1",
+            title: "Never Gonna Give You Up",
+            start: 42,
+            resolution: "1080",
+        }
+    }
+
+    #[test]
+    fn test_split_command_simple() {
+        assert_eq!(
+            split_command("celluloid {url}").unwrap(),
+            vec!["celluloid", "{url}"]
+        );
+    }
+
+    #[test]
+    fn test_split_command_quotes() {
+        assert_eq!(
+            split_command(r#"mpv --title="{title} - yt" '--script-opts=a b' {url}"#).unwrap(),
+            vec!["mpv", "--title={title} - yt", "--script-opts=a b", "{url}"]
+        );
+    }
+
+    #[test]
+    fn test_split_command_escapes() {
+        assert_eq!(
+            split_command(r"my\ player {url}").unwrap(),
+            vec!["my player", "{url}"]
+        );
+    }
+
+    #[test]
+    fn test_split_command_errors() {
+        assert!(split_command("").is_err());
+        assert!(split_command("   ").is_err());
+        assert!(split_command("mpv 'unterminated").is_err());
+    }
+
+    #[test]
+    fn test_expand_template_all_placeholders() {
+        let words = split_command(
+            "mpv --profile=yt --start={start} --title={title} --ytdl-format=bv[height<={resolution}] {url}",
+        )
+        .unwrap();
+        assert_eq!(
+            expand_template(&words, &vars()),
+            vec![
+                "mpv",
+                "--profile=yt",
+                "--start=42",
+                "--title=Never Gonna Give You Up",
+                "--ytdl-format=bv[height<=1080]",
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_template_stream_url() {
+        let words = split_command("vlc {stream_url}").unwrap();
+        assert_eq!(
+            expand_template(&words, &vars())[1],
+            "https://rr1.googlevideo.com/videoplayback?x=1"
+        );
+    }
+
+    #[test]
+    fn test_expand_template_does_not_expand_values() {
+        let words = split_command("player --title={title} {url} {unknown} {").unwrap();
+        let vars = TemplateVars {
+            title: "{url} and {start}",
+            ..vars()
+        };
+        assert_eq!(
+            expand_template(&words, &vars),
+            vec![
+                "player",
+                "--title={url} and {start}",
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                "{unknown}",
+                "{",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_template_title_stays_one_argument() {
+        let words = split_command("player --title {title}").unwrap();
+        let argv = expand_template(&words, &vars());
+        assert_eq!(argv.len(), 3);
+        assert_eq!(argv[2], "Never Gonna Give You Up");
+    }
+
+    // ── Queue tests ──────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_resolve_queue_caps_and_skips_failures() {
+        let fake = Arc::new(
+            FixtureExtractor::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ytdlp"))
+                .route("https://youtu.be/a", "stream_url.txt")
+                .route("https://youtu.be/b", "stream_url.txt"),
+        );
+        let config = RunnerConfig {
+            requests_per_minute: 0,
+            ..RunnerConfig::default()
+        };
+        let runner: &'static Runner =
+            Box::leak(Box::new(Runner::with_extractor(config, Box::new(fake.clone()))));
+        // The third entry has no recording, so yt-dlp fails for it
+        let urls: Vec<String> = ["a", "b", "gone", "a", "b", "a", "b"]
+            .iter()
+            .map(|id| format!("https://youtu.be/{}", id))
+            .collect();
+
+        let (streams, skipped) = resolve_queue(runner, &urls, "best").await;
+        assert_eq!(fake.calls().len(), STREAM_QUEUE_LIMIT);
+        assert_eq!(streams.len(), STREAM_QUEUE_LIMIT - 1);
+        assert!(streams.iter().all(|s| s.contains("googlevideo.com")));
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].0, "https://youtu.be/gone");
+        assert!(skipped[0].1.contains("no fixture"), "{}", skipped[0].1);
+    }
 }
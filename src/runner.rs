@@ -0,0 +1,433 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::timeout;
+
+use crate::db::{Cookies, Settings};
+use crate::extractor::{Exit, Extractor, Output, Process, YtDlp};
+
+/// Longest pause after repeated rate limiting.
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Limits shared by every yt-dlp invocation, taken from the settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunnerConfig {
+    /// The yt-dlp binary, by name or path.
+    pub program: String,
+    /// Arguments added to every call, before its own.
+    pub extra_args: Vec<String>,
+    /// Signed-in session passed to every call.
+    pub cookies: Option<Cookies>,
+    /// yt-dlp processes running at once.
+    pub concurrency: usize,
+    /// Processes started, plus direct requests paced here, per minute; 0 means
+    /// no limit.
+    pub requests_per_minute: u32,
+    /// Extra attempts after a rate-limited one.
+    pub retries: u32,
+    /// First pause after a rate limit, doubled each time it repeats.
+    pub backoff: Duration,
+    /// Feed downloads running at once during a refresh.
+    pub feed_concurrency: usize,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        RunnerConfig::from_settings(&Settings::default())
+    }
+}
+
+impl RunnerConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        RunnerConfig {
+            program: settings.ytdlp_path.clone(),
+            extra_args: settings.ytdlp_args.clone(),
+            cookies: settings.cookies(),
+            concurrency: settings.ytdlp_concurrency.max(1) as usize,
+            requests_per_minute: settings.ytdlp_requests_per_minute,
+            retries: settings.ytdlp_retries,
+            backoff: Duration::from_secs(settings.ytdlp_backoff_secs.max(1)),
+            feed_concurrency: settings.feed_concurrency.max(1) as usize,
+        }
+    }
+}
+
+// ── Token Bucket ───────────────────────────────────────────
+
+/// Allows bursts of up to `capacity` starts, refilled at a steady rate.
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    per_sec: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(per_minute: u32, capacity: usize, now: Instant) -> Self {
+        TokenBucket {
+            capacity: capacity as f64,
+            tokens: capacity as f64,
+            per_sec: per_minute as f64 / 60.0,
+            updated: now,
+        }
+    }
+
+    /// Take a token, or say how long until one is available.
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        if self.per_sec <= 0.0 {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec))
+        }
+    }
+}
+
+// ── Backoff ────────────────────────────────────────────────
+
+/// Pause shared by all tasks once YouTube starts rate limiting.
+#[derive(Default)]
+struct Backoff {
+    strikes: u32,
+    until: Option<Instant>,
+}
+
+impl Backoff {
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.until
+            .map(|until| until.saturating_duration_since(now))
+            .filter(|d| !d.is_zero())
+    }
+
+    /// Record a rate limit, doubling the pause each time one follows another.
+    fn throttled(&mut self, now: Instant, base: Duration) -> Duration {
+        let delay = base
+            .saturating_mul(2u32.saturating_pow(self.strikes))
+            .min(MAX_BACKOFF);
+        self.strikes = self.strikes.saturating_add(1);
+        let until = now + delay;
+        self.until = Some(self.until.map_or(until, |u| u.max(until)));
+        delay
+    }
+
+    fn succeeded(&mut self) {
+        self.strikes = 0;
+    }
+}
+
+/// Whether yt-dlp's error output says the request was rate limited.
+pub fn is_throttled(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    lower.contains("http error 429")
+        || lower.contains("too many requests")
+        || lower.contains("rate limit")
+}
+
+// ── Runner ─────────────────────────────────────────────────
+
+/// Runs yt-dlp under one global concurrency limit, start rate and backoff.
+pub struct Runner {
+    config: RunnerConfig,
+    extractor: Box<dyn Extractor>,
+    slots: Semaphore,
+    bucket: Mutex<TokenBucket>,
+    backoff: Mutex<Backoff>,
+}
+
+static RUNNER: OnceLock<Runner> = OnceLock::new();
+
+/// Set the limits for this process. Only the first call counts; calls made
+/// after the runner was first used are ignored.
+pub fn configure(config: RunnerConfig) {
+    let _ = RUNNER.set(Runner::new(config));
+}
+
+/// The shared runner, with default limits unless `configure` ran first.
+pub fn global() -> &'static Runner {
+    RUNNER.get_or_init(|| Runner::new(RunnerConfig::default()))
+}
+
+impl Runner {
+    pub fn new(config: RunnerConfig) -> Self {
+        let extractor = YtDlp::new(&config.program, &config.extra_args);
+        Runner::with_extractor(config, Box::new(extractor))
+    }
+
+    /// A runner answering from another backend, such as recorded fixtures.
+    pub fn with_extractor(config: RunnerConfig, extractor: Box<dyn Extractor>) -> Self {
+        Runner {
+            extractor,
+            slots: Semaphore::new(config.concurrency),
+            bucket: Mutex::new(TokenBucket::new(
+                config.requests_per_minute,
+                config.concurrency,
+                Instant::now(),
+            )),
+            backoff: Mutex::new(Backoff::default()),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &RunnerConfig {
+        &self.config
+    }
+
+    /// A call's arguments behind the cookie arguments, if any.
+    fn command_line(&self, args: &[&str]) -> Vec<String> {
+        let cookies = self.config.cookies.iter().flat_map(Cookies::ytdlp_args);
+        cookies.chain(args.iter().map(|a| a.to_string())).collect()
+    }
+
+    /// Hide the cookie file path or browser profile in yt-dlp's output.
+    fn redact(&self, text: &str) -> String {
+        match self.config.cookies {
+            Some(ref cookies) => cookies.redact(text),
+            None => text.to_string(),
+        }
+    }
+
+    /// Wait for a free slot, any shared backoff and a start token. Hold the
+    /// permit for as long as the process runs.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        let permit = self.slots.acquire().await.expect("runner semaphore closed");
+        self.pace().await;
+        permit
+    }
+
+    /// Wait out any shared backoff and take a start token, without holding a
+    /// slot. For direct requests to YouTube that don't go through yt-dlp.
+    pub async fn pace(&self) {
+        loop {
+            let wait = self.backoff.lock().unwrap().remaining(Instant::now());
+            match wait {
+                Some(d) => tokio::time::sleep(d).await,
+                None => break,
+            }
+        }
+        loop {
+            let wait = self.bucket.lock().unwrap().take(Instant::now());
+            match wait {
+                Some(d) => tokio::time::sleep(d).await,
+                None => break,
+            }
+        }
+    }
+
+    /// Note how a run went so every task backs off after a rate limit.
+    /// Returns whether it was rate limited.
+    pub fn report(&self, stderr: &str) -> bool {
+        let mut backoff = self.backoff.lock().unwrap();
+        if is_throttled(stderr) {
+            backoff.throttled(Instant::now(), self.config.backoff);
+            true
+        } else {
+            backoff.succeeded();
+            false
+        }
+    }
+
+    /// Note a successful run, so the next rate limit starts from the base pause.
+    pub fn succeeded(&self) {
+        self.backoff.lock().unwrap().succeeded();
+    }
+
+    /// Start yt-dlp for reading its output as it arrives. The caller holds a
+    /// slot from `acquire` and reports the outcome.
+    pub fn spawn(&self, args: &[&str]) -> Result<Process, String> {
+        let line = self.command_line(args);
+        let args: Vec<&str> = line.iter().map(String::as_str).collect();
+        let process = self.extractor.spawn(&args).map_err(|e| self.redact(&e))?;
+        let Some(cookies) = self.config.cookies.clone() else {
+            return Ok(process);
+        };
+        let exit = process.exit;
+        Ok(Process {
+            stdout: process.stdout,
+            exit: Box::pin(async move {
+                let exit = exit.await.map_err(|e| cookies.redact(&e))?;
+                Ok(Exit { stderr: cookies.redact(&exit.stderr), ..exit })
+            }),
+        })
+    }
+
+    /// Run yt-dlp to completion, retrying rate-limited attempts after the
+    /// shared backoff. `limit` bounds each attempt, not the wait for a slot.
+    /// A failed run is returned as output for the caller to judge.
+    pub async fn output(&self, args: &[&str], limit: Duration) -> Result<Output, String> {
+        let line = self.command_line(args);
+        let args: Vec<&str> = line.iter().map(String::as_str).collect();
+        let mut attempt = 0;
+        loop {
+            let permit = self.acquire().await;
+            let mut output = timeout(limit, self.extractor.output(&args))
+                .await
+                .map_err(|_| format!("yt-dlp timed out after {}s", limit.as_secs()))?
+                .map_err(|e| self.redact(&e))?;
+            drop(permit);
+            if self.config.cookies.is_some() {
+                output.stderr = self.redact(&String::from_utf8_lossy(&output.stderr)).into_bytes();
+            }
+
+            let throttled = if output.success {
+                self.succeeded();
+                false
+            } else {
+                self.report(&String::from_utf8_lossy(&output.stderr))
+            };
+            if !throttled || attempt >= self.config.retries {
+                return Ok(output);
+            }
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── TokenBucket tests ────────────────────────────────────
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(60, 2, start);
+        assert_eq!(bucket.take(start), None);
+        assert_eq!(bucket.take(start), None);
+        let wait = bucket.take(start).unwrap();
+        assert!(wait > Duration::from_millis(990) && wait <= Duration::from_secs(1));
+        assert_eq!(bucket.take(start + Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn test_bucket_unlimited() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(0, 1, start);
+        for _ in 0..100 {
+            assert_eq!(bucket.take(start), None);
+        }
+    }
+
+    // ── Backoff tests ────────────────────────────────────────
+
+    #[test]
+    fn test_backoff_doubles_and_resets() {
+        let now = Instant::now();
+        let base = Duration::from_secs(2);
+        let mut backoff = Backoff::default();
+        assert_eq!(backoff.remaining(now), None);
+        assert_eq!(backoff.throttled(now, base), Duration::from_secs(2));
+        assert_eq!(backoff.throttled(now, base), Duration::from_secs(4));
+        assert_eq!(backoff.remaining(now), Some(Duration::from_secs(4)));
+        assert_eq!(backoff.remaining(now + Duration::from_secs(5)), None);
+
+        backoff.succeeded();
+        assert_eq!(backoff.throttled(now, base), Duration::from_secs(2));
+        for _ in 0..20 {
+            backoff.throttled(now, base);
+        }
+        assert_eq!(backoff.throttled(now, base), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_is_throttled() {
+        assert!(is_throttled("ERROR: HTTP Error 429: Too Many Requests"));
+        assert!(is_throttled("Sign in to confirm you're not a bot. Rate limit exceeded"));
+        assert!(is_throttled("error: http error 429"));
+        assert!(!is_throttled("ERROR: Video unavailable"));
+        assert!(!is_throttled("ERROR: [youtube] xy429zAbcde: Private video"));
+    }
+
+    // ── Runner tests ─────────────────────────────────────────
+
+    #[test]
+    fn test_config_from_settings() {
+        let settings = Settings {
+            ytdlp_concurrency: 0,
+            ytdlp_requests_per_minute: 30,
+            ..Settings::default()
+        };
+        let config = RunnerConfig::from_settings(&settings);
+        assert_eq!(config.concurrency, 1);
+        assert_eq!(config.requests_per_minute, 30);
+    }
+
+    #[tokio::test]
+    async fn test_runner_passes_and_redacts_cookies() {
+        let fake = crate::extractor::FixtureExtractor::new(std::env::temp_dir());
+        let config = RunnerConfig {
+            cookies: Some(Cookies::File("/home/me/secret.txt".to_string())),
+            ..RunnerConfig::default()
+        };
+        let runner = Runner::with_extractor(config, Box::new(fake));
+        let output = runner
+            .output(&["--dump-json", "https://x/home/me/secret.txt"], Duration::from_secs(5))
+            .await
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(stderr, "ERROR: no fixture for https://x<redacted>");
+        assert_eq!(
+            runner.command_line(&["URL"]),
+            vec!["--cookies", "/home/me/secret.txt", "URL"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_runner_report_pauses_everyone() {
+        let runner = Runner::new(RunnerConfig {
+            backoff: Duration::from_millis(1000),
+            ..RunnerConfig::default()
+        });
+        assert!(runner.report("HTTP Error 429"));
+        assert!(runner.backoff.lock().unwrap().remaining(Instant::now()).is_some());
+        assert!(!runner.report("ERROR: Private video"));
+        assert_eq!(runner.backoff.lock().unwrap().strikes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pace_takes_a_token_without_a_slot() {
+        let runner = Runner::new(RunnerConfig {
+            concurrency: 1,
+            requests_per_minute: 1,
+            ..RunnerConfig::default()
+        });
+        let _permit = runner.slots.acquire().await.unwrap();
+        // The only slot is held, but a paced request still goes through...
+        let limit = Duration::from_millis(500);
+        assert!(tokio::time::timeout(limit, runner.pace()).await.is_ok());
+        // ...and spends the bucket, so the next one waits
+        assert!(runner.bucket.lock().unwrap().take(Instant::now()).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_runner_success_resets_strikes() {
+        let fake = crate::extractor::FixtureExtractor::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/ytdlp"
+        ))
+        .route("https://x/limited", "rate_limited.err")
+        .route("https://x/ok", "channel_info.json");
+        let config = RunnerConfig {
+            retries: 0,
+            backoff: Duration::from_millis(1),
+            ..RunnerConfig::default()
+        };
+        let runner = Runner::with_extractor(config, Box::new(fake));
+        let limit = Duration::from_secs(5);
+        let strikes = || runner.backoff.lock().unwrap().strikes;
+
+        assert!(!runner.output(&["https://x/limited"], limit).await.unwrap().success);
+        assert_eq!(strikes(), 1);
+        assert!(runner.output(&["https://x/ok"], limit).await.unwrap().success);
+        assert_eq!(strikes(), 0);
+        assert!(!runner.output(&["https://x/limited"], limit).await.unwrap().success);
+        assert_eq!(strikes(), 1);
+    }
+}
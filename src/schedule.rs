@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::db::{Database, Subscription, Video};
+
+/// Uploads looked at when estimating how often a channel posts.
+const HISTORY: usize = 10;
+
+/// Checks per typical gap between uploads.
+const CHECKS_PER_GAP: i32 = 8;
+
+fn min_interval() -> Duration {
+    Duration::minutes(30)
+}
+
+fn max_interval() -> Duration {
+    Duration::days(3)
+}
+
+// ── Intervals ──────────────────────────────────────────────
+
+/// How long to wait before checking a channel again, given its latest upload
+/// dates newest first. The typical gap is the mean spacing of those uploads,
+/// or the time since the newest one when that is longer, so channels that
+/// went quiet slow down. Channels with no history are checked soon.
+pub fn check_interval(uploads: &[DateTime<Utc>], now: DateTime<Utc>) -> Duration {
+    let (Some(newest), Some(oldest)) = (uploads.first(), uploads.last()) else {
+        return min_interval();
+    };
+    let since_newest = now.signed_duration_since(*newest);
+    let mean_gap = match uploads.len() {
+        1 => since_newest,
+        n => newest.signed_duration_since(*oldest) / (n as i32 - 1),
+    };
+    let typical = mean_gap.max(since_newest);
+    (typical / CHECKS_PER_GAP).clamp(min_interval(), max_interval())
+}
+
+// ── Scheduling ─────────────────────────────────────────────
+
+/// Subscriptions whose next check has come. Ones never scheduled are due.
+pub fn due(
+    subs: &[Subscription],
+    next_checks: &HashMap<String, DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Vec<Subscription> {
+    subs.iter()
+        .filter(|s| next_checks.get(&s.id).is_none_or(|at| *at <= now))
+        .cloned()
+        .collect()
+}
+
+/// Subscriptions to refresh now: the due ones, or all of them when forced.
+pub fn due_subscriptions(db: &Database, subs: &[Subscription], force: bool) -> Vec<Subscription> {
+    if force {
+        return subs.to_vec();
+    }
+    due(subs, &db.get_next_checks(), Utc::now())
+}
+
+/// Set the next check for channels that were just checked, from their
+/// stored upload history.
+pub fn reschedule(db: &Database, checked: &[String]) {
+    if checked.is_empty() {
+        return;
+    }
+    let history = db.get_recent_upload_dates(HISTORY);
+    let now = Utc::now();
+    for id in checked {
+        let uploads = history.get(id).map(Vec::as_slice).unwrap_or_default();
+        db.set_next_check(id, now + check_interval(uploads, now));
+    }
+}
+
+/// Store fetched feeds and schedule the channels that answered; failed ones
+/// stay due. Returns the fetched videos.
+pub fn record(db: &Database, results: Vec<(String, Result<Vec<Video>, String>)>) -> Vec<Video> {
+    let mut checked = Vec::new();
+    let mut fresh = Vec::new();
+    for (id, result) in results {
+        if let Ok(videos) = result {
+            db.store_source_videos(&id, &videos);
+            checked.push(id);
+            fresh.extend(videos);
+        }
+    }
+    reschedule(db, &checked);
+    fresh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sub(id: &str) -> Subscription {
+        Subscription {
+            id: id.to_string(),
+            name: id.to_string(),
+            url: format!("https://www.youtube.com/channel/{}", id),
+            added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
+        }
+    }
+
+    fn days_ago(now: DateTime<Utc>, days: &[i64]) -> Vec<DateTime<Utc>> {
+        days.iter().map(|d| now - Duration::days(*d)).collect()
+    }
+
+    // ── check_interval tests ─────────────────────────────────
+
+    #[test]
+    fn test_daily_uploader_checked_often() {
+        let now = Utc::now();
+        let uploads = days_ago(now, &[0, 1, 2, 3, 4]);
+        assert_eq!(check_interval(&uploads, now), Duration::hours(3));
+    }
+
+    #[test]
+    fn test_weekly_uploader() {
+        let now = Utc::now();
+        let uploads = days_ago(now, &[2, 9, 16, 23]);
+        assert_eq!(check_interval(&uploads, now), Duration::minutes(7 * 24 * 60 / 8));
+    }
+
+    #[test]
+    fn test_dormant_channel_checked_rarely() {
+        let now = Utc::now();
+        let uploads = days_ago(now, &[700, 701, 702]);
+        assert_eq!(check_interval(&uploads, now), max_interval());
+    }
+
+    #[test]
+    fn test_no_history_and_bursts_use_minimum() {
+        let now = Utc::now();
+        assert_eq!(check_interval(&[], now), min_interval());
+        let burst = vec![now, now - Duration::minutes(10)];
+        assert_eq!(check_interval(&burst, now), min_interval());
+    }
+
+    // ── due tests ────────────────────────────────────────────
+
+    #[test]
+    fn test_due() {
+        let now = Utc::now();
+        let subs = vec![sub("later"), sub("past"), sub("never")];
+        let next_checks: HashMap<String, DateTime<Utc>> = [
+            ("later".to_string(), now + Duration::hours(1)),
+            ("past".to_string(), now - Duration::minutes(1)),
+        ]
+        .into_iter()
+        .collect();
+        let ids: Vec<String> = due(&subs, &next_checks, now).into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["past", "never"]);
+    }
+
+    #[test]
+    fn test_record_schedules_answered_channels() {
+        let db = Database::open_in_memory().unwrap();
+        db.add_subscription(&sub("ok")).unwrap();
+        db.add_subscription(&sub("failed")).unwrap();
+
+        let fresh = record(
+            &db,
+            vec![
+                ("ok".to_string(), Ok(Vec::new())),
+                ("failed".to_string(), Err("Response is not a feed".to_string())),
+            ],
+        );
+        assert!(fresh.is_empty());
+        let subs = db.get_subscriptions();
+        let ids: Vec<String> = due_subscriptions(&db, &subs, false).into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["failed"]);
+        assert_eq!(due_subscriptions(&db, &subs, true).len(), 2);
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+use crate::runner;
+
+/// Videos checked per background pass.
+pub const BATCH_SIZE: usize = 50;
+
+const CONCURRENCY: usize = 8;
+
+// ── Classification ─────────────────────────────────────────
+
+/// What a `youtube.com/shorts/<id>` response says about a video. Shorts are
+/// served directly; regular videos redirect to `/watch`. Anything else, such as
+/// a consent page redirect or a rate limit, is inconclusive.
+fn classify_response(status: u16, location: Option<&str>) -> Option<bool> {
+    match status {
+        200 => Some(true),
+        300..=399 => location.filter(|l| l.contains("/watch")).map(|_| false),
+        _ => None,
+    }
+}
+
+pub struct ShortsClient {
+    http: reqwest::Client,
+}
+
+impl ShortsClient {
+    pub fn new() -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+        ShortsClient { http }
+    }
+
+    pub async fn is_short(&self, video_id: &str) -> Result<bool, String> {
+        // Shares yt-dlp's request budget so background checks can't get us limited
+        runner::global().pace().await;
+        let response = self
+            .http
+            .head(format!("https://www.youtube.com/shorts/{}", video_id))
+            .send()
+            .await
+            .map_err(|e| format!("Shorts check failed: {e}"))?;
+        let status = response.status().as_u16();
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok());
+        classify_response(status, location)
+            .ok_or_else(|| format!("Shorts check inconclusive ({})", status))
+    }
+}
+
+/// Check a batch of videos, a few at a time. None marks a failed or
+/// inconclusive check.
+pub async fn classify(video_ids: Vec<String>) -> Vec<(String, Option<bool>)> {
+    let client = std::sync::Arc::new(ShortsClient::new());
+    let mut results = Vec::new();
+    for chunk in video_ids.chunks(CONCURRENCY) {
+        let handles: Vec<_> = chunk
+            .iter()
+            .cloned()
+            .map(|id| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    let result = client.is_short(&id).await.ok();
+                    (id, result)
+                })
+            })
+            .collect();
+        for handle in handles {
+            if let Ok(result) = handle.await {
+                results.push(result);
+            }
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── classify_response tests ──────────────────────────────
+
+    #[test]
+    fn test_classify_response() {
+        assert_eq!(classify_response(200, None), Some(true));
+        assert_eq!(
+            classify_response(303, Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ")),
+            Some(false)
+        );
+        assert_eq!(classify_response(302, Some("https://consent.youtube.com/m?continue=x")), None);
+        assert_eq!(classify_response(302, None), None);
+        assert_eq!(classify_response(429, None), None);
+        assert_eq!(classify_response(404, None), None);
+    }
+}
@@ -0,0 +1,252 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::db::{db_dir, SponsorSegment};
+
+pub const DEFAULT_API_BASE: &str = "https://sponsor.ajay.app";
+
+const CATEGORIES: &[&str] = &["sponsor", "selfpromo", "interaction"];
+
+const SKIP_SCRIPT: &str = r#"-- Generated by feeding-tube: skips SponsorBlock segments passed via script-opts
+local opts = { segments = "" }
+require("mp.options").read_options(opts, "feedingtube_sponsorblock")
+
+local ranges = {}
+for s, e in string.gmatch(opts.segments, "([%d%.]+)-([%d%.]+)") do
+    table.insert(ranges, { tonumber(s), tonumber(e) })
+end
+
+mp.observe_property("time-pos", "number", function(_, pos)
+    if not pos then
+        return
+    end
+    for _, r in ipairs(ranges) do
+        if pos >= r[1] and pos < r[2] - 0.5 then
+            mp.set_property_number("time-pos", r[2])
+            mp.osd_message("Skipped sponsor segment")
+            return
+        end
+    end
+end)
+"#;
+
+// ── API Client ─────────────────────────────────────────────
+
+pub struct SponsorBlockClient {
+    api_base: String,
+    http: reqwest::Client,
+}
+
+impl SponsorBlockClient {
+    pub fn new(api_base: &str) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap_or_default();
+        SponsorBlockClient {
+            api_base: api_base.trim_end_matches('/').to_string(),
+            http,
+        }
+    }
+
+    /// Skip segments for a video. A video nobody has submitted segments for is
+    /// answered with 404, which is reported as an empty list rather than an error.
+    pub async fn fetch_segments(&self, video_id: &str) -> Result<Vec<SponsorSegment>, String> {
+        let categories = serde_json::to_string(CATEGORIES).unwrap();
+        let response = self
+            .http
+            .get(format!("{}/api/skipSegments", self.api_base))
+            .query(&[("videoID", video_id), ("categories", categories.as_str())])
+            .send()
+            .await
+            .map_err(|e| format!("SponsorBlock request failed: {e}"))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            return Err(format!("SponsorBlock returned {}", response.status()));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("SponsorBlock read failed: {e}"))?;
+        parse_segments(&body)
+    }
+}
+
+fn parse_segments(body: &str) -> Result<Vec<SponsorSegment>, String> {
+    let data: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| format!("SponsorBlock parse error: {e}"))?;
+
+    Ok(data
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter(|item| item["actionType"].as_str().unwrap_or("skip") == "skip")
+                .filter_map(|item| {
+                    let segment = item["segment"].as_array()?;
+                    Some(SponsorSegment {
+                        start: segment.first()?.as_f64()?,
+                        end: segment.get(1)?.as_f64()?,
+                        category: item["category"].as_str().unwrap_or("sponsor").to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+// ── mpv Integration ────────────────────────────────────────
+
+fn skip_script_path() -> PathBuf {
+    db_dir().join("sponsorblock.lua")
+}
+
+fn ensure_skip_script() -> Result<PathBuf, String> {
+    let path = skip_script_path();
+    if fs::read_to_string(&path).ok().as_deref() != Some(SKIP_SCRIPT) {
+        fs::create_dir_all(db_dir()).map_err(|e| format!("Failed to create data dir: {e}"))?;
+        fs::write(&path, SKIP_SCRIPT).map_err(|e| format!("Failed to write skip script: {e}"))?;
+    }
+    Ok(path)
+}
+
+fn segments_opt(segments: &[SponsorSegment]) -> String {
+    segments
+        .iter()
+        .map(|s| format!("{:.2}-{:.2}", s.start, s.end))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Arguments that load the skip script into mpv (or iina's embedded mpv).
+/// Other players get nothing, as do videos without segments.
+pub fn player_args(player: &str, segments: &[SponsorSegment]) -> Vec<String> {
+    let prefix = match player {
+        "mpv" => "--",
+        "iina" => "--mpv-",
+        _ => return Vec::new(),
+    };
+    if segments.is_empty() {
+        return Vec::new();
+    }
+    let script = match ensure_skip_script() {
+        Ok(p) => p,
+        Err(_) => return Vec::new(),
+    };
+    vec![
+        format!("{}script={}", prefix, script.display()),
+        format!(
+            "{}script-opts=feedingtube_sponsorblock-segments={}",
+            prefix,
+            segments_opt(segments)
+        ),
+    ]
+}
+
+pub fn total_seconds(segments: &[SponsorSegment]) -> f64 {
+    segments.iter().map(|s| (s.end - s.start).max(0.0)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve a single canned HTTP response on a random local port and return the
+    /// base URL plus a handle yielding the raw request line.
+    fn mock_server(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&buf[..n])
+                .lines()
+                .next()
+                .unwrap_or("")
+                .to_string()
+        });
+        (format!("http://{}", addr), handle)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_segments_from_mock() {
+        let body = r#"[
+            {"segment": [10.5, 30.0], "category": "sponsor", "actionType": "skip", "UUID": "a"},
+            {"segment": [100.0, 110.0], "category": "selfpromo", "actionType": "skip", "UUID": "b"},
+            {"segment": [200.0, 200.0], "category": "poi_highlight", "actionType": "poi", "UUID": "c"}
+        ]"#;
+        let (base, handle) = mock_server("200 OK", body);
+        let client = SponsorBlockClient::new(&base);
+
+        let segments = client.fetch_segments("dQw4w9WgXcQ").await.unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].start, 10.5);
+        assert_eq!(segments[0].end, 30.0);
+        assert_eq!(segments[1].category, "selfpromo");
+
+        let request_line = handle.join().unwrap();
+        assert!(request_line.starts_with("GET /api/skipSegments?videoID=dQw4w9WgXcQ&categories="));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_segments_not_found_is_empty() {
+        let (base, handle) = mock_server("404 Not Found", "Not Found");
+        let client = SponsorBlockClient::new(&format!("{}/", base));
+        let segments = client.fetch_segments("dQw4w9WgXcQ").await.unwrap();
+        assert!(segments.is_empty());
+        handle.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_segments_server_error() {
+        let (base, handle) = mock_server("500 Internal Server Error", "oops");
+        let client = SponsorBlockClient::new(&base);
+        assert!(client.fetch_segments("dQw4w9WgXcQ").await.is_err());
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_parse_segments_invalid_json() {
+        assert!(parse_segments("not json").is_err());
+    }
+
+    #[test]
+    fn test_segments_opt() {
+        let segments = vec![
+            SponsorSegment { start: 1.0, end: 2.5, category: "sponsor".to_string() },
+            SponsorSegment { start: 60.0, end: 75.25, category: "intro".to_string() },
+        ];
+        assert_eq!(segments_opt(&segments), "1.00-2.50;60.00-75.25");
+    }
+
+    #[test]
+    fn test_player_args_unsupported_or_empty() {
+        let segments = vec![SponsorSegment { start: 1.0, end: 2.0, category: "sponsor".to_string() }];
+        assert!(player_args("vlc", &segments).is_empty());
+        assert!(player_args("mpv", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_total_seconds() {
+        let segments = vec![
+            SponsorSegment { start: 10.0, end: 40.0, category: "sponsor".to_string() },
+            SponsorSegment { start: 100.0, end: 115.5, category: "sponsor".to_string() },
+        ];
+        assert_eq!(total_seconds(&segments), 45.5);
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+
+use serde_json::{json, Value};
+
+use crate::db::{Database, Subscription, Video};
+use crate::{schedule, shorts, ytdlp};
+
+/// Outcome of refreshing one subscription.
+pub struct ChannelSync {
+    pub id: String,
+    pub name: String,
+    pub new_videos: Vec<Video>,
+    pub error: Option<String>,
+}
+
+/// Outcome of a headless refresh, printed as a summary or JSON.
+pub struct SyncReport {
+    pub channels: Vec<ChannelSync>,
+    pub enriched: usize,
+}
+
+impl SyncReport {
+    pub fn new_count(&self) -> usize {
+        self.channels.iter().map(|c| c.new_videos.len()).sum()
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.channels.iter().filter(|c| c.error.is_some()).count()
+    }
+
+    /// 0 when every subscription refreshed, 2 when some failed and 1 when all
+    /// of them did.
+    pub fn exit_code(&self) -> i32 {
+        match self.failed_count() {
+            0 => 0,
+            n if n == self.channels.len() => 1,
+            _ => 2,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let channels: Vec<Value> = self
+            .channels
+            .iter()
+            .map(|c| {
+                let videos: Vec<Value> = c.new_videos.iter().map(|v| v.to_json()).collect();
+                json!({
+                    "id": c.id,
+                    "name": c.name,
+                    "newVideos": videos,
+                    "error": c.error,
+                })
+            })
+            .collect();
+        json!({
+            "channels": channels,
+            "newCount": self.new_count(),
+            "failedCount": self.failed_count(),
+            "enriched": self.enriched,
+        })
+    }
+
+    /// Human-readable summary, one line per subscription with news or errors.
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "Refreshed {} subscription(s): {} new video(s), {} failed",
+            self.channels.len(),
+            self.new_count(),
+            self.failed_count()
+        )];
+        for c in &self.channels {
+            if let Some(ref e) = c.error {
+                lines.push(format!("  {}: failed ({})", c.name, e));
+            } else if !c.new_videos.is_empty() {
+                lines.push(format!("  {}: {} new", c.name, c.new_videos.len()));
+                for v in &c.new_videos {
+                    lines.push(format!("    {}", v.title));
+                }
+            }
+        }
+        if self.enriched > 0 {
+            lines.push(format!("Fetched metadata for {} video(s)", self.enriched));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Refresh every subscription's feed and store the results. With `enrich`,
+/// new videos also get their duration, view count and Shorts flag filled in.
+pub async fn run(db: &Database, subs: &[Subscription], enrich: bool) -> SyncReport {
+    let results = ytdlp::fetch_all_channels_feeds(subs).await;
+    let ids: Vec<String> = results
+        .iter()
+        .filter_map(|(_, r)| r.as_ref().ok())
+        .flatten()
+        .map(|v| v.id.clone())
+        .collect();
+    let known = db.get_known_video_ids(&ids);
+
+    let mut channels = Vec::new();
+    for sub in subs {
+        let Some((_, result)) = results.iter().find(|(id, _)| *id == sub.id) else {
+            continue;
+        };
+        let channel = match result {
+            Ok(videos) => {
+                db.store_source_videos(&sub.id, videos);
+                ChannelSync {
+                    id: sub.id.clone(),
+                    name: sub.label().to_string(),
+                    new_videos: new_videos(videos, &known),
+                    error: None,
+                }
+            }
+            Err(e) => ChannelSync {
+                id: sub.id.clone(),
+                name: sub.label().to_string(),
+                new_videos: Vec::new(),
+                error: Some(e.clone()),
+            },
+        };
+        channels.push(channel);
+    }
+    // Channels checked here don't need another check at the next launch
+    let checked: Vec<String> = channels
+        .iter()
+        .filter(|c| c.error.is_none())
+        .map(|c| c.id.clone())
+        .collect();
+    schedule::reschedule(db, &checked);
+
+    let mut report = SyncReport { channels, enriched: 0 };
+    if enrich {
+        report.enriched = enrich_new_videos(db, &mut report).await;
+    }
+    report
+}
+
+fn new_videos(videos: &[Video], known: &HashSet<String>) -> Vec<Video> {
+    videos
+        .iter()
+        .filter(|v| !known.contains(&v.id))
+        .cloned()
+        .collect()
+}
+
+/// Fetch metadata and Shorts flags for the report's new videos, updating both
+/// the database and the report. Returns how many got metadata.
+async fn enrich_new_videos(db: &Database, report: &mut SyncReport) -> usize {
+    let fresh: Vec<Video> = report
+        .channels
+        .iter()
+        .flat_map(|c| c.new_videos.iter().cloned())
+        .collect();
+    if fresh.is_empty() {
+        return 0;
+    }
+
+    // The yt-dlp runner decides how many of these run at once
+    let handles: Vec<_> = fresh
+        .iter()
+        .cloned()
+        .map(|v| tokio::spawn(async move { ytdlp::get_video_metadata(&v).await }))
+        .collect();
+    let mut detailed = Vec::new();
+    for handle in handles {
+        if let Ok(Ok(video)) = handle.await {
+            detailed.push(video);
+        }
+    }
+    db.store_videos(&detailed);
+
+    let flags = shorts::classify(fresh.iter().map(|v| v.id.clone()).collect()).await;
+    db.set_short_flags(&flags);
+
+    for video in report.channels.iter_mut().flat_map(|c| c.new_videos.iter_mut()) {
+        if let Some(d) = detailed.iter().find(|d| d.id == video.id) {
+            video.duration = d.duration;
+            video.view_count = d.view_count;
+        }
+        if let Some((_, Some(is_short))) = flags.iter().find(|(id, _)| *id == video.id) {
+            video.is_short = *is_short;
+        }
+    }
+    detailed.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(id: &str) -> Video {
+        Video {
+            id: id.to_string(),
+            title: format!("Video {}", id),
+            url: format!("https://www.youtube.com/watch?v={}", id),
+            is_short: false,
+            channel_name: Some("Chan".to_string()),
+            channel_id: Some("UC1".to_string()),
+            published_date: None,
+            stored_at: None,
+            relative_date: String::new(),
+            duration: None,
+            duration_string: None,
+            view_count: None,
+            live_status: None,
+            thumbnail: None,
+        }
+    }
+
+    fn channel(name: &str, new_videos: Vec<Video>, error: Option<&str>) -> ChannelSync {
+        ChannelSync {
+            id: format!("UC{}", name),
+            name: name.to_string(),
+            new_videos,
+            error: error.map(|e| e.to_string()),
+        }
+    }
+
+    // ── SyncReport tests ─────────────────────────────────────
+
+    #[test]
+    fn test_exit_codes() {
+        let ok = SyncReport { channels: vec![channel("A", vec![], None)], enriched: 0 };
+        assert_eq!(ok.exit_code(), 0);
+        let partial = SyncReport {
+            channels: vec![channel("A", vec![], None), channel("B", vec![], Some("boom"))],
+            enriched: 0,
+        };
+        assert_eq!(partial.exit_code(), 2);
+        let failed = SyncReport { channels: vec![channel("B", vec![], Some("boom"))], enriched: 0 };
+        assert_eq!(failed.exit_code(), 1);
+        let empty = SyncReport { channels: vec![], enriched: 0 };
+        assert_eq!(empty.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_report_json() {
+        let report = SyncReport {
+            channels: vec![
+                channel("A", vec![video("v1"), video("v2")], None),
+                channel("B", vec![], Some("Response is not a feed")),
+            ],
+            enriched: 0,
+        };
+        let json = report.to_json();
+        assert_eq!(json["newCount"], 2);
+        assert_eq!(json["failedCount"], 1);
+        assert_eq!(json["channels"][0]["newVideos"][1]["id"], "v2");
+        assert!(json["channels"][0]["error"].is_null());
+        assert_eq!(json["channels"][1]["error"], "Response is not a feed");
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let report = SyncReport {
+            channels: vec![
+                channel("A", vec![video("v1")], None),
+                channel("B", vec![], Some("boom")),
+                channel("C", vec![], None),
+            ],
+            enriched: 0,
+        };
+        assert_eq!(
+            report.summary(),
+            "Refreshed 3 subscription(s): 1 new video(s), 1 failed\n  A: 1 new\n    Video v1\n  B: failed (boom)"
+        );
+    }
+
+    #[test]
+    fn test_new_videos_skips_known() {
+        let known: HashSet<String> = ["v1".to_string()].into_iter().collect();
+        let fresh = new_videos(&[video("v1"), video("v2")], &known);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].id, "v2");
+    }
+}
@@ -0,0 +1,406 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use image::imageops::FilterType;
+use image::RgbImage;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+use crate::db::db_dir;
+use crate::ytdlp::is_valid_video_id;
+
+/// Preview size in terminal cells. With the usual 1:2 cell shape this is close to 16:9.
+pub const COLS: u16 = 24;
+pub const ROWS: u16 = 7;
+
+const KITTY_CHUNK: usize = 4096;
+const DEFAULT_CELL_PX: (u32, u32) = (10, 20);
+
+// ── Protocols ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Protocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+    HalfBlocks,
+}
+
+impl Protocol {
+    /// Protocol for the `thumbnails` setting. "off" (or anything unknown) disables
+    /// previews; "auto" picks one from the terminal's environment.
+    pub fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::detect(|key| std::env::var(key).ok())),
+            "kitty" => Some(Protocol::Kitty),
+            "iterm2" => Some(Protocol::Iterm2),
+            "sixel" => Some(Protocol::Sixel),
+            "blocks" => Some(Protocol::HalfBlocks),
+            _ => None,
+        }
+    }
+
+    fn detect(env: impl Fn(&str) -> Option<String>) -> Self {
+        let term = env("TERM").unwrap_or_default();
+        let program = env("TERM_PROGRAM").unwrap_or_default();
+
+        if env("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || matches!(program.as_str(), "ghostty" | "WezTerm")
+        {
+            Protocol::Kitty
+        } else if program == "iTerm.app" || env("LC_TERMINAL").as_deref() == Some("iTerm2") {
+            Protocol::Iterm2
+        } else if ["foot", "mlterm", "sixel"].iter().any(|t| term.contains(t)) {
+            Protocol::Sixel
+        } else {
+            Protocol::HalfBlocks
+        }
+    }
+
+    /// Whether the terminal draws the image itself, outside ratatui's buffer.
+    pub fn is_graphics(&self) -> bool {
+        *self != Protocol::HalfBlocks
+    }
+}
+
+// ── Fetching ───────────────────────────────────────────────
+
+pub struct Thumbnail {
+    pub jpeg: Vec<u8>,
+    pub image: RgbImage,
+}
+
+/// The i.ytimg.com thumbnail for a video that came without one, such as from
+/// a database written before thumbnail URLs were stored.
+pub fn thumbnail_url(video_id: &str) -> String {
+    format!("https://i.ytimg.com/vi/{}/mqdefault.jpg", video_id)
+}
+
+fn cache_path(video_id: &str) -> Option<PathBuf> {
+    is_valid_video_id(video_id).then(|| db_dir().join("thumbnails").join(format!("{}.jpg", video_id)))
+}
+
+/// Load a thumbnail from the disk cache, downloading it first if needed from
+/// `url`, or the URL built from the video ID when there is none.
+pub async fn fetch(video_id: &str, url: Option<&str>) -> Result<Thumbnail, String> {
+    let path = cache_path(video_id).ok_or("Invalid video ID format")?;
+    if let Ok(jpeg) = fs::read(&path) {
+        return decode(jpeg);
+    }
+
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_default();
+    let jpeg = http
+        .get(url.map_or_else(|| thumbnail_url(video_id), str::to_string))
+        .send()
+        .await
+        .map_err(|e| format!("Thumbnail download failed: {e}"))?
+        .error_for_status()
+        .map_err(|e| format!("Thumbnail download failed: {e}"))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Thumbnail read failed: {e}"))?
+        .to_vec();
+
+    let thumbnail = decode(jpeg)?;
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(&path, &thumbnail.jpeg);
+    Ok(thumbnail)
+}
+
+/// Decode a downloaded thumbnail. The 4:3 sizes that feeds link to have the
+/// 16:9 frame letterboxed inside, so those are cropped to it.
+fn decode(jpeg: Vec<u8>) -> Result<Thumbnail, String> {
+    let image = image::load_from_memory(&jpeg)
+        .map_err(|e| format!("Thumbnail decode failed: {e}"))?
+        .to_rgb8();
+    let (width, height) = image.dimensions();
+    if width * 3 != height * 4 {
+        return Ok(Thumbnail { jpeg, image });
+    }
+    let frame = width * 9 / 16;
+    let image = image::imageops::crop_imm(&image, 0, (height - frame) / 2, width, frame).to_image();
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new(&mut jpeg)
+        .encode_image(&image)
+        .map_err(|e| format!("Thumbnail encode failed: {e}"))?;
+    Ok(Thumbnail { jpeg, image })
+}
+
+// ── Rendering ──────────────────────────────────────────────
+
+/// Render as Unicode upper-half blocks: each cell shows two pixels, the top one
+/// as foreground and the bottom one as background.
+pub fn half_block_lines(image: &RgbImage, cols: u16, rows: u16) -> Vec<Line<'static>> {
+    if cols == 0 || rows == 0 {
+        return Vec::new();
+    }
+    let scaled = image::imageops::resize(image, cols as u32, rows as u32 * 2, FilterType::Triangle);
+    (0..rows as u32)
+        .map(|row| {
+            let spans: Vec<Span> = (0..cols as u32)
+                .map(|col| {
+                    let top = scaled.get_pixel(col, row * 2);
+                    let bottom = scaled.get_pixel(col, row * 2 + 1);
+                    Span::styled(
+                        "▀",
+                        Style::default()
+                            .fg(Color::Rgb(top[0], top[1], top[2]))
+                            .bg(Color::Rgb(bottom[0], bottom[1], bottom[2])),
+                    )
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Escape sequence that draws `thumbnail` over `cols`x`rows` cells at the cursor.
+/// Returns None for the half-block fallback, which ratatui draws itself.
+pub fn escape_sequence(
+    protocol: Protocol,
+    thumbnail: &Thumbnail,
+    cols: u16,
+    rows: u16,
+    cell_px: (u16, u16),
+) -> Option<String> {
+    match protocol {
+        Protocol::Kitty => Some(kitty_sequence(&thumbnail.image, cols, rows)),
+        Protocol::Iterm2 => Some(format!(
+            "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+            thumbnail.jpeg.len(),
+            cols,
+            rows,
+            BASE64.encode(&thumbnail.jpeg)
+        )),
+        Protocol::Sixel => {
+            let (cell_w, cell_h) = match cell_px {
+                (w, h) if w > 0 && h > 0 => (w as u32, h as u32),
+                _ => DEFAULT_CELL_PX,
+            };
+            let scaled = image::imageops::resize(
+                &thumbnail.image,
+                cols as u32 * cell_w,
+                rows as u32 * cell_h,
+                FilterType::Triangle,
+            );
+            Some(sixel_sequence(&scaled))
+        }
+        Protocol::HalfBlocks => None,
+    }
+}
+
+/// Sequence removing previously drawn images, for protocols that keep images
+/// on their own layer. Cell-based protocols are cleared by repainting instead.
+pub fn clear_sequence(protocol: Protocol) -> Option<&'static str> {
+    match protocol {
+        Protocol::Kitty => Some("\x1b_Ga=d,q=2\x1b\\"),
+        _ => None,
+    }
+}
+
+fn kitty_sequence(image: &RgbImage, cols: u16, rows: u16) -> String {
+    let payload = BASE64.encode(image.as_raw());
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=24,s={},v={},c={},r={},C=1,q=2,m={};{}\x1b\\",
+                image.width(),
+                image.height(),
+                cols,
+                rows,
+                more,
+                chunk
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    out
+}
+
+/// Encode as sixel using a fixed 6x6x6 colour cube.
+fn sixel_sequence(image: &RgbImage) -> String {
+    let level = |v: u8| (v as u32 * 5 + 127) / 255;
+    let index = |p: &image::Rgb<u8>| (level(p[0]) * 36 + level(p[1]) * 6 + level(p[2])) as usize;
+
+    let (width, height) = image.dimensions();
+    let mut out = format!("\x1bPq\"1;1;{};{}", width, height);
+    for i in 0..216u32 {
+        let (r, g, b) = (i / 36, (i / 6) % 6, i % 6);
+        out.push_str(&format!("#{};2;{};{};{}", i, r * 20, g * 20, b * 20));
+    }
+
+    for band in (0..height).step_by(6) {
+        let band_height = (height - band).min(6);
+        let mut used = [false; 216];
+        for y in band..band + band_height {
+            for x in 0..width {
+                used[index(image.get_pixel(x, y))] = true;
+            }
+        }
+
+        for color in (0..216).filter(|&c| used[c]) {
+            out.push_str(&format!("#{}", color));
+            let mut run_char = '\0';
+            let mut run_len = 0;
+            for x in 0..width {
+                let bits = (0..band_height)
+                    .filter(|&dy| index(image.get_pixel(x, band + dy)) == color)
+                    .fold(0u8, |acc, dy| acc | (1 << dy));
+                let c = (63 + bits) as char;
+                if c == run_char {
+                    run_len += 1;
+                } else {
+                    push_sixel_run(&mut out, run_char, run_len);
+                    run_char = c;
+                    run_len = 1;
+                }
+            }
+            push_sixel_run(&mut out, run_char, run_len);
+            out.push('$');
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, c: char, len: usize) {
+    match len {
+        0 => {}
+        1..=3 => out.extend(std::iter::repeat_n(c, len)),
+        _ => out.push_str(&format!("!{}{}", len, c)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |key| {
+            pairs
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    fn solid(width: u32, height: u32, rgb: [u8; 3]) -> RgbImage {
+        RgbImage::from_pixel(width, height, image::Rgb(rgb))
+    }
+
+    // ── Protocol tests ───────────────────────────────────────
+
+    #[test]
+    fn test_from_setting() {
+        assert_eq!(Protocol::from_setting("off"), None);
+        assert_eq!(Protocol::from_setting("bogus"), None);
+        assert_eq!(Protocol::from_setting("kitty"), Some(Protocol::Kitty));
+        assert_eq!(Protocol::from_setting("blocks"), Some(Protocol::HalfBlocks));
+        assert!(Protocol::from_setting("auto").is_some());
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Protocol::detect(env(&[("KITTY_WINDOW_ID", "1")])), Protocol::Kitty);
+        assert_eq!(Protocol::detect(env(&[("TERM_PROGRAM", "WezTerm")])), Protocol::Kitty);
+        assert_eq!(Protocol::detect(env(&[("TERM_PROGRAM", "iTerm.app")])), Protocol::Iterm2);
+        assert_eq!(Protocol::detect(env(&[("TERM", "foot")])), Protocol::Sixel);
+        assert_eq!(Protocol::detect(env(&[("TERM", "xterm-256color")])), Protocol::HalfBlocks);
+    }
+
+    // ── Rendering tests ──────────────────────────────────────
+
+    #[test]
+    fn test_half_block_lines() {
+        let mut img = solid(2, 2, [255, 0, 0]);
+        for x in 0..2 {
+            img.put_pixel(x, 1, image::Rgb([0, 0, 255]));
+        }
+        let lines = half_block_lines(&img, 2, 1);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].spans.len(), 2);
+        let style = lines[0].spans[0].style;
+        assert_eq!(lines[0].spans[0].content, "▀");
+        assert_eq!(style.fg, Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(style.bg, Some(Color::Rgb(0, 0, 255)));
+    }
+
+    #[test]
+    fn test_half_block_lines_empty_area() {
+        assert!(half_block_lines(&solid(4, 4, [0, 0, 0]), 0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_kitty_sequence_chunks() {
+        let seq = kitty_sequence(&solid(64, 36, [10, 20, 30]), COLS, ROWS);
+        assert!(seq.starts_with("\x1b_Ga=T,f=24,s=64,v=36,c=24,r=7,C=1,q=2,m=1;"));
+        assert!(seq.contains("\x1b_Gm=0;"));
+        assert!(seq.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn test_sixel_sequence() {
+        let seq = sixel_sequence(&solid(8, 7, [255, 255, 255]));
+        assert!(seq.starts_with("\x1bPq\"1;1;8;7"));
+        // White is the last cube entry; a full band is '~', the 1-row remainder is '@'
+        assert!(seq.contains("#215!8~$-"));
+        assert!(seq.contains("#215!8@$-"));
+        assert!(seq.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn test_half_blocks_have_no_escape_sequence() {
+        let thumb = Thumbnail {
+            jpeg: Vec::new(),
+            image: solid(2, 2, [0, 0, 0]),
+        };
+        assert!(escape_sequence(Protocol::HalfBlocks, &thumb, COLS, ROWS, (0, 0)).is_none());
+        assert!(escape_sequence(Protocol::Iterm2, &thumb, COLS, ROWS, (0, 0))
+            .unwrap()
+            .starts_with("\x1b]1337;File=inline=1;size=0;width=24;height=7"));
+        assert_eq!(clear_sequence(Protocol::Sixel), None);
+    }
+
+    #[test]
+    fn test_cache_path_rejects_invalid_ids() {
+        assert!(cache_path("../../etc/pwd").is_none());
+        assert!(cache_path("dQw4w9WgXcQ")
+            .unwrap()
+            .ends_with("thumbnails/dQw4w9WgXcQ.jpg"));
+    }
+
+    #[test]
+    fn test_decode_crops_letterbox() {
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new(&mut jpeg)
+            .encode_image(&solid(48, 36, [10, 20, 30]))
+            .unwrap();
+        let thumb = decode(jpeg).unwrap();
+        assert_eq!(thumb.image.dimensions(), (48, 27));
+        assert_eq!(decode(thumb.jpeg).unwrap().image.dimensions(), (48, 27));
+    }
+
+    #[test]
+    fn test_thumbnail_url() {
+        assert_eq!(
+            thumbnail_url("dQw4w9WgXcQ"),
+            "https://i.ytimg.com/vi/dQw4w9WgXcQ/mqdefault.jpg"
+        );
+    }
+}
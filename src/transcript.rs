@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use regex::Regex;
+
+use crate::db::{decode_xml_entities, Transcript, TranscriptLine};
+use crate::ytdlp;
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleTrack {
+    pub language: String,
+    pub url: String,
+    pub automatic: bool,
+}
+
+// ── Track Selection ────────────────────────────────────────
+
+fn vtt_url(formats: &serde_json::Value) -> Option<String> {
+    formats
+        .as_array()?
+        .iter()
+        .find(|f| f["ext"].as_str() == Some("vtt"))
+        .and_then(|f| f["url"].as_str())
+        .map(|u| u.to_string())
+}
+
+/// Pick a VTT subtitle track from `--dump-json` metadata. Manual subtitles in
+/// `language` (including regional variants like en-GB) win over automatic
+/// captions, and original-language captions win over machine translations.
+pub fn pick_track(data: &serde_json::Value, language: &str) -> Option<SubtitleTrack> {
+    if let Some(subs) = data["subtitles"].as_object() {
+        let regional = format!("{}-", language);
+        let mut keys: Vec<&String> = subs
+            .keys()
+            .filter(|k| *k == language || k.starts_with(&regional))
+            .collect();
+        keys.sort_by_key(|k| *k != language);
+        for key in keys {
+            if let Some(url) = vtt_url(&subs[key]) {
+                return Some(SubtitleTrack {
+                    language: key.clone(),
+                    url,
+                    automatic: false,
+                });
+            }
+        }
+    }
+
+    let auto = data["automatic_captions"].as_object()?;
+    [format!("{}-orig", language), language.to_string()]
+        .into_iter()
+        .find_map(|key| {
+            let url = vtt_url(auto.get(&key)?)?;
+            Some(SubtitleTrack {
+                language: key,
+                url,
+                automatic: true,
+            })
+        })
+}
+
+// ── VTT Parsing ────────────────────────────────────────────
+
+fn parse_timestamp(s: &str) -> Option<f64> {
+    let parts: Vec<&str> = s.trim().split(':').collect();
+    let (h, m, sec) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<f64>().ok()?, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        [m, s] => (0.0, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        _ => return None,
+    };
+    Some(h * 3600.0 + m * 60.0 + sec)
+}
+
+/// Transcript lines from a WebVTT file. Inline styling and timing tags are
+/// stripped, and lines repeated by YouTube's rolling auto-captions are dropped.
+pub fn parse_vtt(text: &str) -> Vec<TranscriptLine> {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let normalized = text.replace("\r\n", "\n");
+    let mut lines: Vec<TranscriptLine> = Vec::new();
+
+    for block in normalized.split("\n\n") {
+        let mut block_lines = block.lines();
+        let start = match block_lines.by_ref().find(|l| l.contains("-->")) {
+            Some(timing) => match timing.split("-->").next().and_then(parse_timestamp) {
+                Some(start) => start,
+                None => continue,
+            },
+            None => continue,
+        };
+
+        for raw in block_lines {
+            let clean = decode_xml_entities(tags.replace_all(raw, "").trim());
+            if clean.is_empty() || lines.last().is_some_and(|l| l.text == clean) {
+                continue;
+            }
+            lines.push(TranscriptLine { start, text: clean });
+        }
+    }
+    lines
+}
+
+// ── Fetching ───────────────────────────────────────────────
+
+pub async fn fetch_transcript(video_id: &str, language: &str) -> Result<Transcript, String> {
+    let data = ytdlp::get_video_json(video_id).await?;
+    let track = pick_track(&data, language)
+        .ok_or_else(|| format!("No \"{}\" subtitles available", language))?;
+
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_default();
+    let body = http
+        .get(&track.url)
+        .send()
+        .await
+        .map_err(|e| format!("Subtitle download failed: {e}"))?
+        .error_for_status()
+        .map_err(|e| format!("Subtitle download failed: {e}"))?
+        .text()
+        .await
+        .map_err(|e| format!("Subtitle read failed: {e}"))?;
+
+    let lines = parse_vtt(&body);
+    if lines.is_empty() {
+        return Err("Subtitles were empty".to_string());
+    }
+    Ok(Transcript {
+        language: track.language,
+        automatic: track.automatic,
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── pick_track tests ─────────────────────────────────────
+
+    fn track_json() -> serde_json::Value {
+        serde_json::json!({
+            "subtitles": {
+                "en-GB": [{"ext": "json3", "url": "https://subs/en-GB.json3"}, {"ext": "vtt", "url": "https://subs/en-GB.vtt"}],
+                "live_chat": [{"ext": "json", "url": "https://chat"}]
+            },
+            "automatic_captions": {
+                "en": [{"ext": "vtt", "url": "https://auto/en.vtt"}],
+                "en-orig": [{"ext": "vtt", "url": "https://auto/en-orig.vtt"}],
+                "de": [{"ext": "vtt", "url": "https://auto/de.vtt"}]
+            }
+        })
+    }
+
+    #[test]
+    fn test_pick_track_prefers_manual() {
+        let track = pick_track(&track_json(), "en").unwrap();
+        assert_eq!(track.language, "en-GB");
+        assert_eq!(track.url, "https://subs/en-GB.vtt");
+        assert!(!track.automatic);
+    }
+
+    #[test]
+    fn test_pick_track_falls_back_to_original_auto_captions() {
+        let mut data = track_json();
+        data["subtitles"] = serde_json::json!({});
+        let track = pick_track(&data, "en").unwrap();
+        assert_eq!(track.url, "https://auto/en-orig.vtt");
+        assert!(track.automatic);
+
+        assert_eq!(pick_track(&data, "de").unwrap().url, "https://auto/de.vtt");
+        assert!(pick_track(&data, "fr").is_none());
+    }
+
+    #[test]
+    fn test_pick_track_no_subtitles() {
+        assert!(pick_track(&serde_json::json!({}), "en").is_none());
+    }
+
+    // ── parse_vtt tests ──────────────────────────────────────
+
+    #[test]
+    fn test_parse_vtt_manual() {
+        let vtt = "WEBVTT\r\nKind: captions\r\n\r\n1\r\n00:00:01.500 --> 00:00:04.000\r\nHello &amp; welcome\r\n\r\n00:01:02.000 --> 00:01:05.000 align:start\r\n<i>Second</i> line\r\nstill second cue\r\n";
+        let lines = parse_vtt(vtt);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], TranscriptLine { start: 1.5, text: "Hello & welcome".to_string() });
+        assert_eq!(lines[1].start, 62.0);
+        assert_eq!(lines[1].text, "Second line");
+        assert_eq!(lines[2].text, "still second cue");
+    }
+
+    #[test]
+    fn test_parse_vtt_rolling_auto_captions() {
+        let vtt = "WEBVTT\n\n\
+00:00:00.000 --> 00:00:02.000 align:start position:0%\n\
+so<00:00:00.500><c> today</c><00:00:01.000><c> we</c>\n\n\
+00:00:02.000 --> 00:00:02.010 align:start position:0%\n\
+so today we\n \n\n\
+00:00:02.010 --> 00:00:04.000 align:start position:0%\n\
+so today we\n\
+build<00:00:02.500><c> a</c><00:00:03.000><c> parser</c>\n";
+        let lines = parse_vtt(vtt);
+        let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, vec!["so today we", "build a parser"]);
+        assert_eq!(lines[1].start, 2.01);
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("01:02:03.500"), Some(3723.5));
+        assert_eq!(parse_timestamp("02:03.000"), Some(123.0));
+        assert_eq!(parse_timestamp("nope"), None);
+    }
+}
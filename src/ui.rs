@@ -5,8 +5,13 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Scrollbar, Scrollb
 use ratatui::Frame;
 use unicode_width::UnicodeWidthStr;
 
-use crate::app::{App, Mode, Screen};
-use crate::db::{format_views, Video};
+use crate::app::{App, InfoTarget, Mode, Screen};
+use crate::db::{
+    format_duration, format_views, get_relative_date, Comment, LiveStatus, Video, VideoDescription,
+};
+use crate::format;
+use crate::thumbnail;
+use crate::ytdlp::ChannelTab;
 
 // ── Color Palette (mindful-jira inspired) ──────────────────
 
@@ -36,19 +41,15 @@ pub fn draw(f: &mut Frame, app: &App) {
         area,
     );
 
-    let chunks = Layout::vertical([
-        Constraint::Length(3),  // Header
-        Constraint::Min(3),    // Content
-        Constraint::Length(2), // Status bar
-    ])
-    .split(area);
+    let chunks = main_layout(area);
 
     draw_header(f, app, chunks[0]);
 
+    let (list_area, _) = preview_column(app, chunks[1]);
     match app.screen {
         Screen::Channels => draw_channel_list(f, app, chunks[1]),
-        Screen::Videos => draw_video_list(f, app, chunks[1]),
-        Screen::Search => draw_search_results(f, app, chunks[1]),
+        Screen::Videos => draw_video_list(f, app, list_area),
+        Screen::Search => draw_search_results(f, app, list_area),
     }
 
     draw_status_bar(f, app, chunks[2]);
@@ -58,9 +59,21 @@ pub fn draw(f: &mut Frame, app: &App) {
         dim_background(f);
         draw_description_panel(f, app);
     }
+    if app.show_comments {
+        dim_background(f);
+        draw_comments_panel(f, app);
+    }
+    if app.show_transcript {
+        dim_background(f);
+        draw_transcript_panel(f, app);
+    }
+
+    if let Some((slot, video_id)) = thumbnail_slot(app, area) {
+        draw_thumbnail(f, app, slot, &video_id);
+    }
 
     match app.mode {
-        Mode::Add | Mode::GlobalSearch | Mode::NewSearch => {
+        Mode::Add | Mode::GlobalSearch | Mode::NewSearch | Mode::Rename => {
             draw_input_overlay(f, app);
         }
         Mode::ConfirmDelete
@@ -69,6 +82,7 @@ pub fn draw(f: &mut Frame, app: &App) {
         | Mode::ConfirmMarkAll
         | Mode::ConfirmMarkAllVideos
         | Mode::ConfirmAddChannel
+        | Mode::ConfirmSubscribeLink
         | Mode::ConfirmChannelWatched => {
             draw_confirm_overlay(f, app);
         }
@@ -76,6 +90,90 @@ pub fn draw(f: &mut Frame, app: &App) {
     }
 }
 
+fn main_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::vertical([
+        Constraint::Length(3), // Header
+        Constraint::Min(3),    // Content
+        Constraint::Length(2), // Status bar
+    ])
+    .split(area)
+}
+
+// ── Thumbnails ─────────────────────────────────────────────
+
+/// Split a video list area into the table and, when previews are on and there is
+/// room, a column on the right for the selected video's thumbnail.
+fn preview_column(app: &App, area: Rect) -> (Rect, Option<Rect>) {
+    let wanted = app.thumbnail_protocol.is_some()
+        && app.screen != Screen::Channels
+        && area.width >= 90
+        && area.height > thumbnail::ROWS;
+    if !wanted {
+        return (area, None);
+    }
+    let cols = Layout::horizontal([
+        Constraint::Min(0),
+        Constraint::Length(thumbnail::COLS + 2),
+    ])
+    .split(area);
+    (cols[0], Some(cols[1]))
+}
+
+/// Where the selected video's thumbnail goes on screen, if anywhere: at the top
+/// of the info panel when it is open, otherwise beside the selected row.
+pub fn thumbnail_slot(app: &App, area: Rect) -> Option<(Rect, String)> {
+    app.thumbnail_protocol?;
+    if !matches!(app.mode, Mode::List | Mode::Filter)
+        || app.loading
+        || app.show_comments
+        || app.show_transcript
+    {
+        return None;
+    }
+
+    let (selected, scroll) = match app.screen {
+        Screen::Videos => (app.video_selected, app.video_scroll),
+        Screen::Search => (app.search_selected, app.search_scroll),
+        Screen::Channels => return None,
+    };
+    let video_id = app.filtered_videos().get(selected)?.id.clone();
+
+    if app.show_description {
+        if app.loading_description || app.description.is_none() {
+            return None;
+        }
+        let inner = description_modal_area(area).inner(ratatui::layout::Margin::new(1, 1));
+        if inner.width < thumbnail::COLS || inner.height <= thumbnail::ROWS + 1 {
+            return None;
+        }
+        let x = inner.x + (inner.width - thumbnail::COLS) / 2;
+        return Some((Rect::new(x, inner.y, thumbnail::COLS, thumbnail::ROWS), video_id));
+    }
+
+    let content = main_layout(area)[1];
+    let column = preview_column(app, content).1?;
+    // Rows start below the table header; keep the preview inside the column
+    let row_y = content.y + 1 + selected.saturating_sub(scroll) as u16;
+    let y = row_y.min(content.bottom().saturating_sub(thumbnail::ROWS));
+    Some((
+        Rect::new(column.x + 1, y, thumbnail::COLS, thumbnail::ROWS),
+        video_id,
+    ))
+}
+
+fn draw_thumbnail(f: &mut Frame, app: &App, slot: Rect, video_id: &str) {
+    f.render_widget(Clear, slot);
+    let loaded = app.thumbnails.get(video_id);
+    match (app.thumbnail_protocol, loaded) {
+        (Some(thumbnail::Protocol::HalfBlocks), Some(thumb)) => {
+            let lines = thumbnail::half_block_lines(&thumb.image, slot.width, slot.height);
+            f.render_widget(Paragraph::new(lines), slot);
+        }
+        // Graphics protocols paint over these cells after the frame is drawn
+        _ => f.render_widget(Block::default().style(Style::default().bg(ZEBRA_DARK)), slot),
+    }
+}
+
 // ── Header ─────────────────────────────────────────────────
 
 fn draw_header(f: &mut Frame, app: &App, area: Rect) {
@@ -89,14 +187,13 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
     ];
 
     let title = match app.screen {
+        Screen::Channels if app.show_archived => "Archived Channels".to_string(),
         Screen::Channels => "Channels".to_string(),
-        Screen::Videos => {
-            if let Some(ref ch) = app.current_channel {
-                ch.name.clone()
-            } else {
-                "All Videos".to_string()
-            }
-        }
+        Screen::Videos => match (&app.current_channel, &app.open_playlist) {
+            (Some(ch), Some(playlist)) => format!("{} › {}", ch.label(), playlist.title),
+            (Some(ch), None) => ch.label().to_string(),
+            (None, _) => "All Videos".to_string(),
+        },
         Screen::Search => "Search YouTube".to_string(),
     };
 
@@ -116,7 +213,7 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
     }
 
     // Watched progress (channel video view only)
-    if app.screen == Screen::Videos {
+    if app.screen == Screen::Videos && !app.showing_playlists() {
         let filtered = app.filtered_videos();
         let total = filtered.len();
         let watched = filtered.iter().filter(|v| app.watched_ids.contains(&v.id)).count();
@@ -147,17 +244,15 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
 
     // Resolution indicator
     spans.push(Span::styled(" │ ", Style::default().fg(DARK_GRAY)));
-    if app.max_resolution == "1080" {
-        spans.push(Span::styled(
-            "1080p",
-            Style::default().fg(YELLOW),
-        ));
+    let res_color = if format::parse_height(&app.max_resolution).is_some() {
+        YELLOW
     } else {
-        spans.push(Span::styled(
-            "max res",
-            Style::default().fg(GRAY),
-        ));
-    }
+        GRAY
+    };
+    spans.push(Span::styled(
+        format::resolution_label(&app.max_resolution),
+        Style::default().fg(res_color),
+    ));
 
     // Loading indicator
     if app.loading || !app.loading_message.is_empty() {
@@ -177,12 +272,45 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
         chunks[0],
     );
 
-    // Separator line
-    let sep = "─".repeat(area.width as usize);
-    f.render_widget(
-        Paragraph::new(Line::from(Span::styled(&sep, Style::default().fg(DARK_GRAY)))),
-        chunks[1],
-    );
+    // Separator line, carrying the tab strip inside a channel
+    let separator = if has_channel_tabs(app) {
+        tab_strip(app, area.width as usize)
+    } else {
+        Line::from(Span::styled(
+            "─".repeat(area.width as usize),
+            Style::default().fg(DARK_GRAY),
+        ))
+    };
+    f.render_widget(Paragraph::new(separator), chunks[1]);
+}
+
+fn has_channel_tabs(app: &App) -> bool {
+    app.screen == Screen::Videos
+        && app
+            .current_channel
+            .as_ref()
+            .is_some_and(|c| c.kind == crate::db::SourceKind::Channel)
+}
+
+fn tab_strip(app: &App, width: usize) -> Line<'static> {
+    let mut spans = vec![Span::styled("─", Style::default().fg(DARK_GRAY))];
+    let mut used = 1;
+    for tab in ChannelTab::ALL {
+        let style = if tab == app.channel_tab {
+            Style::default().fg(CYAN).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(GRAY)
+        };
+        let label = format!(" {} ", tab.label());
+        used += label.len() + 1;
+        spans.push(Span::styled(label, style));
+        spans.push(Span::styled("─", Style::default().fg(DARK_GRAY)));
+    }
+    spans.push(Span::styled(
+        "─".repeat(width.saturating_sub(used)),
+        Style::default().fg(DARK_GRAY),
+    ));
+    Line::from(spans)
 }
 
 fn build_subtitle(app: &App) -> String {
@@ -201,6 +329,20 @@ fn build_subtitle(app: &App) -> String {
                 filter_info
             )
         }
+        Screen::Videos if app.showing_playlists() => {
+            let count = app.filtered_playlists().len();
+            let filter_info = if app.filter_text.is_empty() {
+                String::new()
+            } else {
+                format!(" filter: \"{}\"", app.filter_text)
+            };
+            format!(
+                "{} playlist{}{}",
+                count,
+                if count != 1 { "s" } else { "" },
+                filter_info
+            )
+        }
         Screen::Videos => {
             let filtered = app.filtered_videos();
             let count = filtered.len();
@@ -240,10 +382,28 @@ fn build_subtitle(app: &App) -> String {
 
 // ── Channel List ───────────────────────────────────────────
 
+/// Channel list label; playlists get a marker so the two source kinds are
+/// distinguishable at a glance, a bell marks notifying subscriptions and a
+/// gem those listing members-only videos.
+fn subscription_label(sub: &crate::db::Subscription) -> String {
+    let label = match sub.kind {
+        crate::db::SourceKind::Channel => sub.label().to_string(),
+        crate::db::SourceKind::Playlist => format!("≡ {}", sub.label()),
+    };
+    let mut label = label;
+    if sub.notify {
+        label.push_str(" 🔔");
+    }
+    if sub.members {
+        label.push_str(" 💎");
+    }
+    label
+}
+
 fn draw_channel_list(f: &mut Frame, app: &App, area: Rect) {
     let filtered = app.filtered_subscriptions();
 
-    if app.subscriptions.is_empty() {
+    if app.subscriptions.is_empty() && !app.show_archived {
         let empty = Paragraph::new(vec![
             Line::from(Span::styled("No subscriptions yet.", Style::default().fg(GRAY))),
             Line::from(Span::styled(
@@ -257,8 +417,13 @@ fn draw_channel_list(f: &mut Frame, app: &App, area: Rect) {
     }
 
     if filtered.is_empty() {
+        let msg = if app.filter_text.is_empty() {
+            "No archived channels."
+        } else {
+            "No channels match filter."
+        };
         let empty = Paragraph::new(Line::from(Span::styled(
-            "No channels match filter.",
+            msg,
             Style::default().fg(GRAY),
         )))
         .style(Style::default().bg(BODY_BG));
@@ -311,6 +476,11 @@ fn draw_channel_list(f: &mut Frame, app: &App, area: Rect) {
     let visible_count = content_area.height as usize;
     let scroll = app.channel_scroll;
     let selected = app.channel_selected;
+    let inactive: std::collections::HashSet<&str> = app
+        .archive_suggestions()
+        .iter()
+        .map(|s| s.id.as_str())
+        .collect();
 
     let rows: Vec<Row> = filtered
         .iter()
@@ -354,20 +524,27 @@ fn draw_channel_list(f: &mut Frame, app: &App, area: Rect) {
             };
             let combined_suffix_len = new_suffix.len() + upcoming_suffix.len();
             let available_name = name_col.saturating_sub(combined_suffix_len);
-            let name_display = truncate_str(&sub.name, available_name.saturating_sub(1));
+            // A moon suggests archiving a channel that stopped uploading
+            let label = if inactive.contains(sub.id.as_str()) {
+                format!("{} 🌙", subscription_label(sub))
+            } else {
+                subscription_label(sub)
+            };
+            let name_display = truncate_str(&label, available_name.saturating_sub(1));
+            let name_width = UnicodeWidthStr::width(name_display.as_str());
             let name_cell = if new_count > 0 || upcoming_count > 0 {
                 let mut spans = vec![Span::styled(name_display.clone(), name_style)];
                 if new_count > 0 {
                     spans.push(Span::styled(new_suffix.clone(), Style::default().fg(GREEN)));
                 }
                 if upcoming_count > 0 {
-                    let remaining = name_col.saturating_sub(name_display.len() + new_suffix.len());
+                    let remaining = name_col.saturating_sub(name_width + new_suffix.len());
                     spans.push(Span::styled(
                         pad_str(&upcoming_suffix, remaining),
                         Style::default().fg(CYAN),
                     ));
                 } else {
-                    let remaining = name_col.saturating_sub(name_display.len() + new_suffix.len());
+                    let remaining = name_col.saturating_sub(name_width + new_suffix.len());
                     spans.push(Span::styled(
                         " ".repeat(remaining),
                         Style::default(),
@@ -450,6 +627,10 @@ fn draw_channel_list(f: &mut Frame, app: &App, area: Rect) {
 // ── Video List ─────────────────────────────────────────────
 
 fn draw_video_list(f: &mut Frame, app: &App, area: Rect) {
+    if app.showing_playlists() {
+        draw_playlist_list(f, app, area);
+        return;
+    }
     let filtered = app.filtered_videos();
 
     if filtered.is_empty() {
@@ -471,6 +652,108 @@ fn draw_video_list(f: &mut Frame, app: &App, area: Rect) {
     draw_video_table(f, app, area, &filtered, show_channel, app.video_selected, app.video_scroll);
 }
 
+// ── Playlist List ──────────────────────────────────────────
+
+fn draw_playlist_list(f: &mut Frame, app: &App, area: Rect) {
+    let filtered = app.filtered_playlists();
+    if filtered.is_empty() {
+        let msg = if app.loading { "" } else { "No playlists found." };
+        let empty = Paragraph::new(Line::from(Span::styled(msg, Style::default().fg(GRAY))))
+            .style(Style::default().bg(BODY_BG));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let chunks = Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).split(area);
+    let content_area = chunks[1];
+    let width = area.width as usize;
+    let pointer_col = 2;
+    let count_col = 8;
+    let title_col = width.saturating_sub(pointer_col + count_col);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::raw(pad_str("", pointer_col)),
+        Span::styled(pad_str("Playlist", title_col), Style::default().fg(DIM_FG)),
+        Span::styled(pad_str("Videos", count_col), Style::default().fg(DIM_FG)),
+    ]))
+    .style(Style::default().bg(BODY_BG));
+    f.render_widget(header, chunks[0]);
+
+    let visible_count = content_area.height as usize;
+    let rows: Vec<Row> = filtered
+        .iter()
+        .enumerate()
+        .skip(app.video_scroll)
+        .take(visible_count)
+        .map(|(i, playlist)| {
+            let is_selected = i == app.video_selected;
+            let bg = if is_selected {
+                HIGHLIGHT_BG
+            } else if i % 2 == 0 {
+                BODY_BG
+            } else {
+                ZEBRA_DARK
+            };
+            let title_style = if is_selected {
+                Style::default().fg(CYAN).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(LIGHT_GRAY)
+            };
+            let count = playlist.video_count.map(|c| c.to_string()).unwrap_or_default();
+            Row::new(vec![
+                ratatui::widgets::Cell::from(Span::styled(
+                    if is_selected { "▶" } else { " " },
+                    Style::default().fg(ACCENT),
+                )),
+                ratatui::widgets::Cell::from(Span::styled(
+                    pad_str(&truncate_str(&playlist.title, title_col.saturating_sub(1)), title_col),
+                    title_style,
+                )),
+                ratatui::widgets::Cell::from(Span::styled(
+                    pad_str(&count, count_col),
+                    Style::default().fg(if is_selected { CYAN } else { GRAY }),
+                )),
+            ])
+            .style(Style::default().bg(bg))
+        })
+        .collect();
+
+    let table = Table::new(rows, [
+        Constraint::Length(pointer_col as u16),
+        Constraint::Length(title_col as u16),
+        Constraint::Length(count_col as u16),
+    ])
+    .style(Style::default().bg(BODY_BG));
+    f.render_widget(table, content_area);
+}
+
+/// Badge shown before the title of a live stream, or the scheduled start of an
+/// upcoming stream or premiere in local time.
+fn live_badge(video: &Video) -> Option<(String, Color)> {
+    match video.live_status? {
+        LiveStatus::Live => Some(("● LIVE".to_string(), RED)),
+        LiveStatus::Upcoming => Some((
+            video
+                .published_date
+                .map(|d| scheduled_label(d, chrono::Local::now()))
+                .unwrap_or_else(|| "UPCOMING".to_string()),
+            CYAN,
+        )),
+        LiveStatus::NotLive | LiveStatus::WasLive => None,
+    }
+}
+
+fn scheduled_label(start: chrono::DateTime<chrono::Utc>, now: chrono::DateTime<chrono::Local>) -> String {
+    let local = start.with_timezone(&chrono::Local);
+    if local.date_naive() == now.date_naive() {
+        local.format("Today %H:%M").to_string()
+    } else if (local - now).num_days() < 6 && local > now {
+        local.format("%a %H:%M").to_string()
+    } else {
+        local.format("%b %-d %H:%M").to_string()
+    }
+}
+
 // ── Search Results ─────────────────────────────────────────
 
 fn draw_search_results(f: &mut Frame, app: &App, area: Rect) {
@@ -517,8 +800,11 @@ fn draw_video_table(
     let duration_col = 8;
     let views_col = 8;
     let pointer_col = 3;
-    let title_col = width
-        .saturating_sub(pointer_col + channel_col + date_col + duration_col + views_col + 2);
+    let show_sponsor = app.settings.sponsorblock;
+    let sponsor_col = if show_sponsor { 7 } else { 0 };
+    let title_col = width.saturating_sub(
+        pointer_col + channel_col + date_col + duration_col + sponsor_col + views_col + 2,
+    );
 
     // Header row
     let mut header_cells: Vec<ratatui::widgets::Cell> = Vec::new();
@@ -540,6 +826,12 @@ fn draw_video_table(
         pad_str("Dur", duration_col),
         Style::default().fg(DIM_FG),
     )));
+    if show_sponsor {
+        header_cells.push(ratatui::widgets::Cell::from(Span::styled(
+            pad_str("Sponsor", sponsor_col),
+            Style::default().fg(DIM_FG),
+        )));
+    }
     header_cells.push(ratatui::widgets::Cell::from(Span::styled(
         pad_str("Views", views_col),
         Style::default().fg(DIM_FG),
@@ -555,6 +847,9 @@ fn draw_video_table(
     }
     header_widths.push(Constraint::Length(title_col as u16));
     header_widths.push(Constraint::Length(duration_col as u16));
+    if show_sponsor {
+        header_widths.push(Constraint::Length(sponsor_col as u16));
+    }
     header_widths.push(Constraint::Length(views_col as u16));
     header_widths.push(Constraint::Length(date_col as u16));
 
@@ -603,18 +898,27 @@ fn draw_video_table(
                 )));
             }
 
-            // Title
-            let title_display = truncate_str(&video.title, title_col.saturating_sub(1));
-            cells.push(ratatui::widgets::Cell::from(Span::styled(
-                pad_str(&title_display, title_col),
-                if is_selected {
-                    Style::default().fg(CYAN).add_modifier(Modifier::BOLD)
-                } else if is_watched {
-                    Style::default().fg(DIM_FG)
-                } else {
-                    Style::default().fg(LIGHT_GRAY)
-                },
-            )));
+            // Title, behind a badge for live and upcoming streams
+            let title_style = if is_selected {
+                Style::default().fg(CYAN).add_modifier(Modifier::BOLD)
+            } else if is_watched {
+                Style::default().fg(DIM_FG)
+            } else {
+                Style::default().fg(LIGHT_GRAY)
+            };
+            let mut title_spans = Vec::new();
+            let mut title_room = title_col;
+            if let Some((badge, color)) = live_badge(video) {
+                let badge = format!("{} ", badge);
+                title_room = title_room.saturating_sub(UnicodeWidthStr::width(badge.as_str()));
+                title_spans.push(Span::styled(
+                    badge,
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+            }
+            let title_display = truncate_str(&video.title, title_room.saturating_sub(1));
+            title_spans.push(Span::styled(pad_str(&title_display, title_room), title_style));
+            cells.push(ratatui::widgets::Cell::from(Line::from(title_spans)));
 
             // Duration + short tag
             let dur = video
@@ -638,6 +942,20 @@ fn draw_video_table(
                 Style::default().fg(dur_color),
             )));
 
+            // Sponsor time (only once SponsorBlock has been checked)
+            if show_sponsor {
+                let sponsor = app
+                    .sponsor_totals
+                    .get(&video.id)
+                    .filter(|&&secs| secs >= 1.0)
+                    .map(|&secs| format_duration(Some(secs.round() as i64)))
+                    .unwrap_or_default();
+                cells.push(ratatui::widgets::Cell::from(Span::styled(
+                    pad_str(&sponsor, sponsor_col),
+                    Style::default().fg(if is_selected { CYAN } else { YELLOW }),
+                )));
+            }
+
             // Views (always shown)
             let views = format_views(video.view_count);
             cells.push(ratatui::widgets::Cell::from(Span::styled(
@@ -711,6 +1029,7 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
     let (mode_label, mode_bg) = match app.mode {
         Mode::Filter => ("FILTER", Color::Rgb(180, 130, 50)),
         Mode::Add => ("ADD", Color::Rgb(60, 140, 60)),
+        Mode::Rename => ("RENAME", Color::Rgb(60, 140, 60)),
         Mode::GlobalSearch | Mode::NewSearch => ("SEARCH", Color::Rgb(60, 140, 60)),
         Mode::Description => ("INFO", Color::Rgb(80, 120, 180)),
         _ => match app.screen {
@@ -737,7 +1056,30 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
             "  Enter:confirm  Esc:cancel",
             Style::default().fg(GRAY),
         ));
+    } else if app.mode == Mode::TranscriptSearch {
+        spans.push(Span::styled("Find: ", Style::default().fg(YELLOW)));
+        spans.push(Span::styled(&app.transcript_query, Style::default().fg(LIGHT_GRAY)));
+        spans.push(Span::styled("│", Style::default().fg(ACCENT)));
+        spans.push(Span::styled(
+            "  Enter:find  Esc:cancel",
+            Style::default().fg(GRAY),
+        ));
+    } else if app.show_transcript {
+        spans.push(key_hint("j/k", "move"));
+        spans.push(key_hint("Enter", "play from line"));
+        spans.push(key_hint("/", "find"));
+        if !app.transcript_query.is_empty() {
+            spans.push(key_hint("n/N", "next/prev"));
+        }
+        spans.push(key_hint("t", "close transcript"));
+    } else if app.show_comments {
+        spans.push(key_hint("j/k", "scroll"));
+        spans.push(key_hint("c", "lose comments"));
     } else if app.show_description {
+        if !app.info_targets().is_empty() {
+            spans.push(key_hint("j/k", "select"));
+            spans.push(key_hint("Enter", "play/open"));
+        }
         spans.push(key_hint("i", "close info"));
     } else {
         // Key hints based on screen and mode
@@ -757,16 +1099,43 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
                         "s",
                         if app.hide_shorts { "+shorts" } else { "-shorts" },
                     ));
-                    spans.push(key_hint("h", "d"));
-                    spans.push(key_hint("r", "efresh"));
+                    spans.push(key_hint("h", "res"));
+                    if !app.filtered_subscriptions().is_empty() {
+                        spans.push(key_hint("n", "otify"));
+                        spans.push(key_hint("M", "embers"));
+                        spans.push(key_hint("e", "dit name"));
+                        spans.push(key_hint(
+                            "A",
+                            if app.show_archived { " restore" } else { "rchive" },
+                        ));
+                    }
+                    spans.push(key_hint(
+                        "Tab",
+                        if app.show_archived { "active" } else { "archived" },
+                    ));
+                    spans.push(key_hint("r/R", "efresh/force"));
                     spans.push(key_hint("m", "ark all"));
                     spans.push(key_hint("q", "uit"));
                 }
             }
+            Screen::Videos if app.showing_playlists() => {
+                if app.mode == Mode::List {
+                    spans.push(key_hint("Enter", "open"));
+                    spans.push(key_hint("e", "nqueue"));
+                    spans.push(key_hint("Tab", "next tab"));
+                    spans.push(key_hint("/", "filter"));
+                    spans.push(key_hint("r", "efresh"));
+                    spans.push(key_hint("b", "ack"));
+                    spans.push(key_hint("q", "uit"));
+                }
+            }
             Screen::Videos => {
                 if app.mode == Mode::List {
                     spans.push(key_hint("Enter", "play"));
+                    spans.push(key_hint("e", "nqueue from here"));
                     spans.push(key_hint("i", "nfo"));
+                    spans.push(key_hint("c", "omments"));
+                    spans.push(key_hint("t", "ranscript"));
                     spans.push(key_hint("w", "atched"));
                     if app.current_channel.is_some() {
                         spans.push(key_hint("m", "ark all"));
@@ -776,7 +1145,10 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
                         "s",
                         if app.hide_shorts { "+shorts" } else { "-shorts" },
                     ));
-                    spans.push(key_hint("h", "d"));
+                    spans.push(key_hint("h", "res"));
+                    if has_channel_tabs(app) {
+                        spans.push(key_hint("Tab", "next tab"));
+                    }
                     if app.current_channel.is_none() && app.total_pages() > 1 {
                         spans.push(key_hint("n", "ext"));
                         spans.push(key_hint("p", "rev"));
@@ -790,6 +1162,8 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
                 if app.mode == Mode::List {
                     spans.push(key_hint("Enter", "play"));
                     spans.push(key_hint("i", "nfo"));
+                    spans.push(key_hint("c", "omments"));
+                    spans.push(key_hint("t", "ranscript"));
                     spans.push(key_hint("a", "dd channel"));
                     spans.push(key_hint("g", "new search"));
                     spans.push(key_hint("b", "ack"));
@@ -851,8 +1225,14 @@ fn draw_input_overlay(f: &mut Frame, app: &App) {
     let area = modal_area(f, 60, 6);
     f.render_widget(Clear, area);
 
+    let upstream_name = app
+        .filtered_subscriptions()
+        .get(app.channel_selected)
+        .map(|s| s.name.clone())
+        .unwrap_or_default();
     let (title, placeholder) = match app.mode {
-        Mode::Add => ("Add Channel", "https://youtube.com/@channel"),
+        Mode::Add => ("Add Channel or Playlist", "https://youtube.com/@channel"),
+        Mode::Rename => ("Rename (empty restores the original)", upstream_name.as_str()),
         Mode::GlobalSearch => ("Search YouTube", "enter search query"),
         Mode::NewSearch => ("New Search", "enter search query"),
         _ => ("Input", ""),
@@ -890,12 +1270,12 @@ fn draw_confirm_overlay(f: &mut Frame, app: &App) {
             let filtered = app.filtered_subscriptions();
             let name = filtered
                 .get(app.channel_selected)
-                .map(|s| s.name.as_str())
+                .map(|s| s.label())
                 .unwrap_or("?");
             (
                 "Delete Channel",
                 format!("Delete \"{}\"?", name),
-                "y:Yes  n:No",
+                "y:Remove  a:Archive  p:Remove + delete videos  n:Cancel",
             )
         }
         Mode::ConfirmPrime => {
@@ -935,7 +1315,7 @@ fn draw_confirm_overlay(f: &mut Frame, app: &App) {
             let name = app
                 .filtered_subscriptions()
                 .get(app.channel_selected)
-                .map(|s| s.name.as_str())
+                .map(|s| s.label())
                 .unwrap_or("?");
             (
                 "Mark Watched",
@@ -955,6 +1335,14 @@ fn draw_confirm_overlay(f: &mut Frame, app: &App) {
                 "Y:Yes  n:No",
             )
         }
+        Mode::ConfirmSubscribeLink => {
+            let url = app.pending_link.as_deref().unwrap_or("?");
+            (
+                "Subscribe",
+                format!("Subscribe to {}?", truncate_str(url, 40)),
+                "Y:Yes  n:No",
+            )
+        }
         _ => ("Confirm", String::new(), ""),
     };
 
@@ -973,13 +1361,35 @@ fn draw_confirm_overlay(f: &mut Frame, app: &App) {
 
 // ── Description Panel (Modal) ──────────────────────────────
 
-fn draw_description_panel(f: &mut Frame, app: &App) {
-    let area = f.area();
+/// "2024-01-15 · 12:34 · 1.2M views · 4K likes", skipping whatever is unknown.
+fn description_meta(desc: &VideoDescription) -> String {
+    let mut parts = Vec::new();
+    if let Some(ref date) = desc.upload_date {
+        parts.push(date.clone());
+    }
+    if desc.duration.is_some_and(|d| d > 0) {
+        parts.push(format_duration(desc.duration));
+    }
+    if desc.view_count.is_some() {
+        parts.push(format!("{} views", format_views(desc.view_count)));
+    }
+    if desc.like_count.is_some() {
+        parts.push(format!("{} likes", format_views(desc.like_count)));
+    }
+    parts.join(" · ")
+}
+
+fn description_modal_area(area: Rect) -> Rect {
     let width = 80.min(area.width.saturating_sub(6));
     let height = (area.height * 2 / 3).max(10);
     let x = (area.width.saturating_sub(width)) / 2;
     let y = (area.height.saturating_sub(height)) / 2;
-    let modal_area = Rect::new(x, y, width, height);
+    Rect::new(x, y, width, height)
+}
+
+fn draw_description_panel(f: &mut Frame, app: &App) {
+    let modal_area = description_modal_area(f.area());
+    let width = modal_area.width;
 
     f.render_widget(Clear, modal_area);
 
@@ -1004,18 +1414,96 @@ fn draw_description_panel(f: &mut Frame, app: &App) {
 
     if let Some(ref desc) = app.description {
         let inner_width = width.saturating_sub(4) as usize;
+        let targets = app.info_targets();
+        let mut selected_line = None;
         let mut lines = vec![
             Line::from(Span::styled(
-                &desc.title,
+                truncate_str(&desc.title, inner_width),
                 Style::default().fg(CYAN).add_modifier(Modifier::BOLD),
             )),
             Line::from(Span::styled(
                 &desc.channel_name,
                 Style::default().fg(YELLOW),
             )),
-            Line::from(""),
         ];
 
+        let meta = description_meta(desc);
+        if !meta.is_empty() {
+            lines.push(Line::from(Span::styled(meta, Style::default().fg(GRAY))));
+        }
+        for (label, values) in [("Category", &desc.categories), ("Tags", &desc.tags)] {
+            if !values.is_empty() {
+                let text = truncate_str(&values.join(", "), inner_width.saturating_sub(label.len() + 2));
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}: ", label), Style::default().fg(DIM_FG)),
+                    Span::styled(text, Style::default().fg(MAGENTA)),
+                ]));
+            }
+        }
+        lines.push(Line::from(""));
+
+        let chapter_count = targets
+            .iter()
+            .filter(|t| matches!(t, InfoTarget::Chapter { .. }))
+            .count();
+        let mut push_targets = |lines: &mut Vec<Line>, range: std::ops::Range<usize>| {
+            let mut section = "";
+            for i in range {
+                let target = &targets[i];
+                let heading = match target {
+                    InfoTarget::Chapter { .. } => "Chapters",
+                    InfoTarget::Timestamp { .. } => "Timestamps",
+                    InfoTarget::Link(_) => "Links",
+                };
+                if heading != section {
+                    if !section.is_empty() {
+                        lines.push(Line::from(""));
+                    }
+                    lines.push(Line::from(Span::styled(
+                        heading,
+                        Style::default().fg(GRAY).add_modifier(Modifier::BOLD),
+                    )));
+                    section = heading;
+                }
+
+                let is_selected = i == app.info_selected;
+                if is_selected {
+                    selected_line = Some(lines.len());
+                }
+                let pointer = if is_selected { "▶ " } else { "  " };
+                let accent = if is_selected { CYAN } else { YELLOW };
+                let text = if is_selected { CYAN } else { LIGHT_GRAY };
+                let mut spans = vec![Span::styled(pointer, Style::default().fg(CYAN))];
+                match target {
+                    InfoTarget::Chapter { start, title: label }
+                    | InfoTarget::Timestamp { start, label } => {
+                        let timestamp = chapter_timestamp(*start as f64);
+                        let label_width = inner_width.saturating_sub(timestamp.len() + 4);
+                        spans.push(Span::styled(
+                            format!("{}  ", timestamp),
+                            Style::default().fg(accent),
+                        ));
+                        spans.push(Span::styled(
+                            truncate_str(label, label_width),
+                            Style::default().fg(text),
+                        ));
+                    }
+                    InfoTarget::Link(url) => {
+                        spans.push(Span::styled(
+                            truncate_str(url, inner_width.saturating_sub(2)),
+                            Style::default().fg(if is_selected { CYAN } else { ACCENT }),
+                        ));
+                    }
+                }
+                lines.push(Line::from(spans));
+            }
+        };
+
+        if chapter_count > 0 {
+            push_targets(&mut lines, 0..chapter_count);
+            lines.push(Line::from(""));
+        }
+
         // Word-wrap description
         let desc_text = if desc.description.len() > 500 {
             format!("{}...", &desc.description[..500])
@@ -1036,20 +1524,231 @@ fn draw_description_panel(f: &mut Frame, app: &App) {
             }
         }
 
+        if targets.len() > chapter_count {
+            lines.push(Line::from(""));
+            push_targets(&mut lines, chapter_count..targets.len());
+        }
+
         lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "Press (i) to close",
-            Style::default().fg(GRAY),
-        )));
+        let hint = if targets.is_empty() {
+            "Press (i) to close"
+        } else {
+            "(j/k) select  (Enter) play or open  (i) close"
+        };
+        lines.push(Line::from(Span::styled(hint, Style::default().fg(GRAY))));
+
+        // Leave room at the top for the thumbnail when previews are on
+        let inner = block.inner(modal_area);
+        f.render_widget(block, modal_area);
+        let text_area = if thumbnail_slot(app, f.area()).is_some() {
+            Layout::vertical([Constraint::Length(thumbnail::ROWS + 1), Constraint::Min(0)])
+                .split(inner)[1]
+        } else {
+            inner
+        };
+
+        // Keep the cursor in view when the targets run past the bottom of the panel
+        let visible = text_area.height as usize;
+        let scroll = selected_line
+            .map(|l| (l + 2).saturating_sub(visible))
+            .unwrap_or(0);
 
-        let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: false });
-        f.render_widget(paragraph, modal_area);
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll as u16, 0));
+        f.render_widget(paragraph, text_area);
     } else {
         let content = Paragraph::new(Line::from("")).block(block);
         f.render_widget(content, modal_area);
     }
 }
 
+// ── Comments Panel (Modal) ─────────────────────────────────
+
+/// "@author · 1.2K likes · 3d ago", with the uploader marked.
+fn comment_header(comment: &Comment) -> String {
+    let mut parts = vec![if comment.is_uploader {
+        format!("{} (uploader)", comment.author)
+    } else {
+        comment.author.clone()
+    }];
+    if comment.like_count.is_some_and(|l| l > 0) {
+        parts.push(format!("{} likes", format_views(comment.like_count)));
+    }
+    if let Some(date) = comment.timestamp.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)) {
+        parts.push(get_relative_date(date));
+    }
+    parts.join(" · ")
+}
+
+fn draw_comments_panel(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let width = 80.min(area.width.saturating_sub(6));
+    let height = (area.height * 2 / 3).max(10);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let modal_area = Rect::new(x, y, width, height);
+
+    f.render_widget(Clear, modal_area);
+
+    let count = app.comments.as_ref().map(|c| c.len()).unwrap_or(0);
+    let title = if count > 0 {
+        format!(" Comments ({}/{}) ", app.comments_scroll + 1, count)
+    } else {
+        " Comments ".to_string()
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ACCENT))
+        .title(Span::styled(
+            title,
+            Style::default().fg(CYAN).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(BODY_BG));
+
+    if app.loading_comments {
+        let content = Paragraph::new(Line::from(Span::styled(
+            "Loading comments...",
+            Style::default().fg(GRAY),
+        )))
+        .block(block);
+        f.render_widget(content, modal_area);
+        return;
+    }
+
+    let comments = match app.comments {
+        Some(ref c) if !c.is_empty() => c,
+        _ => {
+            let content = Paragraph::new(Line::from(Span::styled(
+                "No comments.",
+                Style::default().fg(GRAY),
+            )))
+            .block(block);
+            f.render_widget(content, modal_area);
+            return;
+        }
+    };
+
+    let inner_width = width.saturating_sub(4) as usize;
+    let mut lines = Vec::new();
+    for comment in comments.iter().skip(app.comments_scroll) {
+        let indent = if comment.is_reply { "    " } else { "" };
+        let text_width = inner_width.saturating_sub(indent.len());
+        let header_style = if comment.is_uploader {
+            Style::default().fg(GREEN).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(YELLOW)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                if comment.is_reply { "  ↳ " } else { "" },
+                Style::default().fg(DIM_FG),
+            ),
+            Span::styled(truncate_str(&comment_header(comment), text_width), header_style),
+        ]));
+        for line in comment.text.lines() {
+            for wrapped in word_wrap(line, text_width) {
+                lines.push(Line::from(Span::styled(
+                    format!("{}{}", indent, wrapped),
+                    Style::default().fg(LIGHT_GRAY),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+
+    let paragraph = Paragraph::new(lines).block(block);
+    f.render_widget(paragraph, modal_area);
+}
+
+// ── Transcript Panel (Modal) ───────────────────────────────
+
+fn draw_transcript_panel(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let width = 80.min(area.width.saturating_sub(6));
+    let height = (area.height * 2 / 3).max(10);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let modal_area = Rect::new(x, y, width, height);
+
+    f.render_widget(Clear, modal_area);
+
+    let title = match app.transcript {
+        Some(ref t) => format!(
+            " Transcript ({}{}) ",
+            t.language,
+            if t.automatic { ", auto" } else { "" }
+        ),
+        None => " Transcript ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(ACCENT))
+        .title(Span::styled(
+            title,
+            Style::default().fg(CYAN).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(BODY_BG));
+
+    let lines = match (app.loading_transcript, app.transcript.as_ref()) {
+        (false, Some(t)) if !t.lines.is_empty() => &t.lines,
+        (loading, _) => {
+            let message = if loading { "Loading transcript..." } else { "No transcript." };
+            let content = Paragraph::new(Line::from(Span::styled(
+                message,
+                Style::default().fg(GRAY),
+            )))
+            .block(block);
+            f.render_widget(content, modal_area);
+            return;
+        }
+    };
+
+    // Keep the selected line roughly centred
+    let visible = height.saturating_sub(2) as usize;
+    let scroll = app
+        .transcript_selected
+        .saturating_sub(visible / 2)
+        .min(lines.len().saturating_sub(visible));
+
+    let inner_width = width.saturating_sub(4) as usize;
+    let query = app.transcript_query.trim().to_lowercase();
+    let rows: Vec<Line> = lines
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, line)| {
+            let is_selected = i == app.transcript_selected;
+            let is_match = !query.is_empty() && line.text.to_lowercase().contains(&query);
+            let timestamp = chapter_timestamp(line.start);
+            let text_width = inner_width.saturating_sub(11);
+            let text_color = if is_selected {
+                CYAN
+            } else if is_match {
+                YELLOW
+            } else {
+                LIGHT_GRAY
+            };
+            Line::from(vec![
+                Span::styled(
+                    if is_selected { "▶ " } else { "  " },
+                    Style::default().fg(CYAN),
+                ),
+                Span::styled(
+                    format!("{:>7}  ", timestamp),
+                    Style::default().fg(if is_selected { CYAN } else { DIM_FG }),
+                ),
+                Span::styled(truncate_str(&line.text, text_width), Style::default().fg(text_color)),
+            ])
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(rows).block(block);
+    f.render_widget(paragraph, modal_area);
+}
+
 // ── Dim Background ─────────────────────────────────────────
 
 fn dim_background(f: &mut Frame) {
@@ -1067,6 +1766,15 @@ fn dim_background(f: &mut Frame) {
 
 // ── Utility Functions ──────────────────────────────────────
 
+fn chapter_timestamp(seconds: f64) -> String {
+    let s = seconds.max(0.0) as i64;
+    if s >= 3600 {
+        format!("{}:{:02}:{:02}", s / 3600, (s % 3600) / 60, s % 60)
+    } else {
+        format!("{}:{:02}", s / 60, s % 60)
+    }
+}
+
 fn truncate_str(s: &str, max_len: usize) -> String {
     let display_width = UnicodeWidthStr::width(s);
     if display_width <= max_len {
@@ -1147,6 +1855,139 @@ fn word_wrap(text: &str, max_width: usize) -> Vec<String> {
 mod tests {
     use super::*;
 
+    // ── live_badge tests ─────────────────────────────────────
+
+    fn stream(status: Option<LiveStatus>, start: Option<chrono::DateTime<chrono::Utc>>) -> Video {
+        Video {
+            id: "v1".to_string(),
+            title: "Stream".to_string(),
+            url: String::new(),
+            is_short: false,
+            channel_name: None,
+            channel_id: None,
+            published_date: start,
+            stored_at: None,
+            relative_date: String::new(),
+            duration: None,
+            duration_string: None,
+            view_count: None,
+            live_status: status,
+            thumbnail: None,
+        }
+    }
+
+    #[test]
+    fn test_live_badge() {
+        assert_eq!(live_badge(&stream(Some(LiveStatus::Live), None)), Some(("● LIVE".to_string(), RED)));
+        assert_eq!(
+            live_badge(&stream(Some(LiveStatus::Upcoming), None)),
+            Some(("UPCOMING".to_string(), CYAN))
+        );
+        assert!(live_badge(&stream(Some(LiveStatus::WasLive), None)).is_none());
+        assert!(live_badge(&stream(None, None)).is_none());
+    }
+
+    #[test]
+    fn test_scheduled_label() {
+        let now = chrono::Local::now();
+        let in_two_hours = (now + chrono::Duration::hours(2)).with_timezone(&chrono::Utc);
+        let label = scheduled_label(in_two_hours, now);
+        assert!(label.starts_with("Today ") || label.len() == 9, "{}", label);
+
+        let next_month = (now + chrono::Duration::days(40)).with_timezone(&chrono::Utc);
+        assert_eq!(
+            scheduled_label(next_month, now),
+            next_month.with_timezone(&chrono::Local).format("%b %-d %H:%M").to_string()
+        );
+    }
+
+    // ── subscription_label tests ─────────────────────────────
+
+    #[test]
+    fn test_subscription_label_marks_playlists() {
+        let mut sub = crate::db::Subscription {
+            id: "UC1".to_string(),
+            name: "Talks".to_string(),
+            url: String::new(),
+            added_at: None,
+            kind: crate::db::SourceKind::Channel,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
+        };
+        assert_eq!(subscription_label(&sub), "Talks");
+        sub.kind = crate::db::SourceKind::Playlist;
+        assert_eq!(subscription_label(&sub), "≡ Talks");
+        sub.notify = true;
+        assert_eq!(subscription_label(&sub), "≡ Talks 🔔");
+        sub.kind = crate::db::SourceKind::Channel;
+        sub.members = true;
+        assert_eq!(subscription_label(&sub), "Talks 🔔 💎");
+    }
+
+    // ── description_meta tests ───────────────────────────────
+
+    #[test]
+    fn test_description_meta() {
+        let desc = VideoDescription {
+            title: String::new(),
+            description: String::new(),
+            channel_name: String::new(),
+            upload_date: Some("2024-01-15".to_string()),
+            duration: Some(754),
+            view_count: Some(1_200_000),
+            like_count: Some(4500),
+            tags: Vec::new(),
+            categories: Vec::new(),
+            chapters: Vec::new(),
+            links: Vec::new(),
+        };
+        assert_eq!(
+            description_meta(&desc),
+            "2024-01-15 · 12:34 · 1.2M views · 4K likes"
+        );
+
+        let sparse = VideoDescription {
+            upload_date: None,
+            like_count: None,
+            ..desc
+        };
+        assert_eq!(description_meta(&sparse), "12:34 · 1.2M views");
+    }
+
+    // ── comment_header tests ─────────────────────────────────
+
+    #[test]
+    fn test_comment_header() {
+        let comment = Comment {
+            id: "a".to_string(),
+            author: "@alice".to_string(),
+            text: String::new(),
+            like_count: Some(1500),
+            timestamp: None,
+            is_reply: false,
+            is_uploader: false,
+        };
+        assert_eq!(comment_header(&comment), "@alice · 1K likes");
+
+        let uploader = Comment {
+            like_count: Some(0),
+            is_uploader: true,
+            ..comment
+        };
+        assert_eq!(comment_header(&uploader), "@alice (uploader)");
+    }
+
+    // ── chapter_timestamp tests ──────────────────────────────
+
+    #[test]
+    fn test_chapter_timestamp() {
+        assert_eq!(chapter_timestamp(0.0), "0:00");
+        assert_eq!(chapter_timestamp(95.7), "1:35");
+        assert_eq!(chapter_timestamp(3725.0), "1:02:05");
+    }
+
     // ── truncate_str tests ───────────────────────────────────
 
     #[test]
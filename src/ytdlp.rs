@@ -3,17 +3,100 @@ use std::time::Duration;
 
 use chrono::{DateTime, NaiveDate, Utc};
 use regex::Regex;
-use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::AsyncBufReadExt;
 use tokio::process::Command;
-use tokio::time::timeout;
 
-use crate::db::{decode_xml_entities, format_duration, get_relative_date, Video};
+use crate::db::{
+    decode_xml_entities, format_duration, get_relative_date, Chapter, Comment, LiveStatus,
+    SourceKind, Subscription, Video, VideoDescription,
+};
+use crate::{doctor, runner};
 
 #[derive(Debug, Clone)]
 pub struct ChannelInfo {
     pub id: String,
     pub name: String,
     pub url: String,
+    pub kind: SourceKind,
+}
+
+impl ChannelInfo {
+    pub fn to_subscription(&self) -> Subscription {
+        Subscription {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            url: self.url.clone(),
+            added_at: None,
+            kind: self.kind,
+            notify: false,
+            display_name: None,
+            archived: false,
+            members: false,
+        }
+    }
+}
+
+/// A tab on a channel page, listed through yt-dlp's `--flat-playlist`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelTab {
+    Videos,
+    Shorts,
+    Live,
+    Podcasts,
+    Playlists,
+}
+
+impl ChannelTab {
+    pub const ALL: [ChannelTab; 5] = [
+        ChannelTab::Videos,
+        ChannelTab::Shorts,
+        ChannelTab::Live,
+        ChannelTab::Podcasts,
+        ChannelTab::Playlists,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChannelTab::Videos => "Videos",
+            ChannelTab::Shorts => "Shorts",
+            ChannelTab::Live => "Live",
+            ChannelTab::Podcasts => "Podcasts",
+            ChannelTab::Playlists => "Playlists",
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            ChannelTab::Videos => "videos",
+            ChannelTab::Shorts => "shorts",
+            ChannelTab::Live => "streams",
+            ChannelTab::Podcasts => "podcasts",
+            ChannelTab::Playlists => "playlists",
+        }
+    }
+
+    /// Podcasts and Playlists tabs list playlists rather than videos.
+    pub fn lists_playlists(self) -> bool {
+        matches!(self, ChannelTab::Podcasts | ChannelTab::Playlists)
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&t| t == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        let i = Self::ALL.iter().position(|&t| t == self).unwrap_or(0);
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistEntry {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub video_count: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -23,6 +106,8 @@ pub struct PrimeResult {
     pub skipped: usize,
     pub failed: usize,
     pub videos: Vec<Video>,
+    /// Why the members-only videos couldn't be listed, when they were asked for.
+    pub members_error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -44,12 +129,53 @@ pub fn is_valid_youtube_url(url: &str) -> bool {
         .any(|p| Regex::new(p).unwrap().is_match(url))
 }
 
+/// The list id of a playlist page URL (`/playlist?list=...`). Watch URLs that
+/// carry a `list=` parameter point at a video and are not treated as playlists.
+pub fn playlist_id(url: &str) -> Option<String> {
+    Regex::new(r"^https?://(?:(?:www|m|music)\.)?youtube\.com/playlist\?(?:[^#]*&)?list=([\w\-]+)")
+        .unwrap()
+        .captures(url.trim())
+        .map(|c| c[1].to_string())
+}
+
 pub fn is_valid_video_id(id: &str) -> bool {
     Regex::new(r"^[a-zA-Z0-9_\-]{11}$")
         .unwrap()
         .is_match(id)
 }
 
+/// A YouTube link that feeding-tube can handle itself rather than in a browser.
+#[derive(Debug, Clone, PartialEq)]
+pub enum YoutubeLink {
+    Video { id: String, start: Option<u64> },
+    Channel(String),
+}
+
+pub fn classify_youtube_link(url: &str) -> Option<YoutubeLink> {
+    let video = Regex::new(
+        r"^https?://(?:(?:www|m)\.)?(?:youtube\.com/(?:watch\?(?:[^#]*&)?v=|shorts/|live/)|youtu\.be/)([a-zA-Z0-9_\-]{11})",
+    )
+    .unwrap();
+    if let Some(caps) = video.captures(url) {
+        let start = Regex::new(r"[?&#]t=(\d+)s?(?:$|[&#])")
+            .unwrap()
+            .captures(url)
+            .and_then(|c| c[1].parse().ok());
+        return Some(YoutubeLink::Video {
+            id: caps[1].to_string(),
+            start,
+        });
+    }
+
+    let channel = Regex::new(
+        r"^https?://(?:(?:www|m)\.)?youtube\.com/(?:@[\w.\-]+|channel/UC[\w\-]+|c/[\w.\-]+|user/[\w.\-]+)",
+    )
+    .unwrap();
+    channel
+        .find(url)
+        .map(|m| YoutubeLink::Channel(m.as_str().to_string()))
+}
+
 pub fn sanitize_search_query(query: &str) -> String {
     let trimmed = query.trim();
     if trimmed.len() > 500 {
@@ -65,7 +191,12 @@ fn validate_url(url: &str) -> bool {
 
 // ── Channel Info ───────────────────────────────────────────
 
+/// Resolve a channel or playlist URL to the source it should subscribe to.
 pub async fn get_channel_info(url: &str) -> Result<ChannelInfo, String> {
+    get_channel_info_with(runner::global(), url).await
+}
+
+async fn get_channel_info_with(runner: &runner::Runner, url: &str) -> Result<ChannelInfo, String> {
     let channel_url = url.trim();
     if !validate_url(channel_url) {
         return Err("Invalid URL format".to_string());
@@ -73,25 +204,29 @@ pub async fn get_channel_info(url: &str) -> Result<ChannelInfo, String> {
     if !is_valid_youtube_url(channel_url) {
         return Err("Not a valid YouTube URL".to_string());
     }
+    if let Some(list_id) = playlist_id(channel_url) {
+        return get_playlist_info(runner, &list_id).await;
+    }
 
     let is_video_url =
         channel_url.contains("/watch?") || channel_url.contains("youtu.be/");
 
-    let output = Command::new("yt-dlp")
-        .args([
-            "--dump-json",
-            "--playlist-items",
-            "1",
-            "--no-warnings",
-            channel_url,
-        ])
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run yt-dlp: {e}"))?;
+    let output = runner
+        .output(
+            &[
+                "--dump-json",
+                "--playlist-items",
+                "1",
+                "--no-warnings",
+                channel_url,
+            ],
+            Duration::from_secs(60),
+        )
+        .await?;
 
-    if !output.status.success() {
+    if !output.success {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("yt-dlp failed: {}", stderr.trim()));
+        return Err(format!("yt-dlp failed: {}", doctor::explain(&stderr)));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -118,7 +253,58 @@ pub async fn get_channel_info(url: &str) -> Result<ChannelInfo, String> {
             }
         });
 
-    Ok(ChannelInfo { id, name, url })
+    Ok(ChannelInfo {
+        id,
+        name,
+        url,
+        kind: SourceKind::Channel,
+    })
+}
+
+async fn get_playlist_info(runner: &runner::Runner, list_id: &str) -> Result<ChannelInfo, String> {
+    let url = format!("https://www.youtube.com/playlist?list={}", list_id);
+    let output = runner
+        .output(
+            &[
+                "--flat-playlist",
+                "--dump-single-json",
+                "--playlist-items",
+                "0",
+                "--no-warnings",
+                &url,
+            ],
+            Duration::from_secs(60),
+        )
+        .await?;
+
+    if !output.success {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("yt-dlp failed: {}", doctor::explain(&stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let data: serde_json::Value =
+        serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse JSON: {e}"))?;
+    parse_playlist_info(&data, list_id, &url)
+}
+
+fn parse_playlist_info(
+    data: &serde_json::Value,
+    list_id: &str,
+    url: &str,
+) -> Result<ChannelInfo, String> {
+    let id = data["id"].as_str().unwrap_or(list_id).to_string();
+    let name = data["title"]
+        .as_str()
+        .filter(|t| !t.is_empty())
+        .ok_or("No playlist title found")?
+        .to_string();
+    Ok(ChannelInfo {
+        id,
+        name,
+        url: url.to_string(),
+        kind: SourceKind::Playlist,
+    })
 }
 
 // ── RSS Feed ───────────────────────────────────────────────
@@ -147,22 +333,47 @@ fn parse_rss_entry(
         .map(|d| get_relative_date(d))
         .unwrap_or_default();
 
+    // Playlist feeds list other channels' uploads, so keep the uploader's
+    // own channel over the feed's
+    let uploader_id = extract_xml_tag(entry, "yt:channelId").unwrap_or_else(|| channel_id.to_string());
+    let uploader = extract_xml_tag(entry, "name")
+        .map(|n| decode_xml_entities(&n))
+        .unwrap_or_else(|| channel_name.to_string());
+
     Some(Video {
         id: video_id,
         title: decode_xml_entities(&title),
         url,
         is_short,
-        channel_name: Some(channel_name.to_string()),
-        channel_id: Some(channel_id.to_string()),
+        channel_name: Some(uploader),
+        channel_id: Some(uploader_id),
         published_date,
         stored_at: None,
         relative_date,
         duration: None,
         duration_string: Some("--:--".to_string()),
         view_count: None,
+        live_status: None,
+        thumbnail: extract_xml_attr(entry, "media:thumbnail", "url"),
     })
 }
 
+/// The thumbnail to show for a yt-dlp entry: the largest JPEG up to
+/// `THUMBNAIL_WIDTH` pixels wide, since only JPEGs can be decoded.
+fn pick_thumbnail(data: &serde_json::Value) -> Option<String> {
+    const THUMBNAIL_WIDTH: u64 = 480;
+    let is_jpeg = |url: &str| url.split('?').next().is_some_and(|path| path.ends_with(".jpg"));
+    let listed = data["thumbnails"].as_array().into_iter().flatten().filter_map(|t| {
+        let url = t["url"].as_str().filter(|u| is_jpeg(u))?;
+        let width = t["width"].as_u64().unwrap_or(0);
+        (width <= THUMBNAIL_WIDTH).then(|| (width, url.to_string()))
+    });
+    listed
+        .max_by_key(|(width, _)| *width)
+        .map(|(_, url)| url)
+        .or_else(|| data["thumbnail"].as_str().filter(|u| is_jpeg(u)).map(str::to_string))
+}
+
 fn extract_xml_tag(xml: &str, tag: &str) -> Option<String> {
     let open = format!("<{}>", tag);
     let close = format!("</{}>", tag);
@@ -206,69 +417,104 @@ fn parse_rss_feed(
     entries
 }
 
-async fn fetch_channel_rss(
+fn feed_url(kind: SourceKind, id: &str) -> String {
+    match kind {
+        SourceKind::Channel => format!("https://www.youtube.com/feeds/videos.xml?channel_id={}", id),
+        SourceKind::Playlist => format!("https://www.youtube.com/feeds/videos.xml?playlist_id={}", id),
+    }
+}
+
+/// One subscription's feed. Unlike `fetch_channel_rss`, a failed request or a
+/// response that isn't a feed is reported instead of treated as empty.
+async fn fetch_channel_feed(
+    kind: SourceKind,
     channel_id: &str,
     channel_name: &str,
-) -> Vec<Video> {
-    let rss_url = format!(
-        "https://www.youtube.com/feeds/videos.xml?channel_id={}",
-        channel_id
-    );
+) -> Result<Vec<Video>, String> {
+    let rss_url = feed_url(kind, channel_id);
 
-    match Command::new("curl")
-        .args(["-s", &rss_url])
+    let output = Command::new("curl")
+        .args(["-s", "-f", &rss_url])
         .output()
         .await
-    {
-        Ok(output) if output.status.success() => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            parse_rss_feed(&stdout, channel_id, channel_name)
-        }
-        _ => Vec::new(),
+        .map_err(|e| format!("Failed to run curl: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Feed request failed (curl exit {})",
+            output.status.code().unwrap_or(-1)
+        ));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.contains("<feed") {
+        return Err("Response is not a feed".to_string());
     }
+    Ok(parse_rss_feed(&stdout, channel_id, channel_name))
 }
 
-pub async fn fetch_all_channels_rss(
-    subscriptions: &[(String, String)],
+async fn fetch_channel_rss(
+    kind: SourceKind,
+    channel_id: &str,
+    channel_name: &str,
 ) -> Vec<Video> {
-    if subscriptions.is_empty() {
-        return Vec::new();
-    }
+    fetch_channel_feed(kind, channel_id, channel_name)
+        .await
+        .unwrap_or_default()
+}
 
-    let mut all_videos = Vec::new();
-    let batch_size = 20;
+/// Every subscription's feed, keyed by subscription ID, with failures kept.
+pub async fn fetch_all_channels_feeds(
+    subscriptions: &[Subscription],
+) -> Vec<(String, Result<Vec<Video>, String>)> {
+    let mut results = Vec::new();
+    let batch_size = runner::global().config().feed_concurrency;
 
     for chunk in subscriptions.chunks(batch_size) {
         let mut handles = Vec::new();
-        for (id, name) in chunk {
-            let id = id.clone();
-            let name = name.clone();
-            handles.push(tokio::spawn(async move {
-                fetch_channel_rss(&id, &name).await
-            }));
+        for sub in chunk {
+            let kind = sub.kind;
+            let id = sub.id.clone();
+            let name = sub.name.clone();
+            handles.push((
+                sub.id.clone(),
+                tokio::spawn(async move { fetch_channel_feed(kind, &id, &name).await }),
+            ));
         }
 
-        for handle in handles {
-            if let Ok(videos) = handle.await {
-                all_videos.extend(videos);
-            }
+        for (id, handle) in handles {
+            results.push((id, joined_feed(handle.await)));
         }
     }
 
-    all_videos
+    results
 }
 
-// ── Channel Videos ─────────────────────────────────────────
+/// A feed task's outcome; one that panicked counts as a failed fetch so the
+/// subscription still shows up in the report.
+fn joined_feed(
+    joined: Result<Result<Vec<Video>, String>, tokio::task::JoinError>,
+) -> Result<Vec<Video>, String> {
+    joined.unwrap_or_else(|e| Err(format!("Feed fetch crashed: {e}")))
+}
 
-pub async fn get_channel_videos(
-    channel_id: &str,
-    channel_name: &str,
+pub async fn fetch_all_channels_rss(
+    subscriptions: &[Subscription],
 ) -> Vec<Video> {
-    fetch_channel_rss(channel_id, channel_name).await
+    fetch_all_channels_feeds(subscriptions)
+        .await
+        .into_iter()
+        .filter_map(|(_, result)| result.ok())
+        .flatten()
+        .collect()
+}
+
+// ── Channel Videos ─────────────────────────────────────────
+
+pub async fn get_channel_videos(channel: &Subscription) -> Vec<Video> {
+    fetch_channel_rss(channel.kind, &channel.id, &channel.name).await
 }
 
 pub async fn refresh_all_videos(
-    subscriptions: &[(String, String)],
+    subscriptions: &[Subscription],
 ) -> Vec<Video> {
     fetch_all_channels_rss(subscriptions).await
 }
@@ -278,6 +524,14 @@ pub async fn refresh_all_videos(
 pub async fn search_youtube(
     query: &str,
     limit: usize,
+) -> Result<Vec<Video>, String> {
+    search_youtube_with(runner::global(), query, limit).await
+}
+
+async fn search_youtube_with(
+    runner: &runner::Runner,
+    query: &str,
+    limit: usize,
 ) -> Result<Vec<Video>, String> {
     let sanitized = sanitize_search_query(query);
     if sanitized.is_empty() {
@@ -287,24 +541,17 @@ pub async fn search_youtube(
 
     let search_arg = format!("ytsearch{}:{}", safe_limit, sanitized);
 
-    let result = timeout(
-        Duration::from_secs(30),
-        Command::new("yt-dlp")
-            .args([
-                &search_arg,
-                "--flat-playlist",
-                "--dump-json",
-                "--no-warnings",
-            ])
-            .output(),
-    )
-    .await
-    .map_err(|_| "Search timed out".to_string())?
-    .map_err(|e| format!("Search failed: {e}"))?;
+    let result = runner
+        .output(
+            &[&search_arg, "--flat-playlist", "--dump-json", "--no-warnings"],
+            Duration::from_secs(30),
+        )
+        .await
+        .map_err(|e| format!("Search failed: {e}"))?;
 
-    if !result.status.success() {
+    if !result.success {
         let stderr = String::from_utf8_lossy(&result.stderr);
-        return Err(format!("Search failed: {}", stderr.trim()));
+        return Err(format!("Search failed: {}", doctor::explain(&stderr)));
     }
 
     let stdout = String::from_utf8_lossy(&result.stdout);
@@ -355,6 +602,8 @@ pub async fn search_youtube(
                 duration,
                 duration_string: Some(duration_string),
                 view_count: data["view_count"].as_u64(),
+                live_status: data["live_status"].as_str().and_then(LiveStatus::parse),
+                thumbnail: pick_thumbnail(&data),
             });
         }
     }
@@ -364,78 +613,271 @@ pub async fn search_youtube(
 
 // ── Video Description ──────────────────────────────────────
 
-pub struct VideoDescription {
-    pub title: String,
-    pub description: String,
-    pub channel_name: String,
+/// Chapters from a `--dump-json` object, ordered by start time. Videos without
+/// chapters have `"chapters": null`, which yields an empty list.
+fn parse_chapters(data: &serde_json::Value) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = data["chapters"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let start = item["start_time"].as_f64()?;
+                    Some(Chapter {
+                        start,
+                        end: item["end_time"].as_f64().unwrap_or(start),
+                        title: item["title"].as_str().unwrap_or("").trim().to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    chapters.sort_by(|a, b| a.start.total_cmp(&b.start));
+    chapters
+}
+
+/// Distinct http(s) links in a description, in order of appearance.
+fn extract_links(text: &str) -> Vec<String> {
+    let re = Regex::new(r#"https?://[^\s<>"']+"#).unwrap();
+    let mut seen = HashSet::new();
+    re.find_iter(text)
+        .map(|m| {
+            m.as_str()
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']'])
+                .to_string()
+        })
+        .filter(|link| seen.insert(link.clone()))
+        .collect()
+}
+
+/// `m:ss` / `h:mm:ss` timestamps in a description, each with the rest of its line
+/// as a label. Repeats of the same offset are dropped.
+pub fn extract_timestamps(text: &str) -> Vec<(u64, String)> {
+    let re = Regex::new(r"\b(?:(\d{1,2}):)?(\d{1,2}):(\d{2})\b").unwrap();
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+
+    for line in text.lines() {
+        if line.contains("://") {
+            continue;
+        }
+        for caps in re.captures_iter(line) {
+            let hours: u64 = caps.get(1).map_or(0, |h| h.as_str().parse().unwrap_or(0));
+            let minutes: u64 = caps[2].parse().unwrap_or(0);
+            let seconds: u64 = caps[3].parse().unwrap_or(0);
+            if seconds >= 60 || (caps.get(1).is_some() && minutes >= 60) {
+                continue;
+            }
+            let offset = hours * 3600 + minutes * 60 + seconds;
+            if !seen.insert(offset) {
+                continue;
+            }
+            let label = line
+                .replace(&caps[0], "")
+                .trim_matches(|c: char| c.is_whitespace() || "-–—|:()[]".contains(c))
+                .to_string();
+            found.push((offset, label));
+        }
+    }
+    found
+}
+
+fn string_list(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_video_description(data: &serde_json::Value) -> VideoDescription {
+    let description = data["description"]
+        .as_str()
+        .unwrap_or("No description available.")
+        .to_string();
+    VideoDescription {
+        title: data["title"].as_str().unwrap_or("").to_string(),
+        links: extract_links(&description),
+        description,
+        channel_name: data["channel"]
+            .as_str()
+            .or(data["uploader"].as_str())
+            .unwrap_or("Unknown")
+            .to_string(),
+        upload_date: data["upload_date"]
+            .as_str()
+            .and_then(parse_date_yyyymmdd)
+            .map(|d| d.format("%Y-%m-%d").to_string()),
+        duration: data["duration"]
+            .as_i64()
+            .or(data["duration"].as_f64().map(|f| f as i64)),
+        view_count: data["view_count"].as_u64(),
+        like_count: data["like_count"].as_u64(),
+        tags: string_list(&data["tags"]),
+        categories: string_list(&data["categories"]),
+        chapters: parse_chapters(data),
+    }
 }
 
 pub async fn get_video_description(
     video_id: &str,
 ) -> Result<VideoDescription, String> {
+    let data = get_video_json(video_id).await?;
+    Ok(parse_video_description(&data))
+}
+
+/// Full `--dump-json` metadata for a single video.
+pub async fn get_video_json(video_id: &str) -> Result<serde_json::Value, String> {
     if !is_valid_video_id(video_id) {
         return Err("Invalid video ID format".to_string());
     }
 
     let url = format!("https://www.youtube.com/watch?v={}", video_id);
 
-    let result = timeout(
-        Duration::from_secs(15),
-        Command::new("yt-dlp")
-            .args([
+    let result = runner::global()
+        .output(
+            &[
                 "--dump-json",
                 "--no-warnings",
                 "--extractor-args",
                 "youtube:skip=dash,hls",
                 &url,
-            ])
-            .output(),
-    )
-    .await
-    .map_err(|_| "Request timed out".to_string())?
-    .map_err(|e| format!("Failed to get description: {e}"))?;
+            ],
+            Duration::from_secs(15),
+        )
+        .await
+        .map_err(|e| format!("Failed to get video info: {e}"))?;
+
+    if !result.success {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(format!("Failed: {}", doctor::explain(&stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    serde_json::from_str(&stdout).map_err(|e| format!("Parse error: {e}"))
+}
+
+/// Duration, view count and live state for a stored video, which RSS lacks.
+pub async fn get_video_metadata(video: &Video) -> Result<Video, String> {
+    let data = get_video_json(&video.id).await?;
+    let channel_id = video.channel_id.as_deref().unwrap_or_default();
+    let channel_name = video.channel_name.as_deref().unwrap_or_default();
+    parse_flat_playlist_video(&data, channel_id, channel_name)
+        .ok_or_else(|| "Missing video metadata".to_string())
+}
+
+// ── Comments ───────────────────────────────────────────────
+
+/// Top comments for a video, at most `limit` including replies, in thread order.
+pub async fn get_video_comments(video_id: &str, limit: u32) -> Result<Vec<Comment>, String> {
+    if !is_valid_video_id(video_id) {
+        return Err("Invalid video ID format".to_string());
+    }
+
+    let url = format!("https://www.youtube.com/watch?v={}", video_id);
+    let extractor_args = format!(
+        "youtube:skip=dash,hls;comment_sort=top;max_comments={},all,all,10",
+        limit
+    );
+
+    let result = runner::global()
+        .output(
+            &[
+                "--skip-download",
+                "--write-comments",
+                "--dump-json",
+                "--no-warnings",
+                "--extractor-args",
+                &extractor_args,
+                &url,
+            ],
+            Duration::from_secs(60),
+        )
+        .await
+        .map_err(|e| format!("Failed to get comments: {e}"))?;
 
-    if !result.status.success() {
+    if !result.success {
         let stderr = String::from_utf8_lossy(&result.stderr);
-        return Err(format!("Failed: {}", stderr.trim()));
+        return Err(format!("Failed: {}", doctor::explain(&stderr)));
     }
 
     let stdout = String::from_utf8_lossy(&result.stdout);
     let data: serde_json::Value =
         serde_json::from_str(&stdout).map_err(|e| format!("Parse error: {e}"))?;
 
-    Ok(VideoDescription {
-        title: data["title"].as_str().unwrap_or("").to_string(),
-        description: data["description"]
-            .as_str()
-            .unwrap_or("No description available.")
-            .to_string(),
-        channel_name: data["channel"]
-            .as_str()
-            .or(data["uploader"].as_str())
-            .unwrap_or("Unknown")
-            .to_string(),
-    })
+    let mut comments = parse_comments(&data);
+    comments.truncate(limit as usize);
+    Ok(comments)
+}
+
+/// Comments from a `--dump-json` object, with each reply placed directly after
+/// its thread's other replies under the top-level comment it answers.
+fn parse_comments(data: &serde_json::Value) -> Vec<Comment> {
+    let items = match data["comments"].as_array() {
+        Some(items) => items,
+        None => return Vec::new(),
+    };
+
+    let to_comment = |item: &serde_json::Value, is_reply: bool| Comment {
+        id: item["id"].as_str().unwrap_or("").to_string(),
+        author: item["author"].as_str().unwrap_or("Unknown").to_string(),
+        text: item["text"].as_str().unwrap_or("").trim().to_string(),
+        like_count: item["like_count"].as_u64(),
+        timestamp: item["timestamp"].as_i64(),
+        is_reply,
+        is_uploader: item["author_is_uploader"].as_bool().unwrap_or(false),
+    };
+
+    let parent_of = |item: &serde_json::Value| -> String {
+        item["parent"].as_str().unwrap_or("root").to_string()
+    };
+
+    let mut comments = Vec::new();
+    for root in items.iter().filter(|item| parent_of(item) == "root") {
+        let root_id = root["id"].as_str().unwrap_or("");
+        comments.push(to_comment(root, false));
+        comments.extend(
+            items
+                .iter()
+                .filter(|item| !root_id.is_empty() && parent_of(item) == root_id)
+                .map(|item| to_comment(item, true)),
+        );
+    }
+    comments
 }
 
 // ── Stream URL ─────────────────────────────────────────────
 
-pub async fn get_stream_url(video_url: &str) -> Result<Vec<String>, String> {
-    let output = Command::new("yt-dlp")
-        .args([
-            "-f",
-            "bestvideo[height<=1080]+bestaudio/best[height<=1080]/best",
-            "-g",
-            "--no-warnings",
-            video_url,
-        ])
-        .output()
+/// Resolve direct media URLs for `format_selector`. Returns one URL for muxed
+/// formats, or video then audio when the selector picks separate streams.
+pub async fn get_stream_url(
+    video_url: &str,
+    format_selector: &str,
+) -> Result<Vec<String>, String> {
+    get_stream_url_with(runner::global(), video_url, format_selector).await
+}
+
+pub async fn get_stream_url_with(
+    runner: &runner::Runner,
+    video_url: &str,
+    format_selector: &str,
+) -> Result<Vec<String>, String> {
+    let output = runner
+        .output(
+            &["-f", format_selector, "-g", "--no-warnings", video_url],
+            Duration::from_secs(60),
+        )
         .await
         .map_err(|e| format!("Failed to get stream URL: {e}"))?;
 
-    if !output.status.success() {
+    if !output.success {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("yt-dlp error: {}", stderr.trim()));
+        return Err(format!("yt-dlp error: {}", doctor::explain(&stderr)));
     }
 
     Ok(String::from_utf8_lossy(&output.stdout)
@@ -447,53 +889,6 @@ pub async fn get_stream_url(video_url: &str) -> Result<Vec<String>, String> {
 
 // ── Priming ────────────────────────────────────────────────
 
-async fn fetch_with_retry(
-    args: &[&str],
-    max_retries: usize,
-    base_delay_ms: u64,
-) -> Result<String, String> {
-    let mut last_error = String::new();
-
-    for attempt in 0..max_retries {
-        match timeout(
-            Duration::from_secs(60),
-            Command::new("yt-dlp").args(args).output(),
-        )
-        .await
-        {
-            Ok(Ok(output)) if output.status.success() => {
-                return Ok(String::from_utf8_lossy(&output.stdout).to_string());
-            }
-            Ok(Ok(output)) => {
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                let is_throttled = stderr.contains("429")
-                    || stderr.contains("Too Many Requests")
-                    || stderr.contains("rate limit");
-
-                if attempt < max_retries - 1 && is_throttled {
-                    let delay = base_delay_ms * 2u64.pow(attempt as u32);
-                    tokio::time::sleep(Duration::from_millis(delay)).await;
-                    last_error = stderr;
-                    continue;
-                }
-                return Err(stderr);
-            }
-            Ok(Err(e)) => return Err(format!("Process error: {e}")),
-            Err(_) => {
-                if attempt < max_retries - 1 {
-                    let delay = base_delay_ms * 2u64.pow(attempt as u32);
-                    tokio::time::sleep(Duration::from_millis(delay)).await;
-                    last_error = "Timeout".to_string();
-                    continue;
-                }
-                return Err("Timed out".to_string());
-            }
-        }
-    }
-
-    Err(last_error)
-}
-
 fn parse_flat_playlist_video(
     data: &serde_json::Value,
     channel_id: &str,
@@ -504,10 +899,15 @@ fn parse_flat_playlist_video(
         return None;
     }
     let title = data["title"].as_str().unwrap_or("").to_string();
-
-    let upload_date = data["upload_date"]
-        .as_str()
-        .and_then(|s| parse_date_yyyymmdd(s))
+    let live_status = data["live_status"].as_str().and_then(LiveStatus::parse);
+
+    // Upcoming streams and premieres are dated by their scheduled start
+    let scheduled = data["release_timestamp"]
+        .as_i64()
+        .filter(|_| live_status == Some(LiveStatus::Upcoming))
+        .and_then(|ts| DateTime::from_timestamp(ts, 0));
+    let upload_date = scheduled
+        .or_else(|| data["upload_date"].as_str().and_then(|s| parse_date_yyyymmdd(s)))
         .or_else(|| {
             data["release_timestamp"]
                 .as_i64()
@@ -516,12 +916,13 @@ fn parse_flat_playlist_video(
         });
 
     let duration = data["duration"].as_i64().or(data["duration"].as_f64().map(|f| f as i64));
-    let is_short = duration.map(|d| d <= 60).unwrap_or(false)
-        || data["webpage_url"]
-            .as_str()
-            .or(data["url"].as_str())
-            .map(|u| u.contains("/shorts/"))
-            .unwrap_or(false);
+    // Length says little since Shorts can run up to three minutes; only a
+    // `/shorts/` URL is trusted here and the rest is settled by `shorts`.
+    let is_short = data["webpage_url"]
+        .as_str()
+        .or(data["url"].as_str())
+        .map(|u| u.contains("/shorts/"))
+        .unwrap_or(false);
 
     let relative_date = upload_date
         .map(|d| get_relative_date(d))
@@ -544,51 +945,164 @@ fn parse_flat_playlist_video(
                 format!("https://www.youtube.com/watch?v={}", id)
             }),
         is_short,
-        channel_name: Some(channel_name.to_string()),
-        channel_id: Some(channel_id.to_string()),
+        channel_name: Some(
+            data["channel"]
+                .as_str()
+                .or(data["uploader"].as_str())
+                .unwrap_or(channel_name)
+                .to_string(),
+        ),
+        channel_id: Some(data["channel_id"].as_str().unwrap_or(channel_id).to_string()),
         published_date: upload_date,
         stored_at: None,
         relative_date,
         duration,
         duration_string: Some(duration_string),
         view_count,
+        live_status,
+        thumbnail: pick_thumbnail(data),
     })
 }
 
+/// Playlists are listed as-is; channel URLs are pointed at their uploads tab.
+fn prime_url(url: &str) -> String {
+    if playlist_id(url).is_some() {
+        url.to_string()
+    } else {
+        channel_tab_url(url, ChannelTab::Videos)
+    }
+}
+
+/// The playlist of a channel's members-only uploads, which YouTube keeps
+/// under the channel id with `UUMO` in place of `UC`.
+fn members_url(channel_id: &str) -> Option<String> {
+    channel_id
+        .strip_prefix("UC")
+        .map(|rest| format!("https://www.youtube.com/playlist?list=UUMO{}", rest))
+}
+
+/// List a channel's uploads, and with `members` its members-only videos too.
 pub async fn prime_channel<F>(
     channel_id: &str,
     channel_name: &str,
     channel_url: &str,
+    members: bool,
     existing_ids: &HashSet<String>,
     on_progress: F,
 ) -> Result<PrimeResult, String>
 where
     F: Fn(PrimeProgress) + Send + Sync + 'static,
 {
-    let mut url = channel_url.to_string();
-    if !url.contains("/videos") {
-        url = url.trim_end_matches('/').to_string() + "/videos";
+    prime_channel_with(
+        runner::global(),
+        channel_id,
+        channel_name,
+        channel_url,
+        members,
+        existing_ids,
+        on_progress,
+    )
+    .await
+}
+
+async fn prime_channel_with<F>(
+    runner: &runner::Runner,
+    channel_id: &str,
+    channel_name: &str,
+    channel_url: &str,
+    members: bool,
+    existing_ids: &HashSet<String>,
+    on_progress: F,
+) -> Result<PrimeResult, String>
+where
+    F: Fn(PrimeProgress),
+{
+    let url = prime_url(channel_url);
+    let mut result = prime_listing(runner, channel_id, channel_name, &url, existing_ids, &on_progress).await?;
+    let Some(url) = members_url(channel_id).filter(|_| members) else {
+        return Ok(result);
+    };
+
+    // Members-only videos count on top of the uploads already listed
+    let base = (result.total, result.added, result.skipped);
+    let progress = |p: PrimeProgress| {
+        on_progress(PrimeProgress {
+            scanned: base.0 + p.scanned,
+            new: base.1 + p.new,
+            skipped: base.2 + p.skipped,
+        })
+    };
+    match prime_listing(runner, channel_id, channel_name, &url, existing_ids, &progress).await {
+        Ok(extra) => {
+            result.added += extra.added;
+            result.total += extra.total;
+            result.skipped += extra.skipped;
+            result.videos.extend(extra.videos);
+        }
+        Err(e) => result.members_error = Some(e),
+    }
+    Ok(result)
+}
+
+/// One listing, retried while rate limited.
+async fn prime_listing<F>(
+    runner: &runner::Runner,
+    channel_id: &str,
+    channel_name: &str,
+    url: &str,
+    existing_ids: &HashSet<String>,
+    on_progress: &F,
+) -> Result<PrimeResult, String>
+where
+    F: Fn(PrimeProgress),
+{
+    let mut attempt = 0;
+    loop {
+        let result =
+            prime_attempt(channel_id, channel_name, url, existing_ids, on_progress, runner).await;
+        match result {
+            Err(stderr) if runner.report(&stderr) && attempt < runner.config().retries => {
+                attempt += 1;
+            }
+            Err(stderr) if stderr.trim().is_empty() => {
+                return Err("yt-dlp failed to fetch videos".to_string());
+            }
+            Err(stderr) => return Err(format!("yt-dlp failed: {}", doctor::explain(&stderr))),
+            Ok(result) => {
+                runner.succeeded();
+                return Ok(result);
+            }
+        }
     }
+}
+
+/// One listing of a channel's uploads under a runner slot. Fails with yt-dlp's
+/// error output when nothing could be listed.
+async fn prime_attempt<F>(
+    channel_id: &str,
+    channel_name: &str,
+    url: &str,
+    existing_ids: &HashSet<String>,
+    on_progress: &F,
+    runner: &runner::Runner,
+) -> Result<PrimeResult, String>
+where
+    F: Fn(PrimeProgress),
+{
+    let _permit = runner.acquire().await;
 
     // Stream yt-dlp output line-by-line for real-time progress
-    let mut child = Command::new("yt-dlp")
-        .args([
-            "--flat-playlist",
-            "--dump-json",
-            "--no-warnings",
-            "--extractor-args",
-            "youtube:skip=dash,hls",
-            "--playlist-end",
-            "5000",
-            &url,
-        ])
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to run yt-dlp: {e}"))?;
-
-    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-    let mut reader = BufReader::new(stdout).lines();
+    let process = runner.spawn(&[
+        "--flat-playlist",
+        "--dump-json",
+        "--no-warnings",
+        "--extractor-args",
+        "youtube:skip=dash,hls",
+        "--playlist-end",
+        "5000",
+        url,
+    ])?;
+    let mut reader = process.stdout.lines();
 
     let mut scanned = 0usize;
     let mut skipped = 0usize;
@@ -621,9 +1135,9 @@ where
         });
     }
 
-    let status = child.wait().await.map_err(|e| format!("Process error: {e}"))?;
-    if !status.success() && scanned == 0 {
-        return Err("yt-dlp failed to fetch videos".to_string());
+    let exit = process.exit.await?;
+    if !exit.success && scanned == 0 {
+        return Err(exit.stderr);
     }
 
     Ok(PrimeResult {
@@ -632,9 +1146,126 @@ where
         skipped,
         failed: 0,
         videos: all_videos,
+        members_error: None,
+    })
+}
+
+// ── Channel Tabs ───────────────────────────────────────────
+
+const TAB_LIMIT: &str = "200";
+
+/// A channel URL pointed at `tab`, replacing any tab suffix it already has.
+fn channel_tab_url(channel_url: &str, tab: ChannelTab) -> String {
+    let mut base = channel_url.trim().trim_end_matches('/');
+    for t in ChannelTab::ALL {
+        if let Some(stripped) = base.strip_suffix(&format!("/{}", t.suffix())) {
+            base = stripped;
+            break;
+        }
+    }
+    format!("{}/{}", base, tab.suffix())
+}
+
+async fn flat_playlist_entries(url: &str) -> Result<Vec<serde_json::Value>, String> {
+    let output = runner::global()
+        .output(
+            &[
+                "--flat-playlist",
+                "--dump-json",
+                "--no-warnings",
+                "--playlist-end",
+                TAB_LIMIT,
+                url,
+            ],
+            Duration::from_secs(60),
+        )
+        .await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let entries: Vec<serde_json::Value> = stdout
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+    if entries.is_empty() && !output.success {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("yt-dlp failed: {}", doctor::explain(&stderr)));
+    }
+    Ok(entries)
+}
+
+fn parse_playlist_entry(data: &serde_json::Value) -> Option<PlaylistEntry> {
+    let id = data["id"].as_str().filter(|s| !s.is_empty())?.to_string();
+    let url = data["url"]
+        .as_str()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| format!("https://www.youtube.com/playlist?list={}", id));
+    Some(PlaylistEntry {
+        title: data["title"].as_str().unwrap_or(&id).to_string(),
+        video_count: data["playlist_count"].as_u64(),
+        id,
+        url,
     })
 }
 
+/// Videos on one of a channel's video tabs (uploads, shorts or past streams).
+pub async fn get_channel_tab_videos(
+    channel: &Subscription,
+    tab: ChannelTab,
+) -> Result<Vec<Video>, String> {
+    let entries = flat_playlist_entries(&channel_tab_url(&channel.url, tab)).await?;
+    Ok(entries
+        .iter()
+        .filter_map(|d| parse_flat_playlist_video(d, &channel.id, &channel.name))
+        .map(|mut v| {
+            v.is_short |= tab == ChannelTab::Shorts;
+            v
+        })
+        .collect())
+}
+
+/// Playlists listed on a channel's Playlists or Podcasts tab.
+pub async fn get_channel_playlists(
+    channel: &Subscription,
+    tab: ChannelTab,
+) -> Result<Vec<PlaylistEntry>, String> {
+    let entries = flat_playlist_entries(&channel_tab_url(&channel.url, tab)).await?;
+    Ok(entries.iter().filter_map(parse_playlist_entry).collect())
+}
+
+/// List the streams tab of each channel to pick up live, upcoming and
+/// finished streams, as many at once as the runner allows. Channels that
+/// fail are left out.
+pub async fn refresh_live_channels(channels: &[Subscription]) -> Vec<(String, Vec<Video>)> {
+    let handles: Vec<_> = channels
+        .iter()
+        .cloned()
+        .map(|channel| {
+            tokio::spawn(async move {
+                let videos = get_channel_tab_videos(&channel, ChannelTab::Live).await;
+                (channel.id, videos)
+            })
+        })
+        .collect();
+    let mut results = Vec::new();
+    for handle in handles {
+        if let Ok((id, Ok(videos))) = handle.await {
+            results.push((id, videos));
+        }
+    }
+    results
+}
+
+pub async fn get_playlist_videos(
+    playlist: &PlaylistEntry,
+    channel: &Subscription,
+) -> Result<Vec<Video>, String> {
+    let entries = flat_playlist_entries(&playlist.url).await?;
+    Ok(entries
+        .iter()
+        .filter_map(|d| parse_flat_playlist_video(d, &channel.id, &channel.name))
+        .collect())
+}
+
 fn parse_date_yyyymmdd(s: &str) -> Option<DateTime<Utc>> {
     if s.len() != 8 {
         return None;
@@ -650,6 +1281,169 @@ fn parse_date_yyyymmdd(s: &str) -> Option<DateTime<Utc>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::extractor::{Extractor, FixtureExtractor};
+    use crate::runner::{Runner, RunnerConfig};
+    use std::sync::{Arc, Mutex};
+
+    // ── classify_youtube_link tests ──────────────────────────
+
+    #[test]
+    fn test_classify_video_links() {
+        assert_eq!(
+            classify_youtube_link("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+            Some(YoutubeLink::Video { id: "dQw4w9WgXcQ".to_string(), start: None })
+        );
+        assert_eq!(
+            classify_youtube_link("https://youtu.be/dQw4w9WgXcQ?t=95"),
+            Some(YoutubeLink::Video { id: "dQw4w9WgXcQ".to_string(), start: Some(95) })
+        );
+        assert_eq!(
+            classify_youtube_link("https://www.youtube.com/watch?list=PL1&v=dQw4w9WgXcQ&t=42s"),
+            Some(YoutubeLink::Video { id: "dQw4w9WgXcQ".to_string(), start: Some(42) })
+        );
+        assert!(matches!(
+            classify_youtube_link("https://youtube.com/shorts/dQw4w9WgXcQ"),
+            Some(YoutubeLink::Video { .. })
+        ));
+    }
+
+    #[test]
+    fn test_classify_channel_links() {
+        assert_eq!(
+            classify_youtube_link("https://www.youtube.com/@rustlang/videos"),
+            Some(YoutubeLink::Channel("https://www.youtube.com/@rustlang".to_string()))
+        );
+        assert_eq!(
+            classify_youtube_link("https://youtube.com/channel/UCaYhcUwRBNscFNUKTjgPFiA"),
+            Some(YoutubeLink::Channel(
+                "https://youtube.com/channel/UCaYhcUwRBNscFNUKTjgPFiA".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_classify_other_links() {
+        assert_eq!(classify_youtube_link("https://github.com/x/y"), None);
+        assert_eq!(classify_youtube_link("https://www.youtube.com/feed/trending"), None);
+    }
+
+    // ── extract_timestamps tests ─────────────────────────────
+
+    #[test]
+    fn test_extract_timestamps() {
+        let text = "0:00 Intro\n1:35 - Setup\nWiring (12:07)\n1:02:05 Wrap-up\nAgain at 1:35";
+        assert_eq!(
+            extract_timestamps(text),
+            vec![
+                (0, "Intro".to_string()),
+                (95, "Setup".to_string()),
+                (727, "Wiring".to_string()),
+                (3725, "Wrap-up".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_timestamps_skips_invalid_and_urls() {
+        let text = "Score was 3:75\nhttps://example.com/watch?t=1:23\nCall at 10:30:99";
+        assert!(extract_timestamps(text).is_empty());
+    }
+
+    // ── parse_video_description tests ────────────────────────
+
+    #[test]
+    fn test_parse_video_description_full() {
+        let data = serde_json::json!({
+            "title": "Building a TUI",
+            "channel": "Rustacean",
+            "description": "Code: https://github.com/x/y. Docs (https://docs.rs/ratatui) and https://github.com/x/y again",
+            "upload_date": "20240115",
+            "duration": 754.0,
+            "view_count": 120000,
+            "like_count": 4500,
+            "tags": ["rust", "tui"],
+            "categories": ["Science & Technology"],
+            "chapters": [{"start_time": 0.0, "end_time": 754.0, "title": "All"}]
+        });
+        let desc = parse_video_description(&data);
+        assert_eq!(desc.title, "Building a TUI");
+        assert_eq!(desc.channel_name, "Rustacean");
+        assert_eq!(desc.upload_date.as_deref(), Some("2024-01-15"));
+        assert_eq!(desc.duration, Some(754));
+        assert_eq!(desc.view_count, Some(120000));
+        assert_eq!(desc.like_count, Some(4500));
+        assert_eq!(desc.tags, vec!["rust", "tui"]);
+        assert_eq!(desc.categories, vec!["Science & Technology"]);
+        assert_eq!(desc.chapters.len(), 1);
+        assert_eq!(
+            desc.links,
+            vec!["https://github.com/x/y", "https://docs.rs/ratatui"]
+        );
+    }
+
+    #[test]
+    fn test_parse_video_description_sparse() {
+        let desc = parse_video_description(&serde_json::json!({"title": "t", "uploader": "u"}));
+        assert_eq!(desc.channel_name, "u");
+        assert_eq!(desc.description, "No description available.");
+        assert_eq!(desc.upload_date, None);
+        assert_eq!(desc.like_count, None);
+        assert!(desc.tags.is_empty());
+        assert!(desc.links.is_empty());
+    }
+
+    // ── parse_comments tests ─────────────────────────────────
+
+    #[test]
+    fn test_parse_comments_threads_replies() {
+        let data = serde_json::json!({
+            "comments": [
+                {"id": "a", "parent": "root", "author": "@one", "text": "First ", "like_count": 40, "timestamp": 1700000000},
+                {"id": "b", "parent": "root", "author": "@two", "text": "Second", "author_is_uploader": true},
+                {"id": "a.1", "parent": "a", "author": "@three", "text": "Reply to first"},
+                {"id": "b.1", "parent": "b", "author": "@four", "text": "Reply to second"},
+                {"id": "a.2", "parent": "a", "author": "@five", "text": "Another reply"}
+            ]
+        });
+        let comments = parse_comments(&data);
+        let ids: Vec<&str> = comments.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "a.1", "a.2", "b", "b.1"]);
+        assert_eq!(comments[0].text, "First");
+        assert_eq!(comments[0].like_count, Some(40));
+        assert!(!comments[0].is_reply);
+        assert!(comments[1].is_reply);
+        assert!(comments[3].is_uploader);
+    }
+
+    #[test]
+    fn test_parse_comments_missing() {
+        assert!(parse_comments(&serde_json::json!({"comments": null})).is_empty());
+        assert!(parse_comments(&serde_json::json!({})).is_empty());
+    }
+
+    // ── parse_chapters tests ─────────────────────────────────
+
+    #[test]
+    fn test_parse_chapters() {
+        let data = serde_json::json!({
+            "chapters": [
+                {"start_time": 95.0, "end_time": 300.0, "title": " Setup "},
+                {"start_time": 0.0, "end_time": 95.0, "title": "Intro"}
+            ]
+        });
+        let chapters = parse_chapters(&data);
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].title, "Intro");
+        assert_eq!(chapters[1].start, 95.0);
+        assert_eq!(chapters[1].end, 300.0);
+        assert_eq!(chapters[1].title, "Setup");
+    }
+
+    #[test]
+    fn test_parse_chapters_missing() {
+        assert!(parse_chapters(&serde_json::json!({"chapters": null})).is_empty());
+        assert!(parse_chapters(&serde_json::json!({})).is_empty());
+    }
 
     // ── is_valid_youtube_url tests ───────────────────────────
 
@@ -724,6 +1518,152 @@ mod tests {
         assert_eq!(sanitize_search_query(""), "");
     }
 
+    // ── Playlist tests ───────────────────────────────────────
+
+    #[test]
+    fn test_playlist_id() {
+        assert_eq!(
+            playlist_id("https://www.youtube.com/playlist?list=PLabc_123-x"),
+            Some("PLabc_123-x".to_string())
+        );
+        assert_eq!(
+            playlist_id("https://music.youtube.com/playlist?si=x&list=OLAK5uy"),
+            Some("OLAK5uy".to_string())
+        );
+        assert_eq!(playlist_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLabc"), None);
+        assert_eq!(playlist_id("https://www.youtube.com/@channel"), None);
+    }
+
+    #[test]
+    fn test_parse_playlist_info() {
+        let data = serde_json::json!({"id": "PLabc", "title": "Talks", "entries": []});
+        let info = parse_playlist_info(&data, "PLabc", "https://www.youtube.com/playlist?list=PLabc").unwrap();
+        assert_eq!(info.id, "PLabc");
+        assert_eq!(info.name, "Talks");
+        assert_eq!(info.kind, SourceKind::Playlist);
+        assert_eq!(info.to_subscription().kind, SourceKind::Playlist);
+
+        assert!(parse_playlist_info(&serde_json::json!({}), "PLabc", "u").is_err());
+    }
+
+    #[test]
+    fn test_feed_url() {
+        assert_eq!(
+            feed_url(SourceKind::Channel, "UC1"),
+            "https://www.youtube.com/feeds/videos.xml?channel_id=UC1"
+        );
+        assert_eq!(
+            feed_url(SourceKind::Playlist, "PL1"),
+            "https://www.youtube.com/feeds/videos.xml?playlist_id=PL1"
+        );
+    }
+
+    #[test]
+    fn test_prime_url() {
+        assert_eq!(prime_url("https://www.youtube.com/@chan/"), "https://www.youtube.com/@chan/videos");
+        assert_eq!(prime_url("https://www.youtube.com/@chan/videos"), "https://www.youtube.com/@chan/videos");
+        assert_eq!(
+            prime_url("https://www.youtube.com/playlist?list=PL1"),
+            "https://www.youtube.com/playlist?list=PL1"
+        );
+    }
+
+    // ── Channel tab tests ────────────────────────────────────
+
+    #[test]
+    fn test_channel_tab_url() {
+        assert_eq!(
+            channel_tab_url("https://www.youtube.com/@chan", ChannelTab::Live),
+            "https://www.youtube.com/@chan/streams"
+        );
+        assert_eq!(
+            channel_tab_url("https://www.youtube.com/@chan/videos/", ChannelTab::Playlists),
+            "https://www.youtube.com/@chan/playlists"
+        );
+        assert_eq!(
+            channel_tab_url("https://www.youtube.com/channel/UC1/shorts", ChannelTab::Shorts),
+            "https://www.youtube.com/channel/UC1/shorts"
+        );
+    }
+
+    #[test]
+    fn test_channel_tab_cycle() {
+        assert_eq!(ChannelTab::Videos.next(), ChannelTab::Shorts);
+        assert_eq!(ChannelTab::Playlists.next(), ChannelTab::Videos);
+        assert_eq!(ChannelTab::Videos.prev(), ChannelTab::Playlists);
+        assert!(ChannelTab::Podcasts.lists_playlists());
+        assert!(!ChannelTab::Live.lists_playlists());
+    }
+
+    #[test]
+    fn test_parse_playlist_entry() {
+        let data = serde_json::json!({
+            "_type": "url",
+            "id": "PLabc",
+            "title": "Conference talks",
+            "url": "https://www.youtube.com/playlist?list=PLabc",
+            "playlist_count": 12
+        });
+        assert_eq!(
+            parse_playlist_entry(&data),
+            Some(PlaylistEntry {
+                id: "PLabc".to_string(),
+                title: "Conference talks".to_string(),
+                url: "https://www.youtube.com/playlist?list=PLabc".to_string(),
+                video_count: Some(12),
+            })
+        );
+
+        let bare = parse_playlist_entry(&serde_json::json!({"id": "PLxyz"})).unwrap();
+        assert_eq!(bare.title, "PLxyz");
+        assert_eq!(bare.url, "https://www.youtube.com/playlist?list=PLxyz");
+        assert!(parse_playlist_entry(&serde_json::json!({"title": "no id"})).is_none());
+    }
+
+    // ── parse_flat_playlist_video tests ──────────────────────
+
+    #[test]
+    fn test_flat_playlist_video_live_status() {
+        let upcoming = serde_json::json!({
+            "id": "dQw4w9WgXcQ",
+            "title": "Launch stream",
+            "live_status": "is_upcoming",
+            "upload_date": "20240101",
+            "release_timestamp": 1893456000
+        });
+        let v = parse_flat_playlist_video(&upcoming, "ch1", "Chan").unwrap();
+        assert_eq!(v.live_status, Some(LiveStatus::Upcoming));
+        assert_eq!(v.published_date.unwrap().timestamp(), 1893456000);
+
+        let vod = serde_json::json!({"id": "abcdefghijk", "title": "VOD", "live_status": "was_live", "upload_date": "20240101"});
+        let v = parse_flat_playlist_video(&vod, "ch1", "Chan").unwrap();
+        assert_eq!(v.live_status, Some(LiveStatus::WasLive));
+        assert_eq!(v.published_date.unwrap().format("%Y-%m-%d").to_string(), "2024-01-01");
+
+        let plain = serde_json::json!({"id": "abcdefghijk", "title": "Upload"});
+        assert_eq!(parse_flat_playlist_video(&plain, "ch1", "Chan").unwrap().live_status, None);
+    }
+
+    #[test]
+    fn test_pick_thumbnail() {
+        let data = serde_json::json!({
+            "thumbnails": [
+                {"url": "https://i.ytimg.com/vi/x/default.jpg", "width": 120},
+                {"url": "https://i.ytimg.com/vi/x/hq720.jpg?sqp=abc", "width": 336},
+                {"url": "https://i.ytimg.com/vi_webp/x/mqdefault.webp", "width": 320},
+                {"url": "https://i.ytimg.com/vi/x/maxresdefault.jpg", "width": 1280}
+            ]
+        });
+        assert_eq!(
+            pick_thumbnail(&data).as_deref(),
+            Some("https://i.ytimg.com/vi/x/hq720.jpg?sqp=abc")
+        );
+        let single = serde_json::json!({"thumbnail": "https://i.ytimg.com/vi/x/hqdefault.jpg"});
+        assert_eq!(pick_thumbnail(&single).as_deref(), Some("https://i.ytimg.com/vi/x/hqdefault.jpg"));
+        let webp = serde_json::json!({"thumbnail": "https://i.ytimg.com/vi_webp/x/maxresdefault.webp"});
+        assert_eq!(pick_thumbnail(&webp), None);
+    }
+
     // ── extract_xml_tag tests ────────────────────────────────
 
     #[test]
@@ -797,6 +1737,40 @@ mod tests {
         assert_eq!(video.title, "Tom & Jerry");
     }
 
+    #[test]
+    fn test_parse_rss_entry_keeps_uploader() {
+        let entry = r#"
+            <yt:videoId>dQw4w9WgXcQ</yt:videoId>
+            <yt:channelId>UCuploader</yt:channelId>
+            <title>Guest Video</title>
+            <author>
+                <name>Rock &amp; Roll</name>
+                <uri>https://www.youtube.com/channel/UCuploader</uri>
+            </author>
+            <media:group>
+                <media:thumbnail url="https://i3.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg" width="480" height="360"/>
+            </media:group>
+        "#;
+        let video = parse_rss_entry(entry, "PLmix", "My Mix").unwrap();
+        assert_eq!(video.channel_id, Some("UCuploader".to_string()));
+        assert_eq!(video.channel_name, Some("Rock & Roll".to_string()));
+        assert_eq!(
+            video.thumbnail.as_deref(),
+            Some("https://i3.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg")
+        );
+    }
+
+    // ── fetch_all_channels_feeds tests ───────────────────────
+
+    #[tokio::test]
+    async fn test_joined_feed_reports_panics() {
+        let ok = tokio::spawn(async { Ok::<Vec<Video>, String>(Vec::new()) }).await;
+        assert!(joined_feed(ok).unwrap().is_empty());
+        let panicked = tokio::spawn(async { panic!("feed parser bug") }).await;
+        let err = joined_feed(panicked).unwrap_err();
+        assert!(err.starts_with("Feed fetch crashed"), "{}", err);
+    }
+
     // ── parse_rss_feed tests ─────────────────────────────────
 
     #[test]
@@ -871,4 +1845,166 @@ mod tests {
     fn test_parse_date_not_numbers() {
         assert!(parse_date_yyyymmdd("abcdefgh").is_none());
     }
+
+    // ── Fixture tests ────────────────────────────────────────
+
+    fn fixtures() -> FixtureExtractor {
+        FixtureExtractor::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ytdlp"))
+    }
+
+    fn fixture_runner(fake: impl Extractor + 'static) -> Runner {
+        let config = RunnerConfig {
+            backoff: Duration::from_millis(1),
+            requests_per_minute: 0,
+            ..RunnerConfig::default()
+        };
+        Runner::with_extractor(config, Box::new(fake))
+    }
+
+    #[tokio::test]
+    async fn test_search_youtube_from_fixture() {
+        let runner = fixture_runner(fixtures().route("ytsearch3:rust", "search_rust.jsonl"));
+        let videos = search_youtube_with(&runner, "rust", 3).await.unwrap();
+        assert_eq!(videos.len(), 3);
+        assert_eq!(videos[0].title, "Rust in 100 Seconds");
+        assert_eq!(videos[0].channel_name.as_deref(), Some("Fireship"));
+        assert_eq!(videos[0].duration_string.as_deref(), Some("2:29"));
+        assert!(videos[0].published_date.is_some());
+        assert_eq!(videos[1].channel_name.as_deref(), Some("Let's Get Rusty"));
+        assert_eq!(videos[1].duration_string.as_deref(), Some("1:00:00"));
+        assert_eq!(videos[2].live_status, Some(LiveStatus::Live));
+    }
+
+    #[tokio::test]
+    async fn test_search_youtube_failure() {
+        let runner = fixture_runner(fixtures());
+        let err = search_youtube_with(&runner, "nothing recorded", 5).await.unwrap_err();
+        assert!(err.starts_with("Search failed"), "{}", err);
+        assert!(search_youtube_with(&runner, "  ", 5).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_channel_info_from_fixture() {
+        let runner = fixture_runner(
+            fixtures()
+                .route("https://www.youtube.com/@fixture", "channel_info.json")
+                .route("https://www.youtube.com/playlist?list=PLfixture", "playlist_info.json"),
+        );
+        let info = get_channel_info_with(&runner, " https://www.youtube.com/@fixture ")
+            .await
+            .unwrap();
+        assert_eq!(info.id, "UCfixture0000000000000000");
+        assert_eq!(info.name, "Fixture Channel");
+        assert_eq!(info.url, "https://www.youtube.com/channel/UCfixture0000000000000000");
+        assert_eq!(info.kind, SourceKind::Channel);
+
+        let playlist = get_channel_info_with(&runner, "https://www.youtube.com/playlist?list=PLfixture")
+            .await
+            .unwrap();
+        assert_eq!(playlist.name, "Fixture Playlist");
+        assert_eq!(playlist.kind, SourceKind::Playlist);
+
+        let err = get_channel_info_with(&runner, "https://example.com/@x").await.unwrap_err();
+        assert_eq!(err, "Not a valid YouTube URL");
+    }
+
+    #[tokio::test]
+    async fn test_prime_channel_from_fixture() {
+        let runner = fixture_runner(
+            fixtures().route("https://www.youtube.com/@fixture/videos", "channel_videos.jsonl"),
+        );
+        let existing: HashSet<String> = ["bbbbbbbbbb3".to_string()].into_iter().collect();
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let seen = progress.clone();
+        let result = prime_channel_with(
+            &runner,
+            "UCfixture",
+            "Fixture Channel",
+            "https://www.youtube.com/@fixture",
+            false,
+            &existing,
+            move |p: PrimeProgress| seen.lock().unwrap().push(p.scanned),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.total, 3);
+        assert_eq!(result.added, 2);
+        assert_eq!(result.skipped, 1);
+        assert_eq!(result.videos[0].channel_id.as_deref(), Some("UCfixture"));
+        assert!(result.videos[0].published_date.is_some());
+        assert!(result.videos[1].is_short);
+        assert_eq!(*progress.lock().unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_prime_channel_with_members() {
+        let runner = fixture_runner(
+            fixtures()
+                .route("https://www.youtube.com/@fixture/videos", "channel_videos.jsonl")
+                .route("https://www.youtube.com/playlist?list=UUMOfixture", "members_videos.jsonl")
+                .route("https://www.youtube.com/@locked/videos", "channel_videos.jsonl")
+                .route("https://www.youtube.com/playlist?list=UUMOlocked", "members_only.err"),
+        );
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let seen = progress.clone();
+        let result = prime_channel_with(
+            &runner,
+            "UCfixture",
+            "Fixture Channel",
+            "https://www.youtube.com/@fixture",
+            true,
+            &HashSet::new(),
+            move |p: PrimeProgress| seen.lock().unwrap().push(p.scanned),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.total, 4);
+        assert_eq!(result.added, 4);
+        assert_eq!(result.videos[3].title, "Members-only Q&A");
+        assert!(result.members_error.is_none());
+        assert_eq!(progress.lock().unwrap().last(), Some(&4));
+
+        let locked = prime_channel_with(
+            &runner,
+            "UClocked",
+            "Locked",
+            "https://www.youtube.com/@locked",
+            true,
+            &HashSet::new(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(locked.added, 3);
+        assert!(locked.members_error.unwrap().contains("Members-only"));
+    }
+
+    #[tokio::test]
+    async fn test_prime_channel_retries_rate_limits() {
+        let fake = Arc::new(
+            fixtures().route("https://www.youtube.com/@limited/videos", "rate_limited.err"),
+        );
+        let runner = fixture_runner(fake.clone());
+        let err = prime_channel_with(
+            &runner,
+            "UClimited",
+            "Limited",
+            "https://www.youtube.com/@limited",
+            false,
+            &HashSet::new(),
+            |_| {},
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("429"), "{}", err);
+        // The first attempt and every retry reached yt-dlp
+        let retries = runner.config().retries as usize;
+        assert!(retries > 0);
+        assert_eq!(fake.calls().len(), retries + 1);
+        assert!(fake
+            .calls()
+            .iter()
+            .all(|call| call.iter().any(|a| a == "https://www.youtube.com/@limited/videos")));
+    }
 }